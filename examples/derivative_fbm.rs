@@ -0,0 +1,16 @@
+//! An example of using the derivative-damped fBm noise function
+
+extern crate noise;
+
+use noise::{utils::*, DerivativeFbm};
+
+fn main() {
+    let noise = DerivativeFbm::new();
+
+    PlaneMapBuilder::new(&noise)
+        .set_size(1000, 1000)
+        .set_x_bounds(-5.0, 5.0)
+        .set_y_bounds(-5.0, 5.0)
+        .build()
+        .write_to_file("derivative_fbm.png");
+}
@@ -0,0 +1,17 @@
+extern crate noise;
+
+use noise::{utils::*, Gabor};
+
+fn main() {
+    PlaneMapBuilder::new(&Gabor::default())
+        .build()
+        .write_to_file("gabor.png");
+
+    PlaneMapBuilder::new(&Gabor::default().set_orientation(45.0).set_bandwidth(0.25))
+        .build()
+        .write_to_file("gabor_narrowband.png");
+
+    PlaneMapBuilder::new(&Gabor::default().set_isotropic(true))
+        .build()
+        .write_to_file("gabor_isotropic.png");
+}
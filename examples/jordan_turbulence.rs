@@ -0,0 +1,16 @@
+//! An example of using the Jordan turbulence noise function
+
+extern crate noise;
+
+use noise::{utils::*, JordanTurbulence};
+
+fn main() {
+    let noise = JordanTurbulence::new();
+
+    PlaneMapBuilder::new(&noise)
+        .set_size(1000, 1000)
+        .set_x_bounds(-5.0, 5.0)
+        .set_y_bounds(-5.0, 5.0)
+        .build()
+        .write_to_file("jordan_turbulence.png");
+}
@@ -0,0 +1,41 @@
+//! An example of rendering normal, slope, curvature and ambient occlusion maps
+//! of a terrain height map
+
+extern crate noise;
+
+use noise::{utils::*, Fbm, MultiFractal};
+
+fn main() {
+    let fbm = Fbm::new().set_octaves(8);
+
+    let height_map = PlaneMapBuilder::new(&fbm)
+        .set_size(512, 512)
+        .set_x_bounds(-2.0, 2.0)
+        .set_y_bounds(-2.0, 2.0)
+        .set_is_seamless(true)
+        .build();
+
+    NormalMapRenderer::new()
+        .set_height_scale(32.0)
+        .enable_wrap()
+        .render(&height_map)
+        .write_to_file("terrain_normal_map.png");
+
+    SlopeRenderer::new()
+        .set_height_scale(32.0)
+        .enable_wrap()
+        .render(&height_map)
+        .write_to_file("terrain_slope_map.png");
+
+    CurvatureRenderer::new()
+        .set_height_scale(32.0)
+        .enable_wrap()
+        .render(&height_map)
+        .write_to_file("terrain_curvature_map.png");
+
+    AmbientOcclusionRenderer::new()
+        .set_height_scale(32.0)
+        .enable_wrap()
+        .render(&height_map)
+        .write_to_file("terrain_ambient_occlusion_map.png");
+}
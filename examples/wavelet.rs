@@ -0,0 +1,29 @@
+extern crate noise;
+
+use noise::{utils::*, Wavelet, WaveletFbm};
+
+fn main() {
+    PlaneMapBuilder::new(&Wavelet::default())
+        .set_size(256, 256)
+        .set_x_bounds(-4.0, 4.0)
+        .set_y_bounds(-4.0, 4.0)
+        .build()
+        .write_to_file("wavelet.png");
+
+    let fbm = WaveletFbm::default().set_octaves(8);
+
+    PlaneMapBuilder::new(&fbm)
+        .set_size(256, 256)
+        .set_x_bounds(-4.0, 4.0)
+        .set_y_bounds(-4.0, 4.0)
+        .build()
+        .write_to_file("wavelet_fbm.png");
+
+    // Dropping the octaves finer than the pixels of the image.
+    PlaneMapBuilder::new(&fbm.set_footprint(8.0 / 256.0))
+        .set_size(256, 256)
+        .set_x_bounds(-4.0, 4.0)
+        .set_y_bounds(-4.0, 4.0)
+        .build()
+        .write_to_file("wavelet_fbm_filtered.png");
+}
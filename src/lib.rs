@@ -8,13 +8,43 @@
 //! let perlin = Perlin::new(1);
 //! let val = perlin.get([42.4, 37.7, 2.8]);
 //! ```
+//!
+//! # Determinism
+//!
+//! For a given seed and set of parameters, every generator outputs the same
+//! values on every platform, and across versions of the crate. A change to the
+//! output of a generator is treated as a breaking change, and the golden-value
+//! tests of each generator guard against accidental ones. The helpers in
+//! [`mix_seed`] and [`derive_seed`] are covered by the same guarantee.
+//!
+//! The guarantee relies on the operations the generators use being exactly
+//! specified by IEEE 754, such as `+`, `*`, `floor` and `sqrt`. A few other
+//! operations are not:
+//!
+//! - `mul_add` rounds once, but the fallback used when targeting emscripten
+//!   multiplies and adds separately, rounding twice;
+//! - `powi`, `powf`, `exp`, `sin`, `cos`, `atan`, `hypot` and `cbrt` defer to
+//!   the platform's math library, whose precision is unspecified.
+//!
+//! These are used by some of the fractals, modifiers, transformers and
+//! selectors, and by the map builders, renderers and analysis in `utils`, so
+//! their output can differ in the last few bits between platforms. Enabling
+//! the `strict` feature replaces them with portable implementations, making the
+//! output of every noise function and utility bit-for-bit reproducible, at a
+//! small cost in speed. Output with the `strict` feature enabled can differ
+//! from output without it.
 
 #![deny(missing_copy_implementations)]
 
-pub use crate::noise_fns::*;
+pub use crate::{
+    noise_fns::*,
+    permutationtable::{LargePermutationTable, NoiseHasher, PcgHasher, PermutationTable},
+    seed::*,
+};
 
 mod gradient;
 mod math;
 mod noise_fns;
 mod permutationtable;
+mod seed;
 pub mod utils;
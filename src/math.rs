@@ -253,37 +253,19 @@ where
     map4(x, cast)
 }
 
-/// f64 doesn't implement From<isize>
-#[inline]
-pub(crate) fn to_f64_2(x: [isize; 2]) -> [f64; 2] {
-    [x[0] as f64, x[1] as f64]
-}
-
-/// f64 doesn't implement From<isize>
-#[inline]
-pub(crate) fn to_f64_3(x: [isize; 3]) -> [f64; 3] {
-    [x[0] as f64, x[1] as f64, x[2] as f64]
-}
-
-/// f64 doesn't implement From<isize>
-#[inline]
-pub(crate) fn to_f64_4(x: [isize; 4]) -> [f64; 4] {
-    [x[0] as f64, x[1] as f64, x[2] as f64, x[3] as f64]
-}
-
 // isize doesn't implement From<f64>
 #[inline]
 pub(crate) fn to_isize2(x: [f64; 2]) -> [isize; 2] {
     [x[0] as isize, x[1] as isize]
 }
 
-#[cfg(not(target_os = "emscripten"))]
+#[cfg(not(any(target_os = "emscripten", feature = "strict")))]
 #[inline]
 pub(crate) fn scale_shift(value: f64, n: f64) -> f64 {
     value.abs().mul_add(n, -1.0_f64)
 }
 
-#[cfg(target_os = "emscripten")]
+#[cfg(any(target_os = "emscripten", feature = "strict"))]
 #[inline]
 pub(crate) fn scale_shift(value: f64, n: f64) -> f64 {
     (value.abs() * n) + -1.0_f64
@@ -298,3 +280,140 @@ pub(crate) fn to_isize3(x: [f64; 3]) -> [isize; 3] {
 pub(crate) fn to_isize4(x: [f64; 4]) -> [isize; 4] {
     [x[0] as isize, x[1] as isize, x[2] as isize, x[3] as isize]
 }
+
+/// Raises a value to an integer power.
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn powi(value: f64, n: i32) -> f64 {
+    value.powi(n)
+}
+
+/// Raises a value to an integer power by repeated squaring, as the precision
+/// of `f64::powi` is unspecified.
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn powi(mut value: f64, n: i32) -> f64 {
+    let mut exponent = n.unsigned_abs();
+    let mut result = 1.0;
+
+    while exponent != 0 {
+        if exponent & 1 == 1 {
+            result *= value;
+        }
+        exponent >>= 1;
+        value *= value;
+    }
+
+    if n < 0 {
+        1.0 / result
+    } else {
+        result
+    }
+}
+
+/// Raises a value to a floating point power.
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn powf(value: f64, n: f64) -> f64 {
+    value.powf(n)
+}
+
+/// Raises a value to a floating point power, using the portable `libm`
+/// implementation instead of the platform's.
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn powf(value: f64, n: f64) -> f64 {
+    libm::pow(value, n)
+}
+
+/// Computes the sine of an angle in radians.
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn sin(value: f64) -> f64 {
+    value.sin()
+}
+
+/// Computes the sine of an angle in radians, using the portable `libm`
+/// implementation instead of the platform's.
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn sin(value: f64) -> f64 {
+    libm::sin(value)
+}
+
+/// Computes the cosine of an angle in radians.
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn cos(value: f64) -> f64 {
+    value.cos()
+}
+
+/// Computes the cosine of an angle in radians, using the portable `libm`
+/// implementation instead of the platform's.
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn cos(value: f64) -> f64 {
+    libm::cos(value)
+}
+
+/// Computes the arctangent of a value, in radians.
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn atan(value: f64) -> f64 {
+    value.atan()
+}
+
+/// Computes the arctangent of a value, in radians, using the portable `libm`
+/// implementation instead of the platform's.
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn atan(value: f64) -> f64 {
+    libm::atan(value)
+}
+
+/// Computes the length of the hypotenuse of a right-angle triangle with the
+/// given legs.
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+    x.hypot(y)
+}
+
+/// Computes the length of the hypotenuse of a right-angle triangle with the
+/// given legs, using the portable `libm` implementation instead of the
+/// platform's.
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+    libm::hypot(x, y)
+}
+
+/// Computes the cube root of a value.
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn cbrt(value: f64) -> f64 {
+    value.cbrt()
+}
+
+/// Computes the cube root of a value, using the portable `libm`
+/// implementation instead of the platform's.
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn cbrt(value: f64) -> f64 {
+    libm::cbrt(value)
+}
+
+/// Computes the exponential function.
+#[cfg(not(feature = "strict"))]
+#[inline]
+pub(crate) fn exp(value: f64) -> f64 {
+    value.exp()
+}
+
+/// Computes the exponential function, using the portable `libm`
+/// implementation instead of the platform's.
+#[cfg(feature = "strict")]
+#[inline]
+pub(crate) fn exp(value: f64) -> f64 {
+    libm::exp(value)
+}
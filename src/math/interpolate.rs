@@ -1,8 +1,9 @@
+#[cfg(not(any(target_os = "emscripten", feature = "strict")))]
 use num_traits::MulAdd;
 use std::ops::{Add, Mul, Sub};
 
 /// Performs linear interpolation between two values.
-#[cfg(not(target_os = "emscripten"))]
+#[cfg(not(any(target_os = "emscripten", feature = "strict")))]
 #[inline]
 pub(crate) fn linear<T>(a: T, b: T, x: T) -> T
 where
@@ -12,11 +13,11 @@ where
 }
 
 /// Performs linear interpolation between two values.
-#[cfg(target_os = "emscripten")]
+#[cfg(any(target_os = "emscripten", feature = "strict"))]
 #[inline]
 pub(crate) fn linear<T>(a: T, b: T, x: T) -> T
 where
-    T: MulAdd<Output = T> + Sub<Output = T> + Copy,
+    T: Add<Output = T> + Mul<Output = T> + Sub<Output = T> + Copy,
 {
     (x * (b - a)) + a
 }
@@ -44,3 +45,18 @@ where
     let s = n1;
     p * alpha * alpha * alpha + q * alpha * alpha + r * alpha + s
 }
+
+/// Performs Catmull-Rom spline interpolation between two values bound between
+/// two other values.
+///
+/// Unlike [`cubic`], the curve follows a straight line through evenly spaced
+/// values that lie on one, so it reproduces linear ramps exactly. The
+/// arguments are the same as those of [`cubic`].
+#[inline]
+pub(crate) fn catmull_rom(n0: f64, n1: f64, n2: f64, n3: f64, alpha: f64) -> f64 {
+    let p = -0.5 * n0 + 1.5 * n1 - 1.5 * n2 + 0.5 * n3;
+    let q = n0 - 2.5 * n1 + 2.0 * n2 - 0.5 * n3;
+    let r = 0.5 * (n2 - n0);
+    let s = n1;
+    ((p * alpha + q) * alpha + r) * alpha + s
+}
@@ -4,8 +4,9 @@ use std::cell::{Cell, RefCell};
 /// Noise function that caches the last output value generated by the source
 /// function.
 ///
-/// If the input coordinates passed to `Cache::get` are equal to the previous
-/// call, the function returns the cached result of the previous call to
+/// If the input coordinates and footprint passed to `Cache::get` or
+/// `Cache::get_with_footprint` are equal to those of the previous call, the
+/// function returns the cached result of the previous call to
 /// `Source::get`. Otherwise, `Source::get` is called with the new coordinates,
 /// overwriting the cache with the result, and returning the result to the
 /// caller.
@@ -22,6 +23,8 @@ pub struct Cache<Source> {
     value: Cell<Option<f64>>,
 
     point: RefCell<Vec<f64>>,
+
+    footprint: Cell<f64>,
 }
 
 impl<Source> Cache<Source> {
@@ -30,6 +33,7 @@ impl<Source> Cache<Source> {
             source,
             value: Cell::new(None),
             point: RefCell::new(Vec::new()),
+            footprint: Cell::new(0.0),
         }
     }
 }
@@ -39,11 +43,20 @@ where
     Source: NoiseFn<f64, DIM>,
 {
     fn get(&self, point: [f64; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; DIM], footprint: f64) -> f64 {
         match self.value.get() {
-            Some(value) if quick_eq(&*self.point.borrow(), &point) => value,
+            Some(value)
+                if self.footprint.get() == footprint && quick_eq(&*self.point.borrow(), &point) =>
+            {
+                value
+            }
             Some(_) | None => {
-                let value = self.source.get(point);
+                let value = self.source.get_with_footprint(point, footprint);
                 self.value.set(Some(value));
+                self.footprint.set(footprint);
 
                 let mut cached_point = self.point.borrow_mut();
                 cached_point.clear();
@@ -60,3 +73,19 @@ fn quick_eq(a: &[f64], b: &[f64]) -> bool {
 
     a.iter().eq(b)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::FootprintProbe;
+
+    #[test]
+    fn test_keyed_on_footprint() {
+        let cache = Cache::new(FootprintProbe);
+        let point = [0.5, 1.5, 2.5];
+
+        assert_eq!(cache.get_with_footprint(point, 0.25), 0.25);
+        assert_eq!(cache.get_with_footprint(point, 0.5), 0.5);
+        assert_eq!(cache.get(point), 0.0);
+    }
+}
@@ -1,7 +1,17 @@
-pub use self::{add::*, max::*, min::*, multiply::*, power::*};
+pub use self::{
+    add::*, average::*, combine::*, divide::*, max::*, min::*, multiply::*, power::*,
+    smooth_max::*, smooth_min::*, subtract::*, weighted_sum::*,
+};
 
 mod add;
+mod average;
+mod combine;
+mod divide;
 mod max;
 mod min;
 mod multiply;
 mod power;
+mod smooth_max;
+mod smooth_min;
+mod subtract;
+mod weighted_sum;
@@ -0,0 +1,41 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the mean of the output values from any number
+/// of source functions.
+///
+/// With no sources, the output is 0.0.
+#[derive(Clone, Debug)]
+pub struct Average<Source> {
+    /// Outputs the values to average.
+    pub sources: Vec<Source>,
+}
+
+impl<Source> Average<Source> {
+    pub fn new(sources: Vec<Source>) -> Self {
+        Self { sources }
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Average<Source>
+where
+    T: Copy,
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        if self.sources.is_empty() {
+            return 0.0;
+        }
+
+        let sum: f64 = self
+            .sources
+            .iter()
+            .map(|source| source.get_with_footprint(point, footprint))
+            .sum();
+
+        sum / self.sources.len() as f64
+    }
+}
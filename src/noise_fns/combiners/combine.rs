@@ -0,0 +1,59 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that combines the two output values from two source
+/// functions using an arbitrary closure.
+///
+/// # Example
+///
+/// ```rust
+/// use noise::{Combine, NoiseFn, Perlin};
+///
+/// let perlin1 = Perlin::new(1);
+/// let perlin2 = Perlin::new(2);
+/// let hypot = Combine::new(&perlin1, &perlin2, |a: f64, b: f64| a.hypot(b));
+///
+/// let val = hypot.get([1.3, 2.7]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Combine<Source1, Source2, F> {
+    /// Outputs the first argument of the closure.
+    pub source1: Source1,
+
+    /// Outputs the second argument of the closure.
+    pub source2: Source2,
+
+    /// Closure combining the two output values.
+    pub combiner: F,
+}
+
+impl<Source1, Source2, F> Combine<Source1, Source2, F>
+where
+    F: Fn(f64, f64) -> f64,
+{
+    pub fn new(source1: Source1, source2: Source2, combiner: F) -> Self {
+        Self {
+            source1,
+            source2,
+            combiner,
+        }
+    }
+}
+
+impl<T, Source1, Source2, F, const DIM: usize> NoiseFn<T, DIM> for Combine<Source1, Source2, F>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+    F: Fn(f64, f64) -> f64,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        (self.combiner)(
+            self.source1.get_with_footprint(point, footprint),
+            self.source2.get_with_footprint(point, footprint),
+        )
+    }
+}
@@ -0,0 +1,37 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the quotient of the two output values from two
+/// source functions, dividing that of `source1` by that of `source2`.
+///
+/// Where the output value of `source2` is zero, the output is infinite or NaN,
+/// following the usual floating point rules.
+#[derive(Clone, Debug)]
+pub struct Divide<Source1, Source2> {
+    /// Outputs the dividend.
+    pub source1: Source1,
+
+    /// Outputs the divisor.
+    pub source2: Source2,
+}
+
+impl<Source1, Source2> Divide<Source1, Source2> {
+    pub fn new(source1: Source1, source2: Source2) -> Self {
+        Self { source1, source2 }
+    }
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for Divide<Source1, Source2>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        self.source1.get_with_footprint(point, footprint)
+            / self.source2.get_with_footprint(point, footprint)
+    }
+}
@@ -2,25 +2,33 @@ use crate::noise_fns::NoiseFn;
 
 /// Noise function that outputs the smaller of the two output values from two source
 /// functions.
-pub struct Min<'a, T, const DIM: usize> {
+#[derive(Clone, Debug)]
+pub struct Min<Source1, Source2> {
     /// Outputs a value.
-    pub source1: &'a dyn NoiseFn<T, DIM>,
+    pub source1: Source1,
 
     /// Outputs a value.
-    pub source2: &'a dyn NoiseFn<T, DIM>,
+    pub source2: Source2,
 }
 
-impl<'a, T, const DIM: usize> Min<'a, T, DIM> {
-    pub fn new(source1: &'a dyn NoiseFn<T, DIM>, source2: &'a dyn NoiseFn<T, DIM>) -> Self {
+impl<Source1, Source2> Min<Source1, Source2> {
+    pub fn new(source1: Source1, source2: Source2) -> Self {
         Self { source1, source2 }
     }
 }
 
-impl<'a, T, const DIM: usize> NoiseFn<T, DIM> for Min<'a, T, DIM>
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for Min<Source1, Source2>
 where
     T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
 {
     fn get(&self, point: [T; DIM]) -> f64 {
-        (self.source1.get(point)).min(self.source2.get(point))
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        (self.source1.get_with_footprint(point, footprint))
+            .min(self.source2.get_with_footprint(point, footprint))
     }
 }
@@ -0,0 +1,54 @@
+use crate::noise_fns::{combiners::smooth_min::smooth_min, NoiseFn};
+
+/// Noise function that outputs the larger of the two output values from two
+/// source functions, rounding off the crease where they cross.
+///
+/// Within `radius` of each other, the two values are blended with a polynomial
+/// smooth maximum, so the output stays continuous in slope. Outside the radius
+/// the output equals that of [`Max`](super::Max).
+#[derive(Clone, Debug)]
+pub struct SmoothMax<Source1, Source2> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+
+    /// Difference between the two output values below which they are blended.
+    pub radius: f64,
+}
+
+impl<Source1, Source2> SmoothMax<Source1, Source2> {
+    pub const DEFAULT_RADIUS: f64 = 0.1;
+
+    pub fn new(source1: Source1, source2: Source2) -> Self {
+        Self {
+            source1,
+            source2,
+            radius: Self::DEFAULT_RADIUS,
+        }
+    }
+
+    pub fn set_radius(self, radius: f64) -> Self {
+        Self { radius, ..self }
+    }
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for SmoothMax<Source1, Source2>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        -smooth_min(
+            -self.source1.get_with_footprint(point, footprint),
+            -self.source2.get_with_footprint(point, footprint),
+            self.radius,
+        )
+    }
+}
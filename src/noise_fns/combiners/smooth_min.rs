@@ -0,0 +1,88 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the smaller of the two output values from two
+/// source functions, rounding off the crease where they cross.
+///
+/// Within `radius` of each other, the two values are blended with a polynomial
+/// smooth minimum, so the output stays continuous in slope. Outside the radius
+/// the output equals that of [`Min`](super::Min).
+#[derive(Clone, Debug)]
+pub struct SmoothMin<Source1, Source2> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+
+    /// Difference between the two output values below which they are blended.
+    pub radius: f64,
+}
+
+impl<Source1, Source2> SmoothMin<Source1, Source2> {
+    pub const DEFAULT_RADIUS: f64 = 0.1;
+
+    pub fn new(source1: Source1, source2: Source2) -> Self {
+        Self {
+            source1,
+            source2,
+            radius: Self::DEFAULT_RADIUS,
+        }
+    }
+
+    pub fn set_radius(self, radius: f64) -> Self {
+        Self { radius, ..self }
+    }
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for SmoothMin<Source1, Source2>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        smooth_min(
+            self.source1.get_with_footprint(point, footprint),
+            self.source2.get_with_footprint(point, footprint),
+            self.radius,
+        )
+    }
+}
+
+/// Polynomial smooth minimum of `a` and `b` with a blend radius of `k`.
+pub(crate) fn smooth_min(a: f64, b: f64, k: f64) -> f64 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+
+    let h = (0.5 + 0.5 * (b - a) / k).clamp(0.0, 1.0);
+
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn test_smooth_min() {
+        let low = Constant::new(-1.0);
+        let high = Constant::new(1.0);
+
+        let sharp = SmoothMin::new(&low, &high).set_radius(0.0);
+        assert_eq!(-1.0, sharp.get([0.0, 0.0]));
+
+        // Far apart compared to the radius, the output equals the minimum.
+        let far = SmoothMin::new(&low, &high).set_radius(1.0);
+        assert_eq!(-1.0, far.get([0.0, 0.0]));
+
+        // Equal values are pulled down by a quarter of the radius.
+        let equal = SmoothMin::new(&low, &low).set_radius(1.0);
+        assert_eq!(-1.25, equal.get([0.0, 0.0]));
+    }
+}
@@ -0,0 +1,34 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the difference of the two output values from two
+/// source functions, subtracting that of `source2` from that of `source1`.
+#[derive(Clone, Debug)]
+pub struct Subtract<Source1, Source2> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+}
+
+impl<Source1, Source2> Subtract<Source1, Source2> {
+    pub fn new(source1: Source1, source2: Source2) -> Self {
+        Self { source1, source2 }
+    }
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for Subtract<Source1, Source2>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        self.source1.get_with_footprint(point, footprint)
+            - self.source2.get_with_footprint(point, footprint)
+    }
+}
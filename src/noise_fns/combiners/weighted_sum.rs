@@ -0,0 +1,45 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the sum of the output values from any number of
+/// source functions, each multiplied by its own weight.
+#[derive(Clone, Debug)]
+pub struct WeightedSum<Source> {
+    /// Sources and the weights their output values are multiplied by.
+    pub sources: Vec<(Source, f64)>,
+}
+
+impl<Source> WeightedSum<Source> {
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+        }
+    }
+
+    pub fn add_source(mut self, source: Source, weight: f64) -> Self {
+        self.sources.push((source, weight));
+        self
+    }
+}
+
+impl<Source> Default for WeightedSum<Source> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for WeightedSum<Source>
+where
+    T: Copy,
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        self.sources
+            .iter()
+            .map(|(source, weight)| source.get_with_footprint(point, footprint) * weight)
+            .sum()
+    }
+}
@@ -0,0 +1,232 @@
+use crate::noise_fns::{
+    Abs, Affine, Cache, Clamp, Curve, Exponent, Fbm, Map, MapPoint, Negate, NoiseOperand,
+    RotatePoint, ScaleBias, ScalePoint, Terrace, Transform, TranslatePoint, Turbulence,
+};
+
+/// Extension trait for chaining modifiers and transformers onto a noise
+/// function.
+///
+/// Each method takes the noise function by value and wraps it in the
+/// corresponding noise function, so graphs can be built without nested
+/// references or intermediate bindings. The returned types have the usual
+/// `set_*` methods for the settings not covered by the arguments.
+///
+/// It is implemented for every noise function that implements
+/// [`NoiseOperand`], and for references to them. To use these methods on your
+/// own noise functions, implement the marker trait for them as well:
+///
+/// ```rust
+/// use noise::{NoiseFn, NoiseFnExt, NoiseOperand};
+///
+/// struct Ramp;
+///
+/// impl NoiseFn<f64, 2> for Ramp {
+///     fn get(&self, point: [f64; 2]) -> f64 {
+///         point[0]
+///     }
+/// }
+///
+/// impl NoiseOperand for Ramp {}
+///
+/// let ramp = Ramp.scale_bias(0.5, 0.5);
+/// assert_eq!(1.0, ramp.get([1.0, 0.0]));
+/// ```
+///
+/// The marker is required because implementing this trait for every type
+/// would make methods such as `map` and `clamp` ambiguous with those of
+/// [`Iterator`] and [`Ord`] wherever it is in scope.
+///
+/// # Example
+///
+/// ```rust
+/// use noise::{NoiseFn, NoiseFnExt, Perlin};
+///
+/// let hills = Perlin::new(1)
+///     .scale(0.5)
+///     .abs()
+///     .scale_bias(2.0, -1.0)
+///     .clamp(-0.5, 1.0)
+///     .cache();
+///
+/// let val = hills.get([1.3, 2.7, 0.4]);
+/// ```
+pub trait NoiseFnExt: Sized {
+    /// Wraps the noise function in [`Abs`].
+    fn abs(self) -> Abs<Self> {
+        Abs::new(self)
+    }
+
+    /// Wraps the noise function in [`Negate`].
+    fn negate(self) -> Negate<Self> {
+        Negate::new(self)
+    }
+
+    /// Wraps the noise function in [`Clamp`] with the given bounds.
+    fn clamp(self, lower_bound: f64, upper_bound: f64) -> Clamp<Self> {
+        Clamp::new(self).set_bounds(lower_bound, upper_bound)
+    }
+
+    /// Wraps the noise function in [`ScaleBias`] with the given scale and bias.
+    fn scale_bias(self, scale: f64, bias: f64) -> ScaleBias<Self> {
+        ScaleBias::new(self).set_scale(scale).set_bias(bias)
+    }
+
+    /// Wraps the noise function in [`Exponent`] with the given exponent.
+    fn exponent(self, exponent: f64) -> Exponent<Self> {
+        Exponent::new(self).set_exponent(exponent)
+    }
+
+    /// Wraps the noise function in [`Terrace`] with the given control points.
+    fn terrace(self, control_points: &[f64]) -> Terrace<Self> {
+        control_points
+            .iter()
+            .fold(Terrace::new(self), |terrace, &point| {
+                terrace.add_control_point(point)
+            })
+    }
+
+    /// Wraps the noise function in [`Curve`] with the given control points,
+    /// given as `(input, output)` pairs.
+    fn curve(self, control_points: &[(f64, f64)]) -> Curve<Self> {
+        control_points
+            .iter()
+            .fold(Curve::new(self), |curve, &(input, output)| {
+                curve.add_control_point(input, output)
+            })
+    }
+
+    /// Wraps the noise function in [`Map`], applying the closure to its output
+    /// values.
+    fn map<F>(self, map: F) -> Map<Self, F>
+    where
+        F: Fn(f64) -> f64,
+    {
+        Map::new(self, map)
+    }
+
+    /// Wraps the noise function in [`MapPoint`], applying the closure to the
+    /// input points.
+    fn map_point<F>(self, map: F) -> MapPoint<Self, F> {
+        MapPoint::new(self, map)
+    }
+
+    /// Wraps the noise function in [`TranslatePoint`] with the given
+    /// translations along each axis.
+    fn translate(self, x: f64, y: f64, z: f64, u: f64) -> TranslatePoint<Self> {
+        TranslatePoint::new(self).set_all_translations(x, y, z, u)
+    }
+
+    /// Wraps the noise function in [`RotatePoint`] with the given rotation
+    /// angles, in degrees.
+    fn rotate(self, x: f64, y: f64, z: f64, u: f64) -> RotatePoint<Self> {
+        RotatePoint::new(self).set_angles(x, y, z, u)
+    }
+
+    /// Wraps the noise function in [`ScalePoint`], scaling all coordinates of
+    /// the input points by the given factor.
+    fn scale(self, scale: f64) -> ScalePoint<Self> {
+        ScalePoint::new(self).set_scale(scale)
+    }
+
+    /// Wraps the noise function in [`Transform`] with the given affine
+    /// transformation.
+    fn transform<const DIM: usize>(self, affine: Affine<DIM>) -> Transform<Self, DIM> {
+        Transform::new(self, affine)
+    }
+
+    /// Wraps the noise function in [`Turbulence`] with the given frequency and
+    /// power.
+    fn turbulence(self, frequency: f64, power: f64) -> Turbulence<Self, Fbm> {
+        Turbulence::new(self)
+            .set_frequency(frequency)
+            .set_power(power)
+    }
+
+    /// Wraps the noise function in [`Cache`].
+    fn cache(self) -> Cache<Self> {
+        Cache::new(self)
+    }
+}
+
+impl<N: NoiseOperand> NoiseFnExt for N {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{NoiseFn, Perlin};
+
+    const POINTS: [[f64; 3]; 3] = [[0.3, 1.7, -2.2], [10.5, -4.25, 0.125], [-0.6, 0.05, 3.9]];
+
+    fn assert_same(a: impl NoiseFn<f64, 3>, b: impl NoiseFn<f64, 3>) {
+        for &point in &POINTS {
+            assert_eq!(a.get(point), b.get(point));
+        }
+    }
+
+    #[test]
+    fn test_modifiers() {
+        let source = Perlin::new(3);
+
+        assert_same(source.abs(), Abs::new(source));
+        assert_same(source.negate(), Negate::new(source));
+        assert_same(
+            source.clamp(-0.2, 0.4),
+            Clamp::new(source).set_bounds(-0.2, 0.4),
+        );
+        assert_same(
+            source.scale_bias(2.0, -0.5),
+            ScaleBias::new(source).set_scale(2.0).set_bias(-0.5),
+        );
+        assert_same(
+            source.exponent(3.0),
+            Exponent::new(source).set_exponent(3.0),
+        );
+        assert_same(
+            source.terrace(&[-1.0, 0.0, 1.0]),
+            Terrace::new(source)
+                .add_control_point(-1.0)
+                .add_control_point(0.0)
+                .add_control_point(1.0),
+        );
+        assert_same(
+            source.curve(&[(-1.0, -1.0), (-0.5, 0.0), (0.5, 0.2), (1.0, 1.0)]),
+            Curve::new(source)
+                .add_control_point(-1.0, -1.0)
+                .add_control_point(-0.5, 0.0)
+                .add_control_point(0.5, 0.2)
+                .add_control_point(1.0, 1.0),
+        );
+        assert_same(
+            source.map(|value| value * value),
+            Map::new(source, |value: f64| value * value),
+        );
+        assert_same(source.cache(), Cache::new(source));
+    }
+
+    #[test]
+    fn test_transformers() {
+        let source = Perlin::new(3);
+
+        assert_same(
+            source.map_point(|[x, y, z]: [f64; 3]| [y, z, x]),
+            MapPoint::new(source, |[x, y, z]: [f64; 3]| [y, z, x]),
+        );
+        assert_same(
+            source.translate(1.0, 2.0, 3.0, 4.0),
+            TranslatePoint::new(source).set_all_translations(1.0, 2.0, 3.0, 4.0),
+        );
+        assert_same(
+            source.rotate(10.0, 20.0, 30.0, 0.0),
+            RotatePoint::new(source).set_angles(10.0, 20.0, 30.0, 0.0),
+        );
+        assert_same(source.scale(0.5), ScalePoint::new(source).set_scale(0.5));
+        assert_same(
+            source.transform(Affine::from_euler_angles(10.0, 20.0, 30.0)),
+            Transform::new(source, Affine::from_euler_angles(10.0, 20.0, 30.0)),
+        );
+        assert_same(
+            source.turbulence(2.0, 0.25),
+            Turbulence::new(source).set_frequency(2.0).set_power(0.25),
+        );
+    }
+}
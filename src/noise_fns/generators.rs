@@ -1,15 +1,23 @@
 pub use self::{
-    checkerboard::*, constant::*, cylinders::*, fractals::*, open_simplex::*, perlin::*,
-    perlin_surflet::*, super_simplex::*, value::*, worley::*,
+    checkerboard::*, closure::*, constant::*, cylinders::*, fractals::*, gabor::*,
+    interpolation::*, open_simplex::*, perlin::*, perlin_surflet::*, super_simplex::*, value::*,
+    wavelet::*, worley::*,
 };
 
 mod checkerboard;
+mod closure;
 mod constant;
 mod cylinders;
 mod fractals;
+mod gabor;
+mod interpolation;
 mod open_simplex;
 mod perlin;
 mod perlin_surflet;
 mod super_simplex;
 mod value;
-mod worley;
+mod wavelet;
+pub(crate) mod worley;
+
+#[cfg(test)]
+mod golden;
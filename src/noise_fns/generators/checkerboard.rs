@@ -53,3 +53,36 @@ impl<const N: usize> NoiseFn<f64, N> for Checkerboard {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &Checkerboard::new(1),
+            &POINTS_2D,
+            &[1.0, -1.0, 1.0, 1.0, -1.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &Checkerboard::new(1),
+            &POINTS_3D,
+            &[1.0, -1.0, 1.0, 1.0, 1.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &Checkerboard::new(1),
+            &POINTS_4D,
+            &[1.0, -1.0, 1.0, -1.0, 1.0, -1.0],
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the value returned by a closure.
+///
+/// This is a convenient way to write small, one-off noise functions without
+/// declaring a new type. Use [`noise_fn`] to create one.
+#[derive(Clone, Copy, Debug)]
+pub struct Closure<F> {
+    /// Closure computing the output value from the input point.
+    pub function: F,
+}
+
+impl<F> Closure<F> {
+    pub fn new(function: F) -> Self {
+        Self { function }
+    }
+}
+
+impl<T, F, const DIM: usize> NoiseFn<T, DIM> for Closure<F>
+where
+    F: Fn([T; DIM]) -> f64,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        (self.function)(point)
+    }
+}
+
+/// Creates a noise function from a closure.
+///
+/// # Example
+///
+/// ```rust
+/// use noise::{noise_fn, NoiseFn};
+///
+/// let sphere = noise_fn(|[x, y, z]: [f64; 3]| (x * x + y * y + z * z).sqrt() - 1.0);
+///
+/// assert_eq!(sphere.get([2.0, 0.0, 0.0]), 1.0);
+/// ```
+pub fn noise_fn<F>(function: F) -> Closure<F> {
+    Closure::new(function)
+}
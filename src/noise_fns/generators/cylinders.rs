@@ -1,4 +1,4 @@
-use crate::noise_fns::NoiseFn;
+use crate::{math, noise_fns::NoiseFn};
 
 /// Noise function that outputs concentric cylinders.
 ///
@@ -38,7 +38,7 @@ impl<const N: usize> NoiseFn<f64, N> for Cylinders {
         let y = point[1] * self.frequency;
 
         // Calculate the distance of the point from the origin.
-        let dist_from_center = (x.powi(2) + y.powi(2)).sqrt();
+        let dist_from_center = (math::powi(x, 2) + math::powi(y, 2)).sqrt();
 
         let dist_from_smaller_sphere = dist_from_center - dist_from_center.floor();
         let dist_from_larger_sphere = 1.0 - dist_from_smaller_sphere;
@@ -48,3 +48,57 @@ impl<const N: usize> NoiseFn<f64, N> for Cylinders {
         1.0 - (nearest_dist * 4.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &Cylinders::new(),
+            &POINTS_2D,
+            &[
+                0.16227766016837952,
+                0.9866592510173575,
+                0.5781765840393405,
+                -0.9999334442613872,
+                0.14570631344577123,
+                0.9537575185557898,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &Cylinders::new(),
+            &POINTS_3D,
+            &[
+                0.16227766016837952,
+                0.9866592510173575,
+                0.5781765840393405,
+                -0.9999334442613872,
+                0.14570631344577123,
+                0.9537575185557898,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &Cylinders::new(),
+            &POINTS_4D,
+            &[
+                0.16227766016837952,
+                0.9866592510173575,
+                0.5781765840393405,
+                -0.9999334442613872,
+                0.14570631344577123,
+                0.9537575185557898,
+            ],
+        );
+    }
+}
@@ -1,14 +1,40 @@
-pub use self::{basicmulti::*, billow::*, fbm::*, hybridmulti::*, ridgedmulti::*};
+pub use self::{
+    anisotropic_fbm::*, anisotropic_ridgedmulti::*, basicmulti::*, billow::*, derivative_fbm::*,
+    fbm::*, hybridmulti::*, jordan_turbulence::*, octaves::Spectrum, ridgedmulti::*,
+    swiss_turbulence::*, wavelet_fbm::*,
+};
 
+// Declared first, so its macro is in scope in the anisotropic fractals.
+#[macro_use]
+mod anisotropy;
+
+mod anisotropic_fbm;
+mod anisotropic_ridgedmulti;
 mod basicmulti;
 mod billow;
+mod derivative_fbm;
 mod fbm;
 mod hybridmulti;
+mod jordan_turbulence;
+mod octaves;
 mod ridgedmulti;
+mod swiss_turbulence;
+mod wavelet_fbm;
 
-use crate::noise_fns::Perlin;
+use crate::{
+    math::interpolate,
+    mix_seed,
+    noise_fns::{Interpolation, Perlin},
+    permutationtable::NoiseHasher,
+};
 
 /// Trait for `MultiFractal` functions
+///
+/// # Implementing
+///
+/// The getters have no default implementations. Implementations written for
+/// earlier versions of this crate, which only required the setters, need to
+/// add them.
 pub trait MultiFractal {
     fn set_octaves(self, octaves: usize) -> Self;
 
@@ -17,12 +43,122 @@ pub trait MultiFractal {
     fn set_lacunarity(self, lacunarity: f64) -> Self;
 
     fn set_persistence(self, persistence: f64) -> Self;
+
+    fn octaves(&self) -> usize;
+
+    fn frequency(&self) -> f64;
+
+    fn lacunarity(&self) -> f64;
+
+    fn persistence(&self) -> f64;
 }
 
-fn build_sources(seed: u32, octaves: usize) -> Vec<Perlin> {
+fn build_sources<H: NoiseHasher>(
+    seed: u64,
+    octaves: usize,
+    interpolation: Interpolation,
+) -> Vec<Perlin<H>> {
     let mut sources = Vec::with_capacity(octaves);
     for x in 0..octaves {
-        sources.push(Perlin::new(seed + x as u32));
+        sources
+            .push(Perlin::with_hasher(mix_seed(seed, x as u64)).set_interpolation(interpolation));
     }
     sources
 }
+
+/// Returns the weight of an octave with the given frequency, in cycles per
+/// unit, when sampled with the given footprint.
+///
+/// The octave is faded out linearly as its frequency rises from the Nyquist
+/// limit of half a cycle per footprint to a full cycle per footprint, and is
+/// dropped beyond that. A footprint of zero keeps every octave.
+///
+/// Octaves are faded toward their expected value rather than toward zero, so
+/// that the mean output stays the same as the footprint grows. See
+/// [`fade_toward`].
+fn octave_fade(frequency: f64, footprint: f64) -> f64 {
+    (2.0 - 2.0 * frequency * footprint).clamp(0.0, 1.0)
+}
+
+/// Blends the signal of an octave toward its expected value by the weight
+/// returned by [`octave_fade`]. An octave that is not faded at all keeps its
+/// signal exactly.
+fn fade_toward(expected: f64, signal: f64, fade: f64) -> f64 {
+    if fade < 1.0 {
+        interpolate::linear(expected, signal, fade)
+    } else {
+        signal
+    }
+}
+
+/// Returns the mean absolute value and the mean square of the output of
+/// `Perlin` noise with the given number of dimensions and interpolation.
+///
+/// These were measured over many samples, and give the expected values that
+/// the octaves of fractals whose signals are not zero-mean fade toward.
+fn perlin_moments(dim: usize, interpolation: Interpolation) -> (f64, f64) {
+    match (dim, interpolation) {
+        (2, Interpolation::Linear) => (0.3257, 0.1750),
+        (2, Interpolation::Cubic) => (0.3339, 0.1775),
+        (2, Interpolation::Quintic) => (0.3457, 0.1847),
+        (3, Interpolation::Linear) => (0.2259, 0.0790),
+        (3, Interpolation::Cubic) => (0.2415, 0.0889),
+        (3, Interpolation::Quintic) => (0.2546, 0.0974),
+        (_, Interpolation::Linear) => (0.5322, 0.4122),
+        (_, Interpolation::Cubic) => (0.5121, 0.3920),
+        (_, Interpolation::Quintic) => (0.5066, 0.3855),
+    }
+}
+
+/// Returns the output value of an octave at the given point, along with its
+/// gradient there, estimated by central differences.
+///
+/// The octave is passed as a function of the point before its rotation and
+/// offset, so the gradient is taken along the axes of the input.
+fn value_and_gradient<F, const DIM: usize>(octave: F, point: [f64; DIM]) -> (f64, [f64; DIM])
+where
+    F: Fn([f64; DIM]) -> f64,
+{
+    const STEP: f64 = 1e-4;
+
+    let mut gradient = [0.0; DIM];
+    for (axis, slope) in gradient.iter_mut().enumerate() {
+        let mut ahead = point;
+        let mut behind = point;
+        ahead[axis] += STEP;
+        behind[axis] -= STEP;
+
+        *slope = (octave(ahead) - octave(behind)) / (2.0 * STEP);
+    }
+
+    (octave(point), gradient)
+}
+
+/// Asserts that the mean output of `noise` over a patch of the plane changes
+/// by at most `tolerance` as the footprint grows until every octave is faded
+/// out.
+#[cfg(test)]
+fn assert_stable_mean<N: crate::noise_fns::NoiseFn<f64, 2>>(noise: &N, tolerance: f64) {
+    let mean = |footprint| {
+        let mut sum = 0.0;
+        for y in 0..100 {
+            for x in 0..100 {
+                let point = [x as f64 * 0.517 + 0.1, y as f64 * 0.493 + 0.2];
+                sum += noise.get_with_footprint(point, footprint);
+            }
+        }
+        sum / 10_000.0
+    };
+
+    let sharp = mean(0.0);
+    for &footprint in &[0.2, 0.5, 1.0, 4.0] {
+        let faded = mean(footprint);
+        assert!(
+            (faded - sharp).abs() < tolerance,
+            "mean changed from {} to {} at footprint {}",
+            sharp,
+            faded,
+            footprint
+        );
+    }
+}
@@ -0,0 +1,474 @@
+use super::{
+    anisotropy::{stretch_point, Anisotropy},
+    octaves::OctaveTransforms,
+};
+use crate::{
+    math,
+    noise_fns::{Constant, Fbm, Interpolation, MultiFractal, NoiseFn, Perlin, Seedable, Spectrum},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+
+/// Noise function that outputs fBm noise whose features are stretched along a
+/// direction.
+///
+/// Each octave is sampled with its input compressed along the direction by a
+/// stretch factor, elongating the features of that octave along it by the same
+/// factor. The stretch can be set separately for each octave, so for example
+/// the coarse octaves can form long streaks while the fine detail stays
+/// isotropic. This is useful for wood grain, sediment layers, or dunes.
+///
+/// The direction can also vary across space: the output value of the
+/// direction control function, multiplied by the control angle, turns the
+/// direction within the _xy_ plane by that many degrees.
+///
+/// With a stretch of 1.0 the output is identical to that of [`Fbm`] with the
+/// same seed and settings.
+#[derive(Clone, Debug)]
+pub struct AnisotropicFbm<H = PermutationTable, C = Constant> {
+    /// Total number of frequency octaves to generate the noise with.
+    ///
+    /// The number of octaves control the _amount of detail_ in the noise
+    /// function. Adding more octaves increases the detail, with the drawback
+    /// of increasing the calculation time.
+    pub octaves: usize,
+
+    /// The number of cycles per unit length that the noise function outputs.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the frequency increases for
+    /// each successive octave in the noise function.
+    pub lacunarity: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave in the noise function.
+    pub persistence: f64,
+
+    seed: u64,
+    sources: Vec<Perlin<H>>,
+    interpolation: Interpolation,
+    spectrum: Spectrum,
+    transforms: OctaveTransforms,
+    scale_factor: f64,
+    anisotropy: Anisotropy<C>,
+}
+
+fn calc_scale_factor(
+    spectrum: &Spectrum,
+    persistence: f64,
+    lacunarity: f64,
+    octaves: usize,
+) -> f64 {
+    match spectrum {
+        Spectrum::Persistence => 1.0 - math::powi(persistence, octaves as i32),
+        _ => spectrum.total(octaves, persistence, lacunarity),
+    }
+}
+
+impl AnisotropicFbm {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = Fbm::DEFAULT_OCTAVE_COUNT;
+    pub const DEFAULT_FREQUENCY: f64 = Fbm::DEFAULT_FREQUENCY;
+    pub const DEFAULT_LACUNARITY: f64 = Fbm::DEFAULT_LACUNARITY;
+    pub const DEFAULT_PERSISTENCE: f64 = Fbm::DEFAULT_PERSISTENCE;
+    pub const DEFAULT_DIRECTION: [f64; 3] = [1.0, 0.0, 0.0];
+    pub const DEFAULT_STRETCH: f64 = 4.0;
+    pub const DEFAULT_CONTROL_ANGLE: f64 = 90.0;
+    pub const MAX_OCTAVES: usize = Fbm::MAX_OCTAVES;
+
+    pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> AnisotropicFbm<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points of its octaves.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            seed,
+            octaves: AnisotropicFbm::DEFAULT_OCTAVE_COUNT,
+            frequency: AnisotropicFbm::DEFAULT_FREQUENCY,
+            lacunarity: AnisotropicFbm::DEFAULT_LACUNARITY,
+            persistence: AnisotropicFbm::DEFAULT_PERSISTENCE,
+            sources: super::build_sources(
+                seed,
+                AnisotropicFbm::DEFAULT_OCTAVE_COUNT,
+                Interpolation::Quintic,
+            ),
+            interpolation: Interpolation::Quintic,
+            spectrum: Spectrum::Persistence,
+            transforms: OctaveTransforms::default(),
+            scale_factor: calc_scale_factor(
+                &Spectrum::Persistence,
+                AnisotropicFbm::DEFAULT_PERSISTENCE,
+                AnisotropicFbm::DEFAULT_LACUNARITY,
+                AnisotropicFbm::DEFAULT_OCTAVE_COUNT,
+            ),
+            anisotropy: Anisotropy::new(
+                AnisotropicFbm::DEFAULT_DIRECTION,
+                AnisotropicFbm::DEFAULT_STRETCH,
+                Constant::new(0.0),
+                AnisotropicFbm::DEFAULT_CONTROL_ANGLE,
+            ),
+        }
+    }
+}
+
+impl<H, C> AnisotropicFbm<H, C> {
+    /// Sets the curve used by every octave to blend between the corners of
+    /// each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self
+    where
+        H: NoiseHasher,
+    {
+        Self {
+            sources: self
+                .sources
+                .into_iter()
+                .map(|source| source.set_interpolation(interpolation))
+                .collect(),
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Sets how the amplitude of each octave is chosen.
+    ///
+    /// With any spectrum other than [`Spectrum::Persistence`], the result is
+    /// scaled by the sum of the amplitudes of all the octaves instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` is an empty list of amplitudes.
+    pub fn set_spectrum(self, spectrum: Spectrum) -> Self {
+        spectrum.assert_valid();
+
+        Self {
+            scale_factor: calc_scale_factor(
+                &spectrum,
+                self.persistence,
+                self.lacunarity,
+                self.octaves,
+            ),
+            spectrum,
+            ..self
+        }
+    }
+
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the amplitude of the given octave under the current spectrum.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        self.spectrum
+            .amplitude(octave, self.persistence, self.lacunarity)
+    }
+
+    /// Sets the angle, in degrees, by which the input of each octave is
+    /// rotated, starting with the coarsest. Octaves past the end of the list
+    /// use its last angle.
+    ///
+    /// Turning the octaves by different angles keeps the axis-aligned
+    /// artifacts of their lattices from lining up.
+    pub fn set_octave_rotations(self, rotations: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                rotations,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_rotations(&self) -> &[f64] {
+        &self.transforms.rotations
+    }
+
+    /// Sets the offset added to every coordinate of the input of each octave,
+    /// starting with the coarsest. Octaves past the end of the list use its
+    /// last offset.
+    pub fn set_octave_offsets(self, offsets: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                offsets,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_offsets(&self) -> &[f64] {
+        &self.transforms.offsets
+    }
+}
+
+impl_anisotropy_methods!(AnisotropicFbm {
+    octaves,
+    frequency,
+    lacunarity,
+    persistence,
+    seed,
+    sources,
+    interpolation,
+    spectrum,
+    transforms,
+    scale_factor,
+});
+
+impl Default for AnisotropicFbm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: NoiseHasher, C> MultiFractal for AnisotropicFbm<H, C> {
+    fn set_octaves(self, mut octaves: usize) -> Self {
+        if self.octaves == octaves {
+            return self;
+        }
+
+        octaves = octaves.clamp(1, AnisotropicFbm::MAX_OCTAVES);
+        Self {
+            octaves,
+            sources: super::build_sources(self.seed, octaves, self.interpolation),
+            scale_factor: calc_scale_factor(
+                &self.spectrum,
+                self.persistence,
+                self.lacunarity,
+                octaves,
+            ),
+            ..self
+        }
+    }
+
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self {
+            lacunarity,
+            scale_factor: calc_scale_factor(
+                &self.spectrum,
+                self.persistence,
+                lacunarity,
+                self.octaves,
+            ),
+            ..self
+        }
+    }
+
+    fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            scale_factor: calc_scale_factor(
+                &self.spectrum,
+                persistence,
+                self.lacunarity,
+                self.octaves,
+            ),
+            ..self
+        }
+    }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
+}
+
+impl<H: NoiseHasher, C> Seedable for AnisotropicFbm<H, C> {
+    fn set_seed(self, seed: u64) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            sources: super::build_sources(seed, self.octaves, self.interpolation),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// n-dimensional `AnisotropicFbm` noise
+impl<H, C, const DIM: usize> NoiseFn<f64, DIM> for AnisotropicFbm<H, C>
+where
+    H: NoiseHasher,
+    C: NoiseFn<f64, DIM>,
+    Perlin<H>: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; DIM], footprint: f64) -> f64 {
+        let mut result = 0.0;
+
+        let direction = self.anisotropy.direction(point);
+
+        for p in point.iter_mut() {
+            *p *= self.frequency;
+        }
+
+        for x in 0..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
+            // Get the signal from the stretched point.
+            // The octave's own rotation turns only its lattice, as it comes
+            // after the stretch.
+            let stretched = stretch_point(point, direction, self.anisotropy.stretch(x));
+            let mut signal = self.sources[x].get(self.transforms.apply(x, stretched));
+
+            // Scale the amplitude appropriately for this frequency.
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+            signal *= fade;
+
+            // Add the signal to the result.
+            result += signal;
+
+            // Increase the frequency for the next octave.
+            for p in point.iter_mut() {
+                *p *= self.lacunarity;
+            }
+        }
+
+        // Scale the result into the [-1,1] range
+        result / self.scale_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    #[test]
+    fn test_unit_stretch_matches_fbm() {
+        let fbm = Fbm::new().set_seed(7);
+        let anisotropic = AnisotropicFbm::new().set_seed(7).set_stretch(1.0);
+
+        for &point in &[[0.3, 1.7, -2.2], [10.5, -4.25, 0.125]] {
+            assert_eq!(fbm.get(point), anisotropic.get(point));
+        }
+    }
+
+    #[test]
+    fn test_octave_settings_match_fbm() {
+        let spectrum = Spectrum::Exponent(0.8);
+        let rotations = vec![0.0, 30.0, 75.0];
+        let fbm = Fbm::new()
+            .set_seed(7)
+            .set_spectrum(spectrum.clone())
+            .set_octave_rotations(rotations.clone());
+        let anisotropic = AnisotropicFbm::new()
+            .set_seed(7)
+            .set_stretch(1.0)
+            .set_spectrum(spectrum)
+            .set_octave_rotations(rotations);
+
+        let point = [0.3, 1.7, -2.2];
+        assert_eq!(fbm.get(point), anisotropic.get(point));
+    }
+
+    #[test]
+    #[should_panic(expected = "stretches must be positive")]
+    fn test_zero_stretch() {
+        let _ = AnisotropicFbm::new().set_octave_stretches(vec![2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_stretch_along_direction() {
+        // A huge stretch makes the noise nearly constant along the direction.
+        let noise = AnisotropicFbm::new()
+            .set_direction([0.0, 1.0, 0.0])
+            .set_stretch(1e6);
+
+        let a = noise.get([0.4, 0.0]);
+        let b = noise.get([0.4, 1.0]);
+        assert!((a - b).abs() < 1e-4);
+
+        // Turning the direction a quarter turn makes it vary along y again.
+        let turned = noise.set_direction_control(Constant::new(1.0));
+        let a = turned.get([0.4, 0.0]);
+        let b = turned.get([0.4, 1.0]);
+        assert!((a - b).abs() > 1e-4);
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &AnisotropicFbm::new().set_seed(42),
+            &POINTS_2D,
+            &[
+                -0.05526727980646457,
+                0.6575609762037576,
+                0.44638251906824983,
+                0.07012724017516238,
+                0.08529796251782021,
+                0.41325259140501774,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &AnisotropicFbm::new().set_seed(42),
+            &POINTS_3D,
+            &[
+                0.08809015092041855,
+                -0.4225476676275669,
+                -0.13535907762970092,
+                0.30707212482325325,
+                -0.1435504825727939,
+                0.5931006620989675,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &AnisotropicFbm::new().set_seed(42),
+            &POINTS_4D,
+            &[
+                0.10550611352431051,
+                1.1931324547157325,
+                -0.30799942390291496,
+                -0.18592264814955847,
+                0.4302938815559422,
+                0.9497015192896175,
+            ],
+        );
+    }
+}
@@ -0,0 +1,472 @@
+use super::{
+    anisotropy::{stretch_point, Anisotropy},
+    octaves::OctaveTransforms,
+};
+use crate::{
+    math::{self, scale_shift},
+    noise_fns::{
+        Constant, Interpolation, MultiFractal, NoiseFn, Perlin, RidgedMulti, Seedable, Spectrum,
+    },
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+
+/// Noise function that outputs ridged-multifractal noise whose features are
+/// stretched along a direction.
+///
+/// Each octave is sampled with its input compressed along the direction by a
+/// stretch factor, elongating the features of that octave along it by the same
+/// factor. The stretch can be set separately for each octave, so for example
+/// the coarse octaves can form long streaks while the fine detail stays
+/// isotropic. This is useful for dune crests, or ridges following a
+/// prevailing direction.
+///
+/// The direction can also vary across space: the output value of the
+/// direction control function, multiplied by the control angle, turns the
+/// direction within the _xy_ plane by that many degrees.
+///
+/// With a stretch of 1.0 the output is identical to that of [`RidgedMulti`]
+/// with the same seed and settings.
+#[derive(Clone, Debug)]
+pub struct AnisotropicRidgedMulti<H = PermutationTable, C = Constant> {
+    /// Total number of frequency octaves to generate the noise with.
+    ///
+    /// The number of octaves control the _amount of detail_ in the noise
+    /// function. Adding more octaves increases the detail, with the drawback
+    /// of increasing the calculation time.
+    pub octaves: usize,
+
+    /// The number of cycles per unit length that the noise function outputs.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the frequency increases for
+    /// each successive octave in the noise function.
+    pub lacunarity: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave in the noise function.
+    pub persistence: f64,
+
+    /// The attenuation to apply to the weight on each octave.
+    pub attenuation: f64,
+
+    seed: u64,
+    sources: Vec<Perlin<H>>,
+    interpolation: Interpolation,
+    spectrum: Spectrum,
+    transforms: OctaveTransforms,
+    anisotropy: Anisotropy<C>,
+}
+
+impl AnisotropicRidgedMulti {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = RidgedMulti::DEFAULT_OCTAVE_COUNT;
+    pub const DEFAULT_FREQUENCY: f64 = RidgedMulti::DEFAULT_FREQUENCY;
+    pub const DEFAULT_LACUNARITY: f64 = RidgedMulti::DEFAULT_LACUNARITY;
+    pub const DEFAULT_PERSISTENCE: f64 = RidgedMulti::DEFAULT_PERSISTENCE;
+    pub const DEFAULT_ATTENUATION: f64 = RidgedMulti::DEFAULT_ATTENUATION;
+    pub const DEFAULT_DIRECTION: [f64; 3] = [1.0, 0.0, 0.0];
+    pub const DEFAULT_STRETCH: f64 = 4.0;
+    pub const DEFAULT_CONTROL_ANGLE: f64 = 90.0;
+    pub const MAX_OCTAVES: usize = RidgedMulti::MAX_OCTAVES;
+
+    pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> AnisotropicRidgedMulti<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points of its octaves.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            seed,
+            octaves: AnisotropicRidgedMulti::DEFAULT_OCTAVE_COUNT,
+            frequency: AnisotropicRidgedMulti::DEFAULT_FREQUENCY,
+            lacunarity: AnisotropicRidgedMulti::DEFAULT_LACUNARITY,
+            persistence: AnisotropicRidgedMulti::DEFAULT_PERSISTENCE,
+            attenuation: AnisotropicRidgedMulti::DEFAULT_ATTENUATION,
+            sources: super::build_sources(
+                seed,
+                AnisotropicRidgedMulti::DEFAULT_OCTAVE_COUNT,
+                Interpolation::Quintic,
+            ),
+            interpolation: Interpolation::Quintic,
+            spectrum: Spectrum::Persistence,
+            transforms: OctaveTransforms::default(),
+            anisotropy: Anisotropy::new(
+                AnisotropicRidgedMulti::DEFAULT_DIRECTION,
+                AnisotropicRidgedMulti::DEFAULT_STRETCH,
+                Constant::new(0.0),
+                AnisotropicRidgedMulti::DEFAULT_CONTROL_ANGLE,
+            ),
+        }
+    }
+}
+
+impl<H, C> AnisotropicRidgedMulti<H, C> {
+    /// Sets the curve used by every octave to blend between the corners of
+    /// each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self
+    where
+        H: NoiseHasher,
+    {
+        Self {
+            sources: self
+                .sources
+                .into_iter()
+                .map(|source| source.set_interpolation(interpolation))
+                .collect(),
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    pub fn set_attenuation(self, attenuation: f64) -> Self {
+        Self {
+            attenuation,
+            ..self
+        }
+    }
+
+    /// Sets how the amplitude of each octave is chosen.
+    ///
+    /// With any spectrum other than [`Spectrum::Persistence`], the result is
+    /// scaled by the sum of the amplitudes of all the octaves instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` is an empty list of amplitudes.
+    pub fn set_spectrum(self, spectrum: Spectrum) -> Self {
+        spectrum.assert_valid();
+
+        Self { spectrum, ..self }
+    }
+
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the amplitude of the given octave under the current spectrum.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        self.spectrum
+            .amplitude(octave, self.persistence, self.lacunarity)
+    }
+
+    /// Sets the angle, in degrees, by which the input of each octave is
+    /// rotated, starting with the coarsest. Octaves past the end of the list
+    /// use its last angle.
+    ///
+    /// Turning the octaves by different angles keeps the axis-aligned
+    /// artifacts of their lattices from lining up.
+    pub fn set_octave_rotations(self, rotations: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                rotations,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_rotations(&self) -> &[f64] {
+        &self.transforms.rotations
+    }
+
+    /// Sets the offset added to every coordinate of the input of each octave,
+    /// starting with the coarsest. Octaves past the end of the list use its
+    /// last offset.
+    pub fn set_octave_offsets(self, offsets: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                offsets,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_offsets(&self) -> &[f64] {
+        &self.transforms.offsets
+    }
+
+    /// Returns the value that the sum of the octaves is scaled by.
+    fn scale(&self) -> f64 {
+        match self.spectrum {
+            Spectrum::Persistence => 2.0 - math::powi(0.5, self.octaves as i32 - 1),
+            _ => self
+                .spectrum
+                .total(self.octaves, self.persistence, self.lacunarity),
+        }
+    }
+}
+
+impl_anisotropy_methods!(AnisotropicRidgedMulti {
+    octaves,
+    frequency,
+    lacunarity,
+    persistence,
+    attenuation,
+    seed,
+    sources,
+    interpolation,
+    spectrum,
+    transforms,
+});
+
+impl Default for AnisotropicRidgedMulti {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: NoiseHasher, C> MultiFractal for AnisotropicRidgedMulti<H, C> {
+    fn set_octaves(self, mut octaves: usize) -> Self {
+        if self.octaves == octaves {
+            return self;
+        }
+
+        octaves = octaves.clamp(1, AnisotropicRidgedMulti::MAX_OCTAVES);
+        Self {
+            octaves,
+            sources: super::build_sources(self.seed, octaves, self.interpolation),
+            ..self
+        }
+    }
+
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self { lacunarity, ..self }
+    }
+
+    fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            ..self
+        }
+    }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
+}
+
+impl<H: NoiseHasher, C> Seedable for AnisotropicRidgedMulti<H, C> {
+    fn set_seed(self, seed: u64) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            sources: super::build_sources(seed, self.octaves, self.interpolation),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// n-dimensional `AnisotropicRidgedMulti` noise
+impl<H, C, const DIM: usize> NoiseFn<f64, DIM> for AnisotropicRidgedMulti<H, C>
+where
+    H: NoiseHasher,
+    C: NoiseFn<f64, DIM>,
+    Perlin<H>: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; DIM], footprint: f64) -> f64 {
+        let mut result = 0.0;
+        let mut weight = 1.0;
+
+        // The expected value of the ridges of each octave, before weighting.
+        let (abs_mean, square_mean) = super::perlin_moments(DIM, self.interpolation);
+        let expected = 1.0 - 2.0 * abs_mean + square_mean;
+
+        let direction = self.anisotropy.direction(point);
+
+        for p in point.iter_mut() {
+            *p *= self.frequency;
+        }
+
+        for x in 0..self.octaves {
+            // Fade out octaves finer than the footprint toward the expected
+            // value, skipping the sampling of those faded out entirely.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            let mut signal = if fade > 0.0 {
+                // Get the value from the stretched point.
+                // The octave's own rotation turns only its lattice, as it comes
+                // after the stretch.
+                let stretched = stretch_point(point, direction, self.anisotropy.stretch(x));
+                let signal = self.sources[x].get(self.transforms.apply(x, stretched));
+
+                // Make the ridges.
+                let ridge = 1.0 - signal.abs();
+
+                // Square the signal to increase the sharpness of the ridges.
+                ridge * ridge
+            } else {
+                expected
+            };
+            signal = super::fade_toward(expected, signal, fade);
+
+            // Apply the weighting from the previous octave to the signal.
+            signal *= weight;
+
+            // Weight successive contributions by the previous signal.
+            weight = signal / self.attenuation;
+
+            // Clamp the weight to [0,1] to prevent the result from diverging.
+            weight = weight.clamp(0.0, 1.0);
+
+            // Scale the amplitude appropriately for this frequency.
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+
+            // Add the signal to the result.
+            result += signal;
+
+            // Increase the frequency.
+            for p in point.iter_mut() {
+                *p *= self.lacunarity;
+            }
+        }
+
+        // Scale and shift the result into the [-1,1] range
+        scale_shift(result, 2.0 / self.scale())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    // `mul_add` is avoided when targeting emscripten or in strict mode, which
+    // changes the rounding of the final scaling.
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_2D: [f64; 6] = [
+        0.10832825873343863,
+        -0.9970856460852243,
+        -0.18067966047530057,
+        -0.3446545687457317,
+        0.018122143238008797,
+        -0.4360322661957688,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_2D: [f64; 6] = [
+        0.10832825873343865,
+        -0.9970856460852243,
+        -0.1806796604753006,
+        -0.34465456874573164,
+        0.01812214323800876,
+        -0.43603226619576885,
+    ];
+
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_3D: [f64; 6] = [
+        -0.6068381491690591,
+        -0.039102049902622366,
+        0.10270750506872088,
+        0.028567154660336033,
+        -0.5532325057099108,
+        -0.35581430282806326,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_3D: [f64; 6] = [
+        -0.6068381491690591,
+        -0.03910204990262234,
+        0.10270750506872095,
+        0.028567154660336103,
+        -0.5532325057099108,
+        -0.35581430282806326,
+    ];
+
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_4D: [f64; 6] = [
+        -0.2234442204780841,
+        -1.0,
+        0.12627299241304826,
+        -0.39374879682884456,
+        -0.06762593638104755,
+        -1.0,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_4D: [f64; 6] = [
+        -0.2234442204780841,
+        -1.0,
+        0.12627299241304835,
+        -0.39374879682884456,
+        -0.06762593638104752,
+        -1.0,
+    ];
+
+    #[test]
+    fn test_unit_stretch_matches_ridged_multi() {
+        let ridged = RidgedMulti::new().set_seed(7);
+        let anisotropic = AnisotropicRidgedMulti::new().set_seed(7).set_stretch(1.0);
+
+        for &point in &[[0.3, 1.7, -2.2], [10.5, -4.25, 0.125]] {
+            assert_eq!(ridged.get(point), anisotropic.get(point));
+        }
+    }
+
+    #[test]
+    fn test_footprint_mean() {
+        super::super::assert_stable_mean(&AnisotropicRidgedMulti::new(), 0.02);
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &AnisotropicRidgedMulti::new().set_seed(42),
+            &POINTS_2D,
+            &EXPECTED_2D,
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &AnisotropicRidgedMulti::new().set_seed(42),
+            &POINTS_3D,
+            &EXPECTED_3D,
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &AnisotropicRidgedMulti::new().set_seed(42),
+            &POINTS_4D,
+            &EXPECTED_4D,
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{math, noise_fns::NoiseFn};
+
+/// Implements the methods for the anisotropy settings on an anisotropic
+/// fractal, given the names of the fields it has besides `anisotropy`.
+macro_rules! impl_anisotropy_methods {
+    ($ty:ident { $($field:ident),* $(,)? }) => {
+        impl<H, C> $ty<H, C> {
+            /// Sets the direction along which the features are stretched. It
+            /// does not need to be normalized.
+            ///
+            /// 2-dimensional noise uses the _x_ and _y_ components only, and
+            /// 4-dimensional noise is not stretched along the _w_ axis.
+            pub fn set_direction(self, direction: [f64; 3]) -> Self {
+                Self {
+                    anisotropy: self.anisotropy.set_direction(direction),
+                    ..self
+                }
+            }
+
+            pub fn direction(&self) -> [f64; 3] {
+                self.anisotropy.direction
+            }
+
+            /// Sets the factor by which the features of every octave are
+            /// stretched.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `stretch` is not positive.
+            pub fn set_stretch(self, stretch: f64) -> Self {
+                self.set_octave_stretches(vec![stretch])
+            }
+
+            /// Sets the stretch factor of each octave, starting with the
+            /// coarsest. Octaves past the end of the list use its last
+            /// factor.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `stretches` is empty, or if any of them is not
+            /// positive.
+            pub fn set_octave_stretches(self, stretches: Vec<f64>) -> Self {
+                Self {
+                    anisotropy: self.anisotropy.set_stretches(stretches),
+                    ..self
+                }
+            }
+
+            pub fn octave_stretches(&self) -> &[f64] {
+                &self.anisotropy.stretches
+            }
+
+            /// Sets the noise function that turns the direction across space.
+            pub fn set_direction_control<D>(self, control: D) -> $ty<H, D> {
+                $ty {
+                    $($field: self.$field,)*
+                    anisotropy: self.anisotropy.set_control(control),
+                }
+            }
+
+            pub fn direction_control(&self) -> &C {
+                &self.anisotropy.control
+            }
+
+            /// Sets the angle, in degrees, by which the direction is turned
+            /// where the direction control function outputs 1.0.
+            pub fn set_control_angle(self, control_angle: f64) -> Self {
+                Self {
+                    anisotropy: self.anisotropy.set_control_angle(control_angle),
+                    ..self
+                }
+            }
+
+            pub fn control_angle(&self) -> f64 {
+                self.anisotropy.control_angle
+            }
+        }
+    };
+}
+
+/// Settings shared by the anisotropic fractals, describing how each octave is
+/// stretched.
+#[derive(Clone, Debug)]
+pub(super) struct Anisotropy<C> {
+    pub(super) direction: [f64; 3],
+    pub(super) stretches: Vec<f64>,
+    pub(super) control: C,
+    pub(super) control_angle: f64,
+}
+
+impl<C> Anisotropy<C> {
+    pub(super) fn new(direction: [f64; 3], stretch: f64, control: C, control_angle: f64) -> Self {
+        Self {
+            direction,
+            stretches: vec![stretch],
+            control,
+            control_angle,
+        }
+    }
+
+    pub(super) fn set_direction(self, direction: [f64; 3]) -> Self {
+        Self { direction, ..self }
+    }
+
+    /// Sets the stretch factor of each octave.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stretches` is empty, or if any of them is not positive.
+    pub(super) fn set_stretches(self, stretches: Vec<f64>) -> Self {
+        assert!(!stretches.is_empty(), "at least one stretch is required");
+        assert!(
+            stretches.iter().all(|&stretch| stretch > 0.0),
+            "stretches must be positive"
+        );
+
+        Self { stretches, ..self }
+    }
+
+    pub(super) fn set_control<D>(self, control: D) -> Anisotropy<D> {
+        Anisotropy {
+            direction: self.direction,
+            stretches: self.stretches,
+            control,
+            control_angle: self.control_angle,
+        }
+    }
+
+    pub(super) fn set_control_angle(self, control_angle: f64) -> Self {
+        Self {
+            control_angle,
+            ..self
+        }
+    }
+
+    /// Returns the stretch factor of the given octave. Octaves past the end
+    /// of the list use its last factor.
+    pub(super) fn stretch(&self, octave: usize) -> f64 {
+        self.stretches[octave.min(self.stretches.len() - 1)]
+    }
+
+    /// Computes the unit direction of the stretch at the given point, turned
+    /// within the _xy_ plane by the control function.
+    pub(super) fn direction<const DIM: usize>(&self, point: [f64; DIM]) -> [f64; DIM]
+    where
+        C: NoiseFn<f64, DIM>,
+    {
+        let mut direction = [0.0; DIM];
+        for (d, &value) in direction.iter_mut().zip(&self.direction) {
+            *d = value;
+        }
+
+        let angle = (self.control.get(point) * self.control_angle).to_radians();
+        if angle != 0.0 {
+            let (sin, cos) = (math::sin(angle), math::cos(angle));
+            let (x, y) = (direction[0], direction[1]);
+            direction[0] = x * cos - y * sin;
+            direction[1] = x * sin + y * cos;
+        }
+
+        let length = direction.iter().map(|d| d * d).sum::<f64>().sqrt();
+        if length > 0.0 {
+            for d in direction.iter_mut() {
+                *d /= length;
+            }
+        }
+
+        direction
+    }
+}
+
+/// Compresses the point along the unit direction by the stretch factor, so the
+/// features of noise sampled at the result are elongated along it.
+pub(super) fn stretch_point<const DIM: usize>(
+    mut point: [f64; DIM],
+    direction: [f64; DIM],
+    stretch: f64,
+) -> [f64; DIM] {
+    let along = point
+        .iter()
+        .zip(&direction)
+        .map(|(p, d)| p * d)
+        .sum::<f64>();
+    let offset = (1.0 / stretch - 1.0) * along;
+
+    for (p, d) in point.iter_mut().zip(&direction) {
+        *p += offset * d;
+    }
+
+    point
+}
@@ -1,6 +1,9 @@
-use crate::math;
-
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use super::octaves::OctaveTransforms;
+use crate::{
+    math,
+    noise_fns::{Interpolation, MultiFractal, NoiseFn, Perlin, Seedable, Spectrum},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
 
 /// Noise function that outputs heterogenous Multifractal noise.
 ///
@@ -13,7 +16,7 @@ use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
 /// not be as damped and thus will grow more jagged as iteration progresses.
 ///
 #[derive(Clone, Debug)]
-pub struct BasicMulti {
+pub struct BasicMulti<H = PermutationTable> {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -42,12 +45,15 @@ pub struct BasicMulti {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
-    seed: u32,
-    sources: Vec<Perlin>,
+    seed: u64,
+    sources: Vec<Perlin<H>>,
+    interpolation: Interpolation,
+    spectrum: Spectrum,
+    transforms: OctaveTransforms,
 }
 
 impl BasicMulti {
-    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_SEED: u64 = 0;
     pub const DEFAULT_OCTAVES: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 2.0;
     pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
@@ -55,15 +61,110 @@ impl BasicMulti {
     pub const MAX_OCTAVES: usize = 32;
 
     pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> BasicMulti<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points of its octaves.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            seed,
+            octaves: BasicMulti::DEFAULT_OCTAVES,
+            frequency: BasicMulti::DEFAULT_FREQUENCY,
+            lacunarity: BasicMulti::DEFAULT_LACUNARITY,
+            persistence: BasicMulti::DEFAULT_PERSISTENCE,
+            sources: super::build_sources(
+                seed,
+                BasicMulti::DEFAULT_OCTAVES,
+                Interpolation::Quintic,
+            ),
+            interpolation: Interpolation::Quintic,
+            spectrum: Spectrum::Persistence,
+            transforms: OctaveTransforms::default(),
+        }
+    }
+
+    /// Sets the curve used by every octave to blend between the corners of
+    /// each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            sources: self
+                .sources
+                .into_iter()
+                .map(|source| source.set_interpolation(interpolation))
+                .collect(),
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Sets how the amplitude of each octave is chosen.
+    ///
+    /// The result is scaled the same way whatever the spectrum, so amplitudes
+    /// larger than those of [`Spectrum::Persistence`] can take it beyond the
+    /// [-1, 1] range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` is an empty list of amplitudes.
+    pub fn set_spectrum(self, spectrum: Spectrum) -> Self {
+        spectrum.assert_valid();
+
+        Self { spectrum, ..self }
+    }
+
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the amplitude of the given octave under the current spectrum.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        self.spectrum
+            .amplitude(octave, self.persistence, self.lacunarity)
+    }
+
+    /// Sets the angle, in degrees, by which the input of each octave is
+    /// rotated, starting with the coarsest. Octaves past the end of the list
+    /// use its last angle.
+    ///
+    /// Turning the octaves by different angles keeps the axis-aligned
+    /// artifacts of their lattices from lining up.
+    pub fn set_octave_rotations(self, rotations: Vec<f64>) -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            octaves: Self::DEFAULT_OCTAVES,
-            frequency: Self::DEFAULT_FREQUENCY,
-            lacunarity: Self::DEFAULT_LACUNARITY,
-            persistence: Self::DEFAULT_PERSISTENCE,
-            sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVES),
+            transforms: OctaveTransforms {
+                rotations,
+                ..self.transforms
+            },
+            ..self
         }
     }
+
+    pub fn octave_rotations(&self) -> &[f64] {
+        &self.transforms.rotations
+    }
+
+    /// Sets the offset added to every coordinate of the input of each octave,
+    /// starting with the coarsest. Octaves past the end of the list use its
+    /// last offset.
+    pub fn set_octave_offsets(self, offsets: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                offsets,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_offsets(&self) -> &[f64] {
+        &self.transforms.offsets
+    }
 }
 
 impl Default for BasicMulti {
@@ -72,16 +173,16 @@ impl Default for BasicMulti {
     }
 }
 
-impl MultiFractal for BasicMulti {
+impl<H: NoiseHasher> MultiFractal for BasicMulti<H> {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
             return self;
         }
 
-        octaves = octaves.clamp(1, Self::MAX_OCTAVES);
+        octaves = octaves.clamp(1, BasicMulti::MAX_OCTAVES);
         Self {
             octaves,
-            sources: super::build_sources(self.seed, octaves),
+            sources: super::build_sources(self.seed, octaves, self.interpolation),
             ..self
         }
     }
@@ -100,43 +201,85 @@ impl MultiFractal for BasicMulti {
             ..self
         }
     }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
 }
 
-impl Seedable for BasicMulti {
-    fn set_seed(self, seed: u32) -> Self {
+impl<H: NoiseHasher> Seedable for BasicMulti<H> {
+    fn set_seed(self, seed: u64) -> Self {
         if self.seed == seed {
             return self;
         }
 
         Self {
             seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources(seed, self.octaves, self.interpolation),
             ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
 /// 2-dimensional `BasicMulti` noise
-impl NoiseFn<f64, 2> for BasicMulti {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
-        // First unscaled octave of function; later octaves are scaled.
+impl<H: NoiseHasher> NoiseFn<f64, 2> for BasicMulti<H> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 2], footprint: f64) -> f64 {
+        // First octave of function, which weights the later octaves. It is
+        // faded out like the others, and with it the whole function.
+        let fade = super::octave_fade(self.frequency, footprint);
+        if fade == 0.0 {
+            return 0.0;
+        }
+
         point = math::mul2(point, self.frequency);
-        let mut result = self.sources[0].get(point);
+        let mut result = self.sources[0].get(self.transforms.apply(0, point))
+            * self
+                .spectrum
+                .amplitude(0, self.persistence, self.lacunarity)
+            * fade;
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
             // Raise the spatial frequency.
             point = math::mul2(point, self.lacunarity);
 
             // Get noise value.
-            let mut signal = self.sources[x].get(point);
+            let mut signal = self.sources[x].get(self.transforms.apply(x, point));
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+            signal *= fade;
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
@@ -151,22 +294,48 @@ impl NoiseFn<f64, 2> for BasicMulti {
 }
 
 /// 3-dimensional `BasicMulti` noise
-impl NoiseFn<f64, 3> for BasicMulti {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
-        // First unscaled octave of function; later octaves are scaled.
+impl<H: NoiseHasher> NoiseFn<f64, 3> for BasicMulti<H> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 3], footprint: f64) -> f64 {
+        // First octave of function, which weights the later octaves. It is
+        // faded out like the others, and with it the whole function.
+        let fade = super::octave_fade(self.frequency, footprint);
+        if fade == 0.0 {
+            return 0.0;
+        }
+
         point = math::mul3(point, self.frequency);
-        let mut result = self.sources[0].get(point);
+        let mut result = self.sources[0].get(self.transforms.apply(0, point))
+            * self
+                .spectrum
+                .amplitude(0, self.persistence, self.lacunarity)
+            * fade;
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
             // Raise the spatial frequency.
             point = math::mul3(point, self.lacunarity);
 
             // Get noise value.
-            let mut signal = self.sources[x].get(point);
+            let mut signal = self.sources[x].get(self.transforms.apply(x, point));
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+            signal *= fade;
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
@@ -181,22 +350,48 @@ impl NoiseFn<f64, 3> for BasicMulti {
 }
 
 /// 4-dimensional `BasicMulti` noise
-impl NoiseFn<f64, 4> for BasicMulti {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
-        // First unscaled octave of function; later octaves are scaled.
+impl<H: NoiseHasher> NoiseFn<f64, 4> for BasicMulti<H> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 4], footprint: f64) -> f64 {
+        // First octave of function, which weights the later octaves. It is
+        // faded out like the others, and with it the whole function.
+        let fade = super::octave_fade(self.frequency, footprint);
+        if fade == 0.0 {
+            return 0.0;
+        }
+
         point = math::mul4(point, self.frequency);
-        let mut result = self.sources[0].get(point);
+        let mut result = self.sources[0].get(self.transforms.apply(0, point))
+            * self
+                .spectrum
+                .amplitude(0, self.persistence, self.lacunarity)
+            * fade;
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
             // Raise the spatial frequency.
             point = math::mul4(point, self.lacunarity);
 
             // Get noise value.
-            let mut signal = self.sources[x].get(point);
+            let mut signal = self.sources[x].get(self.transforms.apply(x, point));
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+            signal *= fade;
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
@@ -209,3 +404,82 @@ impl NoiseFn<f64, 4> for BasicMulti {
         result * 0.5
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    #[test]
+    fn test_spectrum() {
+        // Silencing every octave but the first leaves its source, scaled into
+        // the output range.
+        let basic = BasicMulti::new()
+            .set_seed(42)
+            .set_spectrum(Spectrum::Amplitudes(vec![1.0, 0.0]))
+            .set_octave_offsets(vec![0.5]);
+        let first = Perlin::new(crate::mix_seed(42, 0));
+        let point = [0.3, 1.7];
+        let frequency = BasicMulti::DEFAULT_FREQUENCY;
+        let expected = first.get([0.3 * frequency + 0.5, 1.7 * frequency + 0.5]);
+        assert_eq!(expected * 0.5, basic.get(point));
+    }
+
+    #[test]
+    fn test_footprint_fades_first_octave() {
+        let multi = BasicMulti::new().set_seed(42);
+        let point = [0.3, 1.7];
+
+        assert_eq!(multi.get_with_footprint(point, 0.0), multi.get(point));
+        assert_ne!(multi.get_with_footprint(point, 0.6), multi.get(point));
+        assert_eq!(multi.get_with_footprint(point, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &BasicMulti::new().set_seed(42),
+            &POINTS_2D,
+            &[
+                0.0,
+                0.23212915008246782,
+                -0.11593704512831254,
+                0.04994211338198491,
+                0.09846949773068193,
+                0.18937202172489,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &BasicMulti::new().set_seed(42),
+            &POINTS_3D,
+            &[
+                0.07167983665161846,
+                -0.2768627599025172,
+                -0.05950692892775925,
+                0.08530613315334595,
+                -0.1845260197246695,
+                0.13311291454532134,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &BasicMulti::new().set_seed(42),
+            &POINTS_4D,
+            &[
+                0.027892110350076414,
+                0.21477380352729217,
+                0.14942200759167415,
+                0.1437487032891148,
+                0.4071873868043542,
+                -0.030886550555306855,
+            ],
+        );
+    }
+}
@@ -1,6 +1,8 @@
+use super::octaves::OctaveTransforms;
 use crate::{
     math::{self, scale_shift},
-    noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable},
+    noise_fns::{Interpolation, MultiFractal, NoiseFn, Perlin, Seedable, Spectrum},
+    permutationtable::{NoiseHasher, PermutationTable},
 };
 
 /// Noise function that outputs "billowy" noise.
@@ -11,7 +13,7 @@ use crate::{
 /// function modifies each octave with an absolute-value function. See the
 /// documentation for fBm for more information.
 #[derive(Clone, Debug)]
-pub struct Billow {
+pub struct Billow<H = PermutationTable> {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -40,17 +42,28 @@ pub struct Billow {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
-    seed: u32,
-    sources: Vec<Perlin>,
+    seed: u64,
+    sources: Vec<Perlin<H>>,
+    interpolation: Interpolation,
+    spectrum: Spectrum,
+    transforms: OctaveTransforms,
     scale_factor: f64,
 }
 
-fn calc_scale_factor(persistence: f64, octaves: usize) -> f64 {
-    1.0 - persistence.powi(octaves as i32)
+fn calc_scale_factor(
+    spectrum: &Spectrum,
+    persistence: f64,
+    lacunarity: f64,
+    octaves: usize,
+) -> f64 {
+    match spectrum {
+        Spectrum::Persistence => 1.0 - math::powi(persistence, octaves as i32),
+        _ => spectrum.total(octaves, persistence, lacunarity),
+    }
 }
 
 impl Billow {
-    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_SEED: u64 = 0;
     pub const DEFAULT_OCTAVE_COUNT: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
     pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
@@ -58,16 +71,124 @@ impl Billow {
     pub const MAX_OCTAVES: usize = 32;
 
     pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> Billow<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points of its octaves.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            seed,
+            octaves: Billow::DEFAULT_OCTAVE_COUNT,
+            frequency: Billow::DEFAULT_FREQUENCY,
+            lacunarity: Billow::DEFAULT_LACUNARITY,
+            persistence: Billow::DEFAULT_PERSISTENCE,
+            sources: super::build_sources(
+                seed,
+                Billow::DEFAULT_OCTAVE_COUNT,
+                Interpolation::Quintic,
+            ),
+            interpolation: Interpolation::Quintic,
+            spectrum: Spectrum::Persistence,
+            transforms: OctaveTransforms::default(),
+            scale_factor: calc_scale_factor(
+                &Spectrum::Persistence,
+                Billow::DEFAULT_PERSISTENCE,
+                Billow::DEFAULT_LACUNARITY,
+                Billow::DEFAULT_OCTAVE_COUNT,
+            ),
+        }
+    }
+
+    /// Sets the curve used by every octave to blend between the corners of
+    /// each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            sources: self
+                .sources
+                .into_iter()
+                .map(|source| source.set_interpolation(interpolation))
+                .collect(),
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Sets how the amplitude of each octave is chosen.
+    ///
+    /// With any spectrum other than [`Spectrum::Persistence`], the result is
+    /// scaled by the sum of the amplitudes of all the octaves instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` is an empty list of amplitudes.
+    pub fn set_spectrum(self, spectrum: Spectrum) -> Self {
+        spectrum.assert_valid();
+
+        Self {
+            scale_factor: calc_scale_factor(
+                &spectrum,
+                self.persistence,
+                self.lacunarity,
+                self.octaves,
+            ),
+            spectrum,
+            ..self
+        }
+    }
+
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the amplitude of the given octave under the current spectrum.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        self.spectrum
+            .amplitude(octave, self.persistence, self.lacunarity)
+    }
+
+    /// Sets the angle, in degrees, by which the input of each octave is
+    /// rotated, starting with the coarsest. Octaves past the end of the list
+    /// use its last angle.
+    ///
+    /// Turning the octaves by different angles keeps the axis-aligned
+    /// artifacts of their lattices from lining up.
+    pub fn set_octave_rotations(self, rotations: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                rotations,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_rotations(&self) -> &[f64] {
+        &self.transforms.rotations
+    }
+
+    /// Sets the offset added to every coordinate of the input of each octave,
+    /// starting with the coarsest. Octaves past the end of the list use its
+    /// last offset.
+    pub fn set_octave_offsets(self, offsets: Vec<f64>) -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            octaves: Self::DEFAULT_OCTAVE_COUNT,
-            frequency: Self::DEFAULT_FREQUENCY,
-            lacunarity: Self::DEFAULT_LACUNARITY,
-            persistence: Self::DEFAULT_PERSISTENCE,
-            sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
-            scale_factor: calc_scale_factor(Self::DEFAULT_PERSISTENCE, Self::DEFAULT_OCTAVE_COUNT),
+            transforms: OctaveTransforms {
+                offsets,
+                ..self.transforms
+            },
+            ..self
         }
     }
+
+    pub fn octave_offsets(&self) -> &[f64] {
+        &self.transforms.offsets
+    }
 }
 
 impl Default for Billow {
@@ -76,17 +197,22 @@ impl Default for Billow {
     }
 }
 
-impl MultiFractal for Billow {
+impl<H: NoiseHasher> MultiFractal for Billow<H> {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
             return self;
         }
 
-        octaves = octaves.clamp(1, Self::MAX_OCTAVES);
+        octaves = octaves.clamp(1, Billow::MAX_OCTAVES);
         Self {
             octaves,
-            sources: super::build_sources(self.seed, octaves),
-            scale_factor: calc_scale_factor(self.persistence, octaves),
+            sources: super::build_sources(self.seed, octaves, self.interpolation),
+            scale_factor: calc_scale_factor(
+                &self.spectrum,
+                self.persistence,
+                self.lacunarity,
+                octaves,
+            ),
             ..self
         }
     }
@@ -96,53 +222,103 @@ impl MultiFractal for Billow {
     }
 
     fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
+        Self {
+            lacunarity,
+            scale_factor: calc_scale_factor(
+                &self.spectrum,
+                self.persistence,
+                lacunarity,
+                self.octaves,
+            ),
+            ..self
+        }
     }
 
     fn set_persistence(self, persistence: f64) -> Self {
         Self {
             persistence,
-            scale_factor: calc_scale_factor(persistence, self.octaves),
+            scale_factor: calc_scale_factor(
+                &self.spectrum,
+                persistence,
+                self.lacunarity,
+                self.octaves,
+            ),
             ..self
         }
     }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
 }
 
-impl Seedable for Billow {
-    fn set_seed(self, seed: u32) -> Self {
+impl<H: NoiseHasher> Seedable for Billow<H> {
+    fn set_seed(self, seed: u64) -> Self {
         if self.seed == seed {
             return self;
         }
 
         Self {
             seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources(seed, self.octaves, self.interpolation),
             ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
 /// 2-dimensional Billow noise
-impl NoiseFn<f64, 2> for Billow {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
+impl<H: NoiseHasher> NoiseFn<f64, 2> for Billow<H> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 2], footprint: f64) -> f64 {
         let mut result = 0.0;
 
+        // The expected value of the signal of each octave.
+        let expected = 2.0 * super::perlin_moments(2, self.interpolation).0 - 1.0;
+
         point = math::mul2(point, self.frequency);
 
         for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
-
-            // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = scale_shift(signal, 2.0);
+            // Fade out octaves finer than the footprint toward the expected
+            // value, skipping the sampling of those faded out entirely.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            let mut signal = if fade > 0.0 {
+                // Get the signal.
+                let signal = self.sources[x].get(self.transforms.apply(x, point));
+
+                // Take the abs of the signal, then scale and shift back to
+                // the [-1,1] range.
+                scale_shift(signal, 2.0)
+            } else {
+                expected
+            };
+            signal = super::fade_toward(expected, signal, fade);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
 
             // Add the signal to the result.
             result += signal;
@@ -157,22 +333,42 @@ impl NoiseFn<f64, 2> for Billow {
 }
 
 /// 3-dimensional Billow noise
-impl NoiseFn<f64, 3> for Billow {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
+impl<H: NoiseHasher> NoiseFn<f64, 3> for Billow<H> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 3], footprint: f64) -> f64 {
         let mut result = 0.0;
 
+        // The expected value of the signal of each octave.
+        let expected = 2.0 * super::perlin_moments(3, self.interpolation).0 - 1.0;
+
         point = math::mul3(point, self.frequency);
 
         for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
-
-            // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = scale_shift(signal, 2.0);
+            // Fade out octaves finer than the footprint toward the expected
+            // value, skipping the sampling of those faded out entirely.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            let mut signal = if fade > 0.0 {
+                // Get the signal.
+                let signal = self.sources[x].get(self.transforms.apply(x, point));
+
+                // Take the abs of the signal, then scale and shift back to
+                // the [-1,1] range.
+                scale_shift(signal, 2.0)
+            } else {
+                expected
+            };
+            signal = super::fade_toward(expected, signal, fade);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
 
             // Add the signal to the result.
             result += signal;
@@ -187,22 +383,42 @@ impl NoiseFn<f64, 3> for Billow {
 }
 
 /// 4-dimensional Billow noise
-impl NoiseFn<f64, 4> for Billow {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
+impl<H: NoiseHasher> NoiseFn<f64, 4> for Billow<H> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 4], footprint: f64) -> f64 {
         let mut result = 0.0;
 
+        // The expected value of the signal of each octave.
+        let expected = 2.0 * super::perlin_moments(4, self.interpolation).0 - 1.0;
+
         point = math::mul4(point, self.frequency);
 
         for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
-
-            // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = scale_shift(signal, 2.0);
+            // Fade out octaves finer than the footprint toward the expected
+            // value, skipping the sampling of those faded out entirely.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            let mut signal = if fade > 0.0 {
+                // Get the signal.
+                let signal = self.sources[x].get(self.transforms.apply(x, point));
+
+                // Take the abs of the signal, then scale and shift back to
+                // the [-1,1] range.
+                scale_shift(signal, 2.0)
+            } else {
+                expected
+            };
+            signal = super::fade_toward(expected, signal, fade);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
 
             // Add the signal to the output value.
             result += signal;
@@ -215,3 +431,62 @@ impl NoiseFn<f64, 4> for Billow {
         result / self.scale_factor
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &Billow::new().set_seed(42),
+            &POINTS_2D,
+            &[
+                -1.037417649747934,
+                -1.4152804600650952,
+                -1.044889493434151,
+                0.47755837206481144,
+                -0.3276127923287041,
+                -1.386470548058005,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &Billow::new().set_seed(42),
+            &POINTS_3D,
+            &[
+                -0.07106123485676315,
+                -1.1889812284551673,
+                -1.4051458016944411,
+                -1.3227677273088838,
+                -1.1190492375714396,
+                -1.5555591006589315,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &Billow::new().set_seed(42),
+            &POINTS_4D,
+            &[
+                -1.2609357350553814,
+                -0.8023473068377598,
+                -0.05048693100468921,
+                0.14869798730166167,
+                0.5817155852394775,
+                0.22832752903118989,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_footprint_mean() {
+        super::super::assert_stable_mean(&Billow::new().set_seed(1), 0.02);
+    }
+}
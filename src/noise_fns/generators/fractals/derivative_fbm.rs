@@ -0,0 +1,390 @@
+use super::octaves::OctaveTransforms;
+use crate::{
+    math,
+    noise_fns::{Interpolation, MultiFractal, NoiseFn, Perlin, Seedable, Spectrum},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+
+/// Noise function that outputs fBm noise damped by its own slope.
+///
+/// Each octave is weighted down by the steepness of the octaves summed before
+/// it, as measured by the accumulated gradient of their noise. Fine detail is
+/// therefore kept on flat ground, such as ridges, peaks and valley floors, and
+/// suppressed on steep slopes, giving terrain an eroded look without running
+/// an erosion simulation.
+///
+/// The gradient of each octave is estimated by sampling it at neighbouring
+/// points, so this noise function is several times slower than
+/// [`Fbm`](super::Fbm).
+#[derive(Clone, Debug)]
+pub struct DerivativeFbm<H = PermutationTable> {
+    /// Total number of frequency octaves to generate the noise with.
+    ///
+    /// The number of octaves control the _amount of detail_ in the noise
+    /// function. Adding more octaves increases the detail, with the drawback
+    /// of increasing the calculation time.
+    pub octaves: usize,
+
+    /// The number of cycles per unit length that the noise function outputs.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the frequency increases for
+    /// each successive octave in the noise function.
+    pub lacunarity: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave in the noise function.
+    pub persistence: f64,
+
+    /// How strongly the slope damps each octave. A damping of 0.0 gives plain
+    /// fBm, and larger values confine the detail to flatter ground.
+    pub damping: f64,
+
+    seed: u64,
+    sources: Vec<Perlin<H>>,
+    interpolation: Interpolation,
+    spectrum: Spectrum,
+    transforms: OctaveTransforms,
+}
+
+impl DerivativeFbm {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = 6;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+    pub const DEFAULT_DAMPING: f64 = 1.0;
+    pub const MAX_OCTAVES: usize = 32;
+
+    pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> DerivativeFbm<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points of its octaves.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            seed,
+            octaves: DerivativeFbm::DEFAULT_OCTAVE_COUNT,
+            frequency: DerivativeFbm::DEFAULT_FREQUENCY,
+            lacunarity: DerivativeFbm::DEFAULT_LACUNARITY,
+            persistence: DerivativeFbm::DEFAULT_PERSISTENCE,
+            damping: DerivativeFbm::DEFAULT_DAMPING,
+            sources: super::build_sources(
+                seed,
+                DerivativeFbm::DEFAULT_OCTAVE_COUNT,
+                Interpolation::Quintic,
+            ),
+            interpolation: Interpolation::Quintic,
+            spectrum: Spectrum::Persistence,
+            transforms: OctaveTransforms::default(),
+        }
+    }
+
+    pub fn set_damping(self, damping: f64) -> Self {
+        Self { damping, ..self }
+    }
+
+    /// Sets the curve used by every octave to blend between the corners of
+    /// each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            sources: self
+                .sources
+                .into_iter()
+                .map(|source| source.set_interpolation(interpolation))
+                .collect(),
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Sets how the amplitude of each octave is chosen.
+    ///
+    /// With any spectrum other than [`Spectrum::Persistence`], the result is
+    /// scaled by the sum of the amplitudes of all the octaves instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` is an empty list of amplitudes.
+    pub fn set_spectrum(self, spectrum: Spectrum) -> Self {
+        spectrum.assert_valid();
+
+        Self { spectrum, ..self }
+    }
+
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the amplitude of the given octave under the current spectrum.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        self.spectrum
+            .amplitude(octave, self.persistence, self.lacunarity)
+    }
+
+    /// Sets the angle, in degrees, by which the input of each octave is
+    /// rotated, starting with the coarsest. Octaves past the end of the list
+    /// use its last angle.
+    ///
+    /// Turning the octaves by different angles keeps the axis-aligned
+    /// artifacts of their lattices from lining up.
+    pub fn set_octave_rotations(self, rotations: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                rotations,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_rotations(&self) -> &[f64] {
+        &self.transforms.rotations
+    }
+
+    /// Sets the offset added to every coordinate of the input of each octave,
+    /// starting with the coarsest. Octaves past the end of the list use its
+    /// last offset.
+    pub fn set_octave_offsets(self, offsets: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                offsets,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_offsets(&self) -> &[f64] {
+        &self.transforms.offsets
+    }
+
+    /// Returns the value that the sum of the octaves is scaled by, which is
+    /// the same as for `Fbm`.
+    fn scale(&self) -> f64 {
+        match self.spectrum {
+            Spectrum::Persistence => 1.0 - math::powi(self.persistence, self.octaves as i32),
+            _ => self
+                .spectrum
+                .total(self.octaves, self.persistence, self.lacunarity),
+        }
+    }
+}
+
+impl Default for DerivativeFbm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: NoiseHasher> MultiFractal for DerivativeFbm<H> {
+    fn set_octaves(self, mut octaves: usize) -> Self {
+        if self.octaves == octaves {
+            return self;
+        }
+
+        octaves = octaves.clamp(1, DerivativeFbm::MAX_OCTAVES);
+        Self {
+            octaves,
+            sources: super::build_sources(self.seed, octaves, self.interpolation),
+            ..self
+        }
+    }
+
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self { lacunarity, ..self }
+    }
+
+    fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            ..self
+        }
+    }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
+}
+
+impl<H: NoiseHasher> Seedable for DerivativeFbm<H> {
+    fn set_seed(self, seed: u64) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            sources: super::build_sources(seed, self.octaves, self.interpolation),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// n-dimensional `DerivativeFbm` noise
+impl<H, const DIM: usize> NoiseFn<f64, DIM> for DerivativeFbm<H>
+where
+    H: NoiseHasher,
+    Perlin<H>: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; DIM], footprint: f64) -> f64 {
+        let mut result = 0.0;
+        let mut slope = [0.0; DIM];
+
+        for p in point.iter_mut() {
+            *p *= self.frequency;
+        }
+
+        for x in 0..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
+            // Get the signal and its gradient, and add the gradient to the
+            // slope of the octaves so far.
+            let (signal, gradient) = super::value_and_gradient(
+                |point| self.sources[x].get(self.transforms.apply(x, point)),
+                point,
+            );
+            for (s, g) in slope.iter_mut().zip(&gradient) {
+                *s += g * fade;
+            }
+
+            // Damp the signal by the steepness of the slope.
+            let steepness = slope.iter().map(|s| s * s).sum::<f64>();
+            let amplitude = self.octave_amplitude(x);
+
+            result += signal * amplitude * fade / (1.0 + self.damping * steepness);
+
+            // Increase the frequency for the next octave.
+            for p in point.iter_mut() {
+                *p *= self.lacunarity;
+            }
+        }
+
+        // Scale the result into the [-1,1] range
+        result / self.scale()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+    use crate::noise_fns::Fbm;
+
+    #[test]
+    fn test_no_damping_matches_fbm() {
+        let fbm = Fbm::new().set_seed(3);
+        let derivative = DerivativeFbm::new().set_seed(3).set_damping(0.0);
+
+        for &point in &[[0.3, 1.7], [-4.25, 10.5]] {
+            assert_eq!(fbm.get(point), derivative.get(point));
+        }
+    }
+
+    #[test]
+    fn test_settings_match_fbm() {
+        let fbm = Fbm::new()
+            .set_seed(3)
+            .set_interpolation(Interpolation::Cubic)
+            .set_spectrum(Spectrum::Exponent(0.8))
+            .set_octave_rotations(vec![0.0, 30.0])
+            .set_octave_offsets(vec![0.5]);
+        let derivative = DerivativeFbm::new()
+            .set_seed(3)
+            .set_damping(0.0)
+            .set_interpolation(Interpolation::Cubic)
+            .set_spectrum(Spectrum::Exponent(0.8))
+            .set_octave_rotations(vec![0.0, 30.0])
+            .set_octave_offsets(vec![0.5]);
+
+        for &point in &[[0.3, 1.7], [-4.25, 10.5]] {
+            assert_eq!(fbm.get(point), derivative.get(point));
+        }
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &DerivativeFbm::new().set_seed(42),
+            &POINTS_2D,
+            &[
+                -0.001865098372074943,
+                -0.010371445155617746,
+                -0.19766927865808057,
+                -0.26306655556004815,
+                0.43807816110950065,
+                0.0164439406316456,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &DerivativeFbm::new().set_seed(42),
+            &POINTS_3D,
+            &[
+                -0.0025111200862787337,
+                0.034449179649953844,
+                -0.05247328135731843,
+                -0.052767059216690775,
+                0.04730185065354909,
+                -0.050688037717132456,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &DerivativeFbm::new().set_seed(42),
+            &POINTS_4D,
+            &[
+                0.027814135786923003,
+                -0.024206340723502844,
+                0.19523346516028828,
+                3.804876309231507e-7,
+                -0.028832229210162538,
+                1.0209777357508387,
+            ],
+        );
+    }
+}
@@ -1,6 +1,9 @@
-use crate::math;
-
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use super::octaves::OctaveTransforms;
+use crate::{
+    math,
+    noise_fns::{Interpolation, MultiFractal, NoiseFn, Perlin, Seedable, Spectrum},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
 
 /// Noise function that outputs fBm (fractal Brownian motion) noise.
 ///
@@ -19,7 +22,7 @@ use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
 ///
 /// fBm is commonly referred to as Perlin noise.
 #[derive(Clone, Debug)]
-pub struct Fbm {
+pub struct Fbm<H = PermutationTable> {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -48,17 +51,28 @@ pub struct Fbm {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
-    seed: u32,
-    sources: Vec<Perlin>,
+    seed: u64,
+    sources: Vec<Perlin<H>>,
+    interpolation: Interpolation,
+    spectrum: Spectrum,
+    transforms: OctaveTransforms,
     scale_factor: f64,
 }
 
-fn calc_scale_factor(persistence: f64, octaves: usize) -> f64 {
-    1.0 - persistence.powi(octaves as i32)
+fn calc_scale_factor(
+    spectrum: &Spectrum,
+    persistence: f64,
+    lacunarity: f64,
+    octaves: usize,
+) -> f64 {
+    match spectrum {
+        Spectrum::Persistence => 1.0 - math::powi(persistence, octaves as i32),
+        _ => spectrum.total(octaves, persistence, lacunarity),
+    }
 }
 
 impl Fbm {
-    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_SEED: u64 = 0;
     pub const DEFAULT_OCTAVE_COUNT: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
     pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
@@ -66,16 +80,120 @@ impl Fbm {
     pub const MAX_OCTAVES: usize = 32;
 
     pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> Fbm<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points of its octaves.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            seed,
+            octaves: Fbm::DEFAULT_OCTAVE_COUNT,
+            frequency: Fbm::DEFAULT_FREQUENCY,
+            lacunarity: Fbm::DEFAULT_LACUNARITY,
+            persistence: Fbm::DEFAULT_PERSISTENCE,
+            sources: super::build_sources(seed, Fbm::DEFAULT_OCTAVE_COUNT, Interpolation::Quintic),
+            interpolation: Interpolation::Quintic,
+            spectrum: Spectrum::Persistence,
+            transforms: OctaveTransforms::default(),
+            scale_factor: calc_scale_factor(
+                &Spectrum::Persistence,
+                Fbm::DEFAULT_PERSISTENCE,
+                Fbm::DEFAULT_LACUNARITY,
+                Fbm::DEFAULT_OCTAVE_COUNT,
+            ),
+        }
+    }
+
+    /// Sets the curve used by every octave to blend between the corners of
+    /// each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            sources: self
+                .sources
+                .into_iter()
+                .map(|source| source.set_interpolation(interpolation))
+                .collect(),
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Sets how the amplitude of each octave is chosen.
+    ///
+    /// With any spectrum other than [`Spectrum::Persistence`], the result is
+    /// scaled by the sum of the amplitudes of all the octaves instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` is an empty list of amplitudes.
+    pub fn set_spectrum(self, spectrum: Spectrum) -> Self {
+        spectrum.assert_valid();
+
+        Self {
+            scale_factor: calc_scale_factor(
+                &spectrum,
+                self.persistence,
+                self.lacunarity,
+                self.octaves,
+            ),
+            spectrum,
+            ..self
+        }
+    }
+
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the amplitude of the given octave under the current spectrum.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        self.spectrum
+            .amplitude(octave, self.persistence, self.lacunarity)
+    }
+
+    /// Sets the angle, in degrees, by which the input of each octave is
+    /// rotated, starting with the coarsest. Octaves past the end of the list
+    /// use its last angle.
+    ///
+    /// Turning the octaves by different angles keeps the axis-aligned
+    /// artifacts of their lattices from lining up.
+    pub fn set_octave_rotations(self, rotations: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                rotations,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_rotations(&self) -> &[f64] {
+        &self.transforms.rotations
+    }
+
+    /// Sets the offset added to every coordinate of the input of each octave,
+    /// starting with the coarsest. Octaves past the end of the list use its
+    /// last offset.
+    pub fn set_octave_offsets(self, offsets: Vec<f64>) -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            octaves: Self::DEFAULT_OCTAVE_COUNT,
-            frequency: Self::DEFAULT_FREQUENCY,
-            lacunarity: Self::DEFAULT_LACUNARITY,
-            persistence: Self::DEFAULT_PERSISTENCE,
-            sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
-            scale_factor: calc_scale_factor(Self::DEFAULT_PERSISTENCE, Self::DEFAULT_OCTAVE_COUNT),
+            transforms: OctaveTransforms {
+                offsets,
+                ..self.transforms
+            },
+            ..self
         }
     }
+
+    pub fn octave_offsets(&self) -> &[f64] {
+        &self.transforms.offsets
+    }
 }
 
 impl Default for Fbm {
@@ -84,17 +202,22 @@ impl Default for Fbm {
     }
 }
 
-impl MultiFractal for Fbm {
+impl<H: NoiseHasher> MultiFractal for Fbm<H> {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
             return self;
         }
 
-        octaves = octaves.clamp(1, Self::MAX_OCTAVES);
+        octaves = octaves.clamp(1, Fbm::MAX_OCTAVES);
         Self {
             octaves,
-            sources: super::build_sources(self.seed, octaves),
-            scale_factor: calc_scale_factor(self.persistence, octaves),
+            sources: super::build_sources(self.seed, octaves, self.interpolation),
+            scale_factor: calc_scale_factor(
+                &self.spectrum,
+                self.persistence,
+                self.lacunarity,
+                octaves,
+            ),
             ..self
         }
     }
@@ -104,49 +227,95 @@ impl MultiFractal for Fbm {
     }
 
     fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
+        Self {
+            lacunarity,
+            scale_factor: calc_scale_factor(
+                &self.spectrum,
+                self.persistence,
+                lacunarity,
+                self.octaves,
+            ),
+            ..self
+        }
     }
 
     fn set_persistence(self, persistence: f64) -> Self {
         Self {
             persistence,
-            scale_factor: calc_scale_factor(persistence, self.octaves),
+            scale_factor: calc_scale_factor(
+                &self.spectrum,
+                persistence,
+                self.lacunarity,
+                self.octaves,
+            ),
             ..self
         }
     }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
 }
 
-impl Seedable for Fbm {
-    fn set_seed(self, seed: u32) -> Self {
+impl<H: NoiseHasher> Seedable for Fbm<H> {
+    fn set_seed(self, seed: u64) -> Self {
         if self.seed == seed {
             return self;
         }
 
         Self {
             seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources(seed, self.octaves, self.interpolation),
             ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
 /// 2-dimensional Fbm noise
-impl NoiseFn<f64, 2> for Fbm {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
+impl<H: NoiseHasher> NoiseFn<f64, 2> for Fbm<H> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 2], footprint: f64) -> f64 {
         let mut result = 0.0;
 
         point = math::mul2(point, self.frequency);
 
         for x in 0..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
             // Get the signal.
-            let mut signal = self.sources[x].get(point);
+            let mut signal = self.sources[x].get(self.transforms.apply(x, point));
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+            signal *= fade;
 
             // Add the signal to the result.
             result += signal;
@@ -161,18 +330,34 @@ impl NoiseFn<f64, 2> for Fbm {
 }
 
 /// 3-dimensional Fbm noise
-impl NoiseFn<f64, 3> for Fbm {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
+impl<H: NoiseHasher> NoiseFn<f64, 3> for Fbm<H> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 3], footprint: f64) -> f64 {
         let mut result = 0.0;
 
         point = math::mul3(point, self.frequency);
 
         for x in 0..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
             // Get the signal.
-            let mut signal = self.sources[x].get(point);
+            let mut signal = self.sources[x].get(self.transforms.apply(x, point));
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+            signal *= fade;
 
             // Add the signal to the result.
             result += signal;
@@ -187,18 +372,34 @@ impl NoiseFn<f64, 3> for Fbm {
 }
 
 /// 4-dimensional Fbm noise
-impl NoiseFn<f64, 4> for Fbm {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
+impl<H: NoiseHasher> NoiseFn<f64, 4> for Fbm<H> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 4], footprint: f64) -> f64 {
         let mut result = 0.0;
 
         point = math::mul4(point, self.frequency);
 
         for x in 0..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
             // Get the signal.
-            let mut signal = self.sources[x].get(point);
+            let mut signal = self.sources[x].get(self.transforms.apply(x, point));
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+            signal *= fade;
 
             // Add the signal to the result.
             result += signal;
@@ -211,3 +412,94 @@ impl NoiseFn<f64, 4> for Fbm {
         result / self.scale_factor
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    #[test]
+    fn test_footprint() {
+        let fbm = Fbm::new().set_seed(42);
+        let point = [0.3, 1.7, -2.2];
+
+        assert_eq!(fbm.get(point), fbm.get_with_footprint(point, 0.0));
+
+        // A footprint wider than the base wavelength fades out every octave.
+        assert_eq!(0.0, fbm.get_with_footprint(point, 1.0));
+
+        // A footprint of 0.23 keeps the first two octaves whole and drops the
+        // rest, whose frequencies are at least 1/0.23.
+        let coarse = Fbm::new().set_seed(42).set_octaves(2);
+        let expected = coarse.get(point) * (1.0 - math::powi(coarse.persistence, 2))
+            / (1.0 - math::powi(fbm.persistence, fbm.octaves as i32));
+        assert!((fbm.get_with_footprint(point, 0.23) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_spectrum() {
+        let fbm = Fbm::new().set_spectrum(Spectrum::Exponent(1.0));
+        let expected = 1.0 / (Fbm::DEFAULT_LACUNARITY * Fbm::DEFAULT_LACUNARITY);
+        assert!((fbm.octave_amplitude(2) - expected).abs() < 1e-12);
+
+        // Silencing every octave but the first leaves its source as is.
+        let fbm = Fbm::new()
+            .set_seed(42)
+            .set_spectrum(Spectrum::Amplitudes(vec![1.0, 0.0]));
+        let first = Perlin::new(crate::mix_seed(42, 0));
+        let point = [0.3, 1.7];
+        assert_eq!(first.get(point), fbm.get(point));
+
+        // A quarter turn of the first octave maps (x, y) to (-y, x).
+        let turned = fbm.set_octave_rotations(vec![90.0]);
+        assert!((turned.get(point) - first.get([-1.7, 0.3])).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &Fbm::new().set_seed(42),
+            &POINTS_2D,
+            &[
+                -0.16048969221317186,
+                -0.039256594095834034,
+                -0.4645916636255452,
+                -0.5694588700904941,
+                0.832179296260596,
+                -0.08360680247049897,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &Fbm::new().set_seed(42),
+            &POINTS_3D,
+            &[
+                0.023357470548530757,
+                0.1379196827942093,
+                -0.20999973671573544,
+                -0.10074501002347576,
+                0.23719291201306458,
+                0.011871867460091664,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &Fbm::new().set_seed(42),
+            &POINTS_4D,
+            &[
+                0.16269573102482335,
+                -0.3958112561973769,
+                0.1788926772998614,
+                0.17635362651655284,
+                -0.287005824481862,
+                1.0673387801324834,
+            ],
+        );
+    }
+}
@@ -1,13 +1,16 @@
-use crate::math;
-
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use super::octaves::OctaveTransforms;
+use crate::{
+    math,
+    noise_fns::{Interpolation, MultiFractal, NoiseFn, Perlin, Seedable, Spectrum},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
 
 /// Noise function that outputs hybrid Multifractal noise.
 ///
 /// The result of this multifractal noise is that valleys in the noise should
 /// have smooth bottoms at all altitudes.
 #[derive(Clone, Debug)]
-pub struct HybridMulti {
+pub struct HybridMulti<H = PermutationTable> {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -36,12 +39,15 @@ pub struct HybridMulti {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
-    seed: u32,
-    sources: Vec<Perlin>,
+    seed: u64,
+    sources: Vec<Perlin<H>>,
+    interpolation: Interpolation,
+    spectrum: Spectrum,
+    transforms: OctaveTransforms,
 }
 
 impl HybridMulti {
-    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_SEED: u64 = 0;
     pub const DEFAULT_OCTAVES: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 2.0;
     pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
@@ -49,15 +55,110 @@ impl HybridMulti {
     pub const MAX_OCTAVES: usize = 32;
 
     pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> HybridMulti<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points of its octaves.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            seed,
+            octaves: HybridMulti::DEFAULT_OCTAVES,
+            frequency: HybridMulti::DEFAULT_FREQUENCY,
+            lacunarity: HybridMulti::DEFAULT_LACUNARITY,
+            persistence: HybridMulti::DEFAULT_PERSISTENCE,
+            sources: super::build_sources(
+                seed,
+                HybridMulti::DEFAULT_OCTAVES,
+                Interpolation::Quintic,
+            ),
+            interpolation: Interpolation::Quintic,
+            spectrum: Spectrum::Persistence,
+            transforms: OctaveTransforms::default(),
+        }
+    }
+
+    /// Sets the curve used by every octave to blend between the corners of
+    /// each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            sources: self
+                .sources
+                .into_iter()
+                .map(|source| source.set_interpolation(interpolation))
+                .collect(),
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Sets how the amplitude of each octave is chosen.
+    ///
+    /// The result is scaled the same way whatever the spectrum, so amplitudes
+    /// larger than those of [`Spectrum::Persistence`] can take it beyond the
+    /// [-1, 1] range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` is an empty list of amplitudes.
+    pub fn set_spectrum(self, spectrum: Spectrum) -> Self {
+        spectrum.assert_valid();
+
+        Self { spectrum, ..self }
+    }
+
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the amplitude of the given octave under the current spectrum.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        self.spectrum
+            .amplitude(octave, self.persistence, self.lacunarity)
+    }
+
+    /// Sets the angle, in degrees, by which the input of each octave is
+    /// rotated, starting with the coarsest. Octaves past the end of the list
+    /// use its last angle.
+    ///
+    /// Turning the octaves by different angles keeps the axis-aligned
+    /// artifacts of their lattices from lining up.
+    pub fn set_octave_rotations(self, rotations: Vec<f64>) -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            octaves: Self::DEFAULT_OCTAVES,
-            frequency: Self::DEFAULT_FREQUENCY,
-            lacunarity: Self::DEFAULT_LACUNARITY,
-            persistence: Self::DEFAULT_PERSISTENCE,
-            sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVES),
+            transforms: OctaveTransforms {
+                rotations,
+                ..self.transforms
+            },
+            ..self
         }
     }
+
+    pub fn octave_rotations(&self) -> &[f64] {
+        &self.transforms.rotations
+    }
+
+    /// Sets the offset added to every coordinate of the input of each octave,
+    /// starting with the coarsest. Octaves past the end of the list use its
+    /// last offset.
+    pub fn set_octave_offsets(self, offsets: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                offsets,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_offsets(&self) -> &[f64] {
+        &self.transforms.offsets
+    }
 }
 
 impl Default for HybridMulti {
@@ -66,16 +167,16 @@ impl Default for HybridMulti {
     }
 }
 
-impl MultiFractal for HybridMulti {
+impl<H: NoiseHasher> MultiFractal for HybridMulti<H> {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
             return self;
         }
 
-        octaves = octaves.clamp(1, Self::MAX_OCTAVES);
+        octaves = octaves.clamp(1, HybridMulti::MAX_OCTAVES);
         Self {
             octaves,
-            sources: super::build_sources(self.seed, octaves),
+            sources: super::build_sources(self.seed, octaves, self.interpolation),
             ..self
         }
     }
@@ -94,36 +195,76 @@ impl MultiFractal for HybridMulti {
             ..self
         }
     }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
 }
 
-impl Seedable for HybridMulti {
-    fn set_seed(self, seed: u32) -> Self {
+impl<H: NoiseHasher> Seedable for HybridMulti<H> {
+    fn set_seed(self, seed: u64) -> Self {
         if self.seed == seed {
             return self;
         }
 
         Self {
             seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources(seed, self.octaves, self.interpolation),
             ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
 /// 2-dimensional `HybridMulti` noise
-impl NoiseFn<f64, 2> for HybridMulti {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
-        // First unscaled octave of function; later octaves are scaled.
+impl<H: NoiseHasher> NoiseFn<f64, 2> for HybridMulti<H> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 2], footprint: f64) -> f64 {
+        // First octave of function, which weights the later octaves. It is
+        // faded out like the others, and with it the whole function.
+        let fade = super::octave_fade(self.frequency, footprint);
+        if fade == 0.0 {
+            return 0.0;
+        }
+
         point = math::mul2(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
+        let mut result = self.sources[0].get(self.transforms.apply(0, point))
+            * self
+                .spectrum
+                .amplitude(0, self.persistence, self.lacunarity)
+            * fade
+            * self.persistence;
         let mut weight = result;
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
             // Prevent divergence.
             weight = weight.max(1.0);
 
@@ -131,10 +272,13 @@ impl NoiseFn<f64, 2> for HybridMulti {
             point = math::mul2(point, self.lacunarity);
 
             // Get noise value.
-            let mut signal = self.sources[x].get(point);
+            let mut signal = self.sources[x].get(self.transforms.apply(x, point));
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+            signal *= fade;
 
             // Add it in, weighted by previous octave's noise value.
             result += weight * signal;
@@ -149,15 +293,39 @@ impl NoiseFn<f64, 2> for HybridMulti {
 }
 
 /// 3-dimensional `HybridMulti` noise
-impl NoiseFn<f64, 3> for HybridMulti {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
-        // First unscaled octave of function; later octaves are scaled.
+impl<H: NoiseHasher> NoiseFn<f64, 3> for HybridMulti<H> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 3], footprint: f64) -> f64 {
+        // First octave of function, which weights the later octaves. It is
+        // faded out like the others, and with it the whole function.
+        let fade = super::octave_fade(self.frequency, footprint);
+        if fade == 0.0 {
+            return 0.0;
+        }
+
         point = math::mul3(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
+        let mut result = self.sources[0].get(self.transforms.apply(0, point))
+            * self
+                .spectrum
+                .amplitude(0, self.persistence, self.lacunarity)
+            * fade
+            * self.persistence;
         let mut weight = result;
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
             // Prevent divergence.
             weight = weight.max(1.0);
 
@@ -165,10 +333,13 @@ impl NoiseFn<f64, 3> for HybridMulti {
             point = math::mul3(point, self.lacunarity);
 
             // Get noise value.
-            let mut signal = self.sources[x].get(point);
+            let mut signal = self.sources[x].get(self.transforms.apply(x, point));
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+            signal *= fade;
 
             // Add it in, weighted by previous octave's noise value.
             result += weight * signal;
@@ -183,15 +354,39 @@ impl NoiseFn<f64, 3> for HybridMulti {
 }
 
 /// 4-dimensional `HybridMulti` noise
-impl NoiseFn<f64, 4> for HybridMulti {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
-        // First unscaled octave of function; later octaves are scaled.
+impl<H: NoiseHasher> NoiseFn<f64, 4> for HybridMulti<H> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 4], footprint: f64) -> f64 {
+        // First octave of function, which weights the later octaves. It is
+        // faded out like the others, and with it the whole function.
+        let fade = super::octave_fade(self.frequency, footprint);
+        if fade == 0.0 {
+            return 0.0;
+        }
+
         point = math::mul4(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
+        let mut result = self.sources[0].get(self.transforms.apply(0, point))
+            * self
+                .spectrum
+                .amplitude(0, self.persistence, self.lacunarity)
+            * fade
+            * self.persistence;
         let mut weight = result;
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
+            // Fade out octaves finer than the footprint, skipping the rest.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            if fade == 0.0 {
+                break;
+            }
+
             // Prevent divergence.
             weight = weight.max(1.0);
 
@@ -199,10 +394,13 @@ impl NoiseFn<f64, 4> for HybridMulti {
             point = math::mul4(point, self.lacunarity);
 
             // Get noise value.
-            let mut signal = self.sources[x].get(point);
+            let mut signal = self.sources[x].get(self.transforms.apply(x, point));
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
+            signal *= fade;
 
             // Add it in, weighted by previous octave's noise value.
             result += weight * signal;
@@ -215,3 +413,67 @@ impl NoiseFn<f64, 4> for HybridMulti {
         result * 3.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    #[test]
+    fn test_footprint_fades_first_octave() {
+        let multi = HybridMulti::new().set_seed(42);
+        let point = [0.3, 1.7];
+
+        assert_eq!(multi.get_with_footprint(point, 0.0), multi.get(point));
+        assert_ne!(multi.get_with_footprint(point, 0.6), multi.get(point));
+        assert_eq!(multi.get_with_footprint(point, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &HybridMulti::new().set_seed(42),
+            &POINTS_2D,
+            &[
+                -0.30656440537428753,
+                0.21277997025995837,
+                -0.18808814409732993,
+                -0.5866677194752958,
+                0.021377836723010676,
+                0.2877801467905362,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &HybridMulti::new().set_seed(42),
+            &POINTS_3D,
+            &[
+                0.14375939647609964,
+                0.11699887527761119,
+                -0.4333975058745316,
+                -0.22307001920391994,
+                -0.4781104725890093,
+                0.25337846390360885,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &HybridMulti::new().set_seed(42),
+            &POINTS_4D,
+            &[
+                0.1421555599990689,
+                0.37255503924275185,
+                0.2521553141131295,
+                1.0472114490915279,
+                0.3396932066765543,
+                0.2910468395681315,
+            ],
+        );
+    }
+}
@@ -0,0 +1,517 @@
+use super::octaves::OctaveTransforms;
+use crate::{
+    math::{self, scale_shift},
+    noise_fns::{Interpolation, MultiFractal, NoiseFn, Perlin, Seedable, Spectrum},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+
+/// Noise function that outputs Jordan turbulence.
+///
+/// Jordan turbulence, after Giliam de Carpentier, sums squared octaves, so
+/// the features form rounded, billowy crests. As in
+/// [`SwissTurbulence`](super::SwissTurbulence), the input of each octave is
+/// warped along the accumulated gradient of the octaves before it. A second
+/// accumulation of the gradient damps the detail on steep slopes, leaving it
+/// on the crests and in the valleys.
+///
+/// The first octave has settings of its own, so the broad shape of the
+/// terrain can be warped and damped differently from its detail.
+///
+/// The gradient of each octave is estimated by sampling it at neighbouring
+/// points, so this noise function is several times slower than
+/// [`Billow`](super::Billow).
+#[derive(Clone, Debug)]
+pub struct JordanTurbulence<H = PermutationTable> {
+    /// Total number of frequency octaves to generate the noise with.
+    ///
+    /// The number of octaves control the _amount of detail_ in the noise
+    /// function. Adding more octaves increases the detail, with the drawback
+    /// of increasing the calculation time.
+    pub octaves: usize,
+
+    /// The number of cycles per unit length that the noise function outputs.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the frequency increases for
+    /// each successive octave in the noise function.
+    pub lacunarity: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave after the second.
+    pub persistence: f64,
+
+    /// The amplitude of the second octave, relative to the first, before it
+    /// is multiplied by the persistence. Only used with
+    /// [`Spectrum::Persistence`].
+    pub first_gain: f64,
+
+    /// How far the first octave warps the input of the following octaves.
+    pub first_warp: f64,
+
+    /// How far each octave after the first warps the input of the following
+    /// octaves.
+    pub warp: f64,
+
+    /// How strongly the slope of the first octave damps the following
+    /// octaves.
+    pub first_damping: f64,
+
+    /// How strongly the slope of each octave after the first damps the
+    /// following octaves.
+    pub damping: f64,
+
+    /// The largest fraction of the amplitude of an octave that the damping
+    /// can remove.
+    pub damping_scale: f64,
+
+    seed: u64,
+    sources: Vec<Perlin<H>>,
+    interpolation: Interpolation,
+    spectrum: Spectrum,
+    transforms: OctaveTransforms,
+}
+
+impl JordanTurbulence {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = 6;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+    pub const DEFAULT_FIRST_GAIN: f64 = 0.8;
+    pub const DEFAULT_FIRST_WARP: f64 = 0.4;
+    pub const DEFAULT_WARP: f64 = 0.35;
+    pub const DEFAULT_FIRST_DAMPING: f64 = 1.0;
+    pub const DEFAULT_DAMPING: f64 = 0.8;
+    pub const DEFAULT_DAMPING_SCALE: f64 = 1.0;
+    pub const MAX_OCTAVES: usize = 32;
+
+    pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> JordanTurbulence<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points of its octaves.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            seed,
+            octaves: JordanTurbulence::DEFAULT_OCTAVE_COUNT,
+            frequency: JordanTurbulence::DEFAULT_FREQUENCY,
+            lacunarity: JordanTurbulence::DEFAULT_LACUNARITY,
+            persistence: JordanTurbulence::DEFAULT_PERSISTENCE,
+            first_gain: JordanTurbulence::DEFAULT_FIRST_GAIN,
+            first_warp: JordanTurbulence::DEFAULT_FIRST_WARP,
+            warp: JordanTurbulence::DEFAULT_WARP,
+            first_damping: JordanTurbulence::DEFAULT_FIRST_DAMPING,
+            damping: JordanTurbulence::DEFAULT_DAMPING,
+            damping_scale: JordanTurbulence::DEFAULT_DAMPING_SCALE,
+            sources: super::build_sources(
+                seed,
+                JordanTurbulence::DEFAULT_OCTAVE_COUNT,
+                Interpolation::Quintic,
+            ),
+            interpolation: Interpolation::Quintic,
+            spectrum: Spectrum::Persistence,
+            transforms: OctaveTransforms::default(),
+        }
+    }
+
+    pub fn set_first_gain(self, first_gain: f64) -> Self {
+        Self { first_gain, ..self }
+    }
+
+    pub fn set_first_warp(self, first_warp: f64) -> Self {
+        Self { first_warp, ..self }
+    }
+
+    pub fn set_warp(self, warp: f64) -> Self {
+        Self { warp, ..self }
+    }
+
+    pub fn set_first_damping(self, first_damping: f64) -> Self {
+        Self {
+            first_damping,
+            ..self
+        }
+    }
+
+    pub fn set_damping(self, damping: f64) -> Self {
+        Self { damping, ..self }
+    }
+
+    pub fn set_damping_scale(self, damping_scale: f64) -> Self {
+        Self {
+            damping_scale,
+            ..self
+        }
+    }
+
+    /// Sets the curve used by every octave to blend between the corners of
+    /// each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            sources: self
+                .sources
+                .into_iter()
+                .map(|source| source.set_interpolation(interpolation))
+                .collect(),
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Sets how the amplitude of each octave is chosen.
+    ///
+    /// With any spectrum other than [`Spectrum::Persistence`], the amplitude
+    /// of each octave, the first included, is taken from the spectrum, and
+    /// the first gain is not used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` is an empty list of amplitudes.
+    pub fn set_spectrum(self, spectrum: Spectrum) -> Self {
+        spectrum.assert_valid();
+
+        Self { spectrum, ..self }
+    }
+
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the amplitude of the given octave under the current spectrum.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        match self.spectrum {
+            // The second and third octaves share an amplitude, as the damping
+            // only starts to shrink it from the third octave on.
+            Spectrum::Persistence if octave == 0 => 1.0,
+            Spectrum::Persistence => {
+                self.first_gain * math::powi(self.persistence, (octave as i32 - 1).max(1))
+            }
+            _ => self
+                .spectrum
+                .amplitude(octave, self.persistence, self.lacunarity),
+        }
+    }
+
+    /// Sets the angle, in degrees, by which the input of each octave is
+    /// rotated, starting with the coarsest. Octaves past the end of the list
+    /// use its last angle.
+    ///
+    /// Turning the octaves by different angles keeps the axis-aligned
+    /// artifacts of their lattices from lining up.
+    pub fn set_octave_rotations(self, rotations: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                rotations,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_rotations(&self) -> &[f64] {
+        &self.transforms.rotations
+    }
+
+    /// Sets the offset added to every coordinate of the input of each octave,
+    /// starting with the coarsest. Octaves past the end of the list use its
+    /// last offset.
+    pub fn set_octave_offsets(self, offsets: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                offsets,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_offsets(&self) -> &[f64] {
+        &self.transforms.offsets
+    }
+
+    /// Returns the largest value the sum of the octaves can reach, which is
+    /// the sum of their amplitudes when none of them is damped.
+    fn max_value(&self) -> f64 {
+        (0..self.octaves)
+            .map(|x| self.octave_amplitude(x).abs())
+            .sum::<f64>()
+    }
+}
+
+impl Default for JordanTurbulence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: NoiseHasher> MultiFractal for JordanTurbulence<H> {
+    fn set_octaves(self, mut octaves: usize) -> Self {
+        if self.octaves == octaves {
+            return self;
+        }
+
+        octaves = octaves.clamp(1, JordanTurbulence::MAX_OCTAVES);
+        Self {
+            octaves,
+            sources: super::build_sources(self.seed, octaves, self.interpolation),
+            ..self
+        }
+    }
+
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self { lacunarity, ..self }
+    }
+
+    fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            ..self
+        }
+    }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
+}
+
+impl<H: NoiseHasher> Seedable for JordanTurbulence<H> {
+    fn set_seed(self, seed: u64) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            sources: super::build_sources(seed, self.octaves, self.interpolation),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// n-dimensional `JordanTurbulence` noise
+impl<H, const DIM: usize> NoiseFn<f64, DIM> for JordanTurbulence<H>
+where
+    H: NoiseHasher,
+    Perlin<H>: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; DIM], footprint: f64) -> f64 {
+        let mut result = 0.0;
+        let mut warp_slope = [0.0; DIM];
+        let mut damping_slope = [0.0; DIM];
+
+        // The first two octaves are not damped.
+        let mut damping_factor = 1.0;
+
+        // The expected value of the squared signal of each octave.
+        let expected = super::perlin_moments(DIM, self.interpolation).1;
+
+        for x in 0..self.octaves {
+            let frequency = self.frequency * math::powi(self.lacunarity, x as i32);
+            let damped_amplitude = self.octave_amplitude(x) * damping_factor;
+
+            // Fade out octaves finer than the footprint toward the expected
+            // value, skipping the sampling of those faded out entirely. Their
+            // slope fades toward zero, its expected value.
+            let fade = super::octave_fade(frequency, footprint);
+            if fade > 0.0 {
+                // Warp the point along the slope of the previous octaves.
+                let mut warped = point;
+                for (w, s) in warped.iter_mut().zip(&warp_slope) {
+                    *w = *w * frequency + s;
+                }
+
+                // Get the signal and its gradient, and square the signal.
+                let (signal, gradient) = super::value_and_gradient(
+                    |point| self.sources[x].get(self.transforms.apply(x, point)),
+                    warped,
+                );
+                result += super::fade_toward(expected, signal * signal, fade) * damped_amplitude;
+
+                // Accumulate half the gradient of the squared signal.
+                let (warp, damping) = if x == 0 {
+                    (self.first_warp, self.first_damping)
+                } else {
+                    (self.warp, self.damping)
+                };
+                for ((ws, ds), g) in warp_slope.iter_mut().zip(&mut damping_slope).zip(&gradient) {
+                    *ws += warp * signal * g * fade;
+                    *ds += damping * signal * g * fade;
+                }
+            } else {
+                result += expected * damped_amplitude;
+            }
+
+            // Damp the octaves after the second by the steepness of the slope.
+            if x > 0 {
+                let steepness = damping_slope.iter().map(|s| s * s).sum::<f64>();
+                damping_factor = 1.0 - self.damping_scale / (1.0 + steepness);
+            }
+        }
+
+        // Scale and shift the result into the [-1,1] range
+        scale_shift(result, 2.0 / self.max_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    // `mul_add` is avoided when targeting emscripten or in strict mode, which
+    // changes the rounding of the final scaling.
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_2D: [f64; 6] = [
+        -0.7557954591520559,
+        -0.9486280998240031,
+        -0.9355361184265404,
+        -0.21305937708604158,
+        -0.8315697465396347,
+        -0.9385506835870301,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_2D: [f64; 6] = [
+        -0.7557954591520559,
+        -0.9486280998240031,
+        -0.9355361184265405,
+        -0.2130593770860416,
+        -0.8315697465396347,
+        -0.9385506835870301,
+    ];
+
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_3D: [f64; 6] = [
+        -0.6275282450375531,
+        -0.9476364549306041,
+        -0.9459343668265946,
+        -0.9719662737071275,
+        -0.9291018430741267,
+        -0.9873160812485859,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_3D: [f64; 6] = [
+        -0.6275282450375531,
+        -0.9476364549306041,
+        -0.9459343668265946,
+        -0.9719662737071275,
+        -0.9291018430741267,
+        -0.9873160812485859,
+    ];
+
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_4D: [f64; 6] = [
+        -0.8807529984670748,
+        -0.27438141556391127,
+        -0.6700584645052489,
+        -0.6368339037697438,
+        -0.3248945891409622,
+        -0.06210546631537333,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_4D: [f64; 6] = [
+        -0.8807529984670748,
+        -0.27438141556391127,
+        -0.670058464505249,
+        -0.6368339037697438,
+        -0.32489458914096225,
+        -0.06210546631537328,
+    ];
+
+    #[test]
+    fn test_no_warp_or_damping_sums_squares() {
+        // Without the warp and the damping, each octave adds its square,
+        // scaled by the first gain and the persistence after the first.
+        let jordan = JordanTurbulence::new()
+            .set_seed(5)
+            .set_first_warp(0.0)
+            .set_warp(0.0)
+            .set_first_damping(0.0)
+            .set_damping(0.0)
+            .set_damping_scale(0.0);
+        let point = [0.3, 1.7];
+
+        let mut sum = 0.0;
+        let mut total_amplitude = 0.0;
+        for x in 0..jordan.octaves {
+            let frequency = jordan.frequency * math::powi(jordan.lacunarity, x as i32);
+            let source = Perlin::new(crate::mix_seed(5, x as u64));
+            let signal = source.get([point[0] * frequency, point[1] * frequency]);
+
+            let amplitude = match x {
+                0 => 1.0,
+                1 | 2 => jordan.first_gain * jordan.persistence,
+                _ => jordan.first_gain * math::powi(jordan.persistence, x as i32 - 1),
+            };
+            sum += signal * signal * amplitude;
+            total_amplitude += amplitude;
+        }
+        let expected = sum * 2.0 / total_amplitude - 1.0;
+
+        assert!((jordan.get(point) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_footprint_mean() {
+        // The damping of the later octaves depends on the slope of the
+        // earlier ones, which fades toward zero, so the mean drifts slightly.
+        super::super::assert_stable_mean(&JordanTurbulence::new(), 0.06);
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &JordanTurbulence::new().set_seed(42),
+            &POINTS_2D,
+            &EXPECTED_2D,
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &JordanTurbulence::new().set_seed(42),
+            &POINTS_3D,
+            &EXPECTED_3D,
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &JordanTurbulence::new().set_seed(42),
+            &POINTS_4D,
+            &EXPECTED_4D,
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::math;
+
+/// How the amplitude of each octave of a fractal is chosen.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Spectrum {
+    /// The amplitude of each successive octave is equal to the product of the
+    /// previous octave's amplitude and the persistence.
+    Persistence,
+
+    /// Musgrave's spectral exponent _H_: the amplitude of each octave is its
+    /// frequency, relative to that of the first octave, raised to the power
+    /// of -_H_. An _H_ of 1.0 gives the classic 1/_f_ spectrum, and lower
+    /// values produce rougher noise.
+    Exponent(f64),
+
+    /// The amplitude of each octave, starting with the coarsest. Octaves past
+    /// the end of the list use its last amplitude.
+    Amplitudes(Vec<f64>),
+}
+
+impl Spectrum {
+    /// Panics if the spectrum is an empty list of amplitudes.
+    pub(super) fn assert_valid(&self) {
+        if let Spectrum::Amplitudes(amplitudes) = self {
+            assert!(!amplitudes.is_empty(), "at least one amplitude is required");
+        }
+    }
+
+    /// Returns the amplitude of the given octave.
+    pub(super) fn amplitude(&self, octave: usize, persistence: f64, lacunarity: f64) -> f64 {
+        match self {
+            Spectrum::Persistence => math::powi(persistence, octave as i32),
+            Spectrum::Exponent(h) => math::powf(lacunarity, -h * octave as f64),
+            Spectrum::Amplitudes(amplitudes) => amplitudes[octave.min(amplitudes.len() - 1)],
+        }
+    }
+
+    /// Returns the sum of the magnitudes of the amplitudes of the first
+    /// `octaves` octaves.
+    pub(super) fn total(&self, octaves: usize, persistence: f64, lacunarity: f64) -> f64 {
+        (0..octaves)
+            .map(|octave| self.amplitude(octave, persistence, lacunarity).abs())
+            .sum()
+    }
+}
+
+// `#[default]` on enum variants requires a newer compiler than we support.
+#[allow(clippy::derivable_impls)]
+impl Default for Spectrum {
+    fn default() -> Self {
+        Spectrum::Persistence
+    }
+}
+
+/// Settings shared by the fractals that turn and shift the input of each
+/// octave individually.
+#[derive(Clone, Debug, Default)]
+pub(super) struct OctaveTransforms {
+    pub(super) rotations: Vec<f64>,
+    pub(super) offsets: Vec<f64>,
+}
+
+impl OctaveTransforms {
+    /// Applies the rotation and then the offset of the given octave to the
+    /// point. Octaves past the end of either list use its last entry, and an
+    /// empty list leaves the point unchanged.
+    ///
+    /// The rotation turns the point by the same angle within the plane of
+    /// each pair of consecutive axes in turn, so no axis of the octave's
+    /// lattice stays aligned with the input axes.
+    pub(super) fn apply<const DIM: usize>(
+        &self,
+        octave: usize,
+        mut point: [f64; DIM],
+    ) -> [f64; DIM] {
+        if let Some(&angle) = self.rotations.get(octave).or_else(|| self.rotations.last()) {
+            let (sin, cos) = (math::sin(angle.to_radians()), math::cos(angle.to_radians()));
+            for axis in 1..DIM {
+                let (a, b) = (point[axis - 1], point[axis]);
+                point[axis - 1] = a * cos - b * sin;
+                point[axis] = a * sin + b * cos;
+            }
+        }
+
+        if let Some(&offset) = self.offsets.get(octave).or_else(|| self.offsets.last()) {
+            for p in point.iter_mut() {
+                *p += offset;
+            }
+        }
+
+        point
+    }
+}
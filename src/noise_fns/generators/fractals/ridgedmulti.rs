@@ -1,6 +1,8 @@
+use super::octaves::OctaveTransforms;
 use crate::{
     math::{self, scale_shift},
-    noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable},
+    noise_fns::{Interpolation, MultiFractal, NoiseFn, Perlin, Seedable, Spectrum},
+    permutationtable::{NoiseHasher, PermutationTable},
 };
 
 /// Noise function that outputs ridged-multifractal noise.
@@ -20,7 +22,7 @@ use crate::{
 /// Ridged-multifractal noise is often used to generate craggy mountainous
 /// terrain or marble-like textures.
 #[derive(Clone, Debug)]
-pub struct RidgedMulti {
+pub struct RidgedMulti<H = PermutationTable> {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -55,12 +57,15 @@ pub struct RidgedMulti {
     /// half the height of the previous.
     pub attenuation: f64,
 
-    seed: u32,
-    sources: Vec<Perlin>,
+    seed: u64,
+    sources: Vec<Perlin<H>>,
+    interpolation: Interpolation,
+    spectrum: Spectrum,
+    transforms: OctaveTransforms,
 }
 
 impl RidgedMulti {
-    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_SEED: u64 = 0;
     pub const DEFAULT_OCTAVE_COUNT: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
     pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
@@ -69,14 +74,29 @@ impl RidgedMulti {
     pub const MAX_OCTAVES: usize = 32;
 
     pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> RidgedMulti<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points of its octaves.
+    pub fn with_hasher(seed: u64) -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            octaves: Self::DEFAULT_OCTAVE_COUNT,
-            frequency: Self::DEFAULT_FREQUENCY,
-            lacunarity: Self::DEFAULT_LACUNARITY,
-            persistence: Self::DEFAULT_PERSISTENCE,
-            attenuation: Self::DEFAULT_ATTENUATION,
-            sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
+            seed,
+            octaves: RidgedMulti::DEFAULT_OCTAVE_COUNT,
+            frequency: RidgedMulti::DEFAULT_FREQUENCY,
+            lacunarity: RidgedMulti::DEFAULT_LACUNARITY,
+            persistence: RidgedMulti::DEFAULT_PERSISTENCE,
+            attenuation: RidgedMulti::DEFAULT_ATTENUATION,
+            sources: super::build_sources(
+                seed,
+                RidgedMulti::DEFAULT_OCTAVE_COUNT,
+                Interpolation::Quintic,
+            ),
+            interpolation: Interpolation::Quintic,
+            spectrum: Spectrum::Persistence,
+            transforms: OctaveTransforms::default(),
         }
     }
 
@@ -86,6 +106,96 @@ impl RidgedMulti {
             ..self
         }
     }
+
+    /// Sets the curve used by every octave to blend between the corners of
+    /// each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            sources: self
+                .sources
+                .into_iter()
+                .map(|source| source.set_interpolation(interpolation))
+                .collect(),
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Sets how the amplitude of each octave is chosen.
+    ///
+    /// With any spectrum other than [`Spectrum::Persistence`], the result is
+    /// scaled by the sum of the amplitudes of all the octaves instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` is an empty list of amplitudes.
+    pub fn set_spectrum(self, spectrum: Spectrum) -> Self {
+        spectrum.assert_valid();
+
+        Self { spectrum, ..self }
+    }
+
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the amplitude of the given octave under the current spectrum.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        self.spectrum
+            .amplitude(octave, self.persistence, self.lacunarity)
+    }
+
+    /// Sets the angle, in degrees, by which the input of each octave is
+    /// rotated, starting with the coarsest. Octaves past the end of the list
+    /// use its last angle.
+    ///
+    /// Turning the octaves by different angles keeps the axis-aligned
+    /// artifacts of their lattices from lining up.
+    pub fn set_octave_rotations(self, rotations: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                rotations,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_rotations(&self) -> &[f64] {
+        &self.transforms.rotations
+    }
+
+    /// Sets the offset added to every coordinate of the input of each octave,
+    /// starting with the coarsest. Octaves past the end of the list use its
+    /// last offset.
+    pub fn set_octave_offsets(self, offsets: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                offsets,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_offsets(&self) -> &[f64] {
+        &self.transforms.offsets
+    }
+}
+
+impl<H> RidgedMulti<H> {
+    fn scale(&self) -> f64 {
+        match self.spectrum {
+            Spectrum::Persistence => 2.0 - math::powi(0.5, self.octaves as i32 - 1),
+            _ => self
+                .spectrum
+                .total(self.octaves, self.persistence, self.lacunarity),
+        }
+    }
 }
 
 impl Default for RidgedMulti {
@@ -94,16 +204,16 @@ impl Default for RidgedMulti {
     }
 }
 
-impl MultiFractal for RidgedMulti {
+impl<H: NoiseHasher> MultiFractal for RidgedMulti<H> {
     fn set_octaves(self, mut octaves: usize) -> Self {
         if self.octaves == octaves {
             return self;
         }
 
-        octaves = octaves.clamp(1, Self::MAX_OCTAVES);
+        octaves = octaves.clamp(1, RidgedMulti::MAX_OCTAVES);
         Self {
             octaves,
-            sources: super::build_sources(self.seed, octaves),
+            sources: super::build_sources(self.seed, octaves, self.interpolation),
             ..self
         }
     }
@@ -122,44 +232,78 @@ impl MultiFractal for RidgedMulti {
             ..self
         }
     }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
 }
 
-impl Seedable for RidgedMulti {
-    fn set_seed(self, seed: u32) -> Self {
+impl<H: NoiseHasher> Seedable for RidgedMulti<H> {
+    fn set_seed(self, seed: u64) -> Self {
         if self.seed == seed {
             return self;
         }
 
         Self {
             seed,
-            sources: super::build_sources(seed, self.octaves),
+            sources: super::build_sources(seed, self.octaves, self.interpolation),
             ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
 /// 2-dimensional `RidgedMulti` noise
-impl NoiseFn<f64, 2> for RidgedMulti {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
+impl<H: NoiseHasher> NoiseFn<f64, 2> for RidgedMulti<H> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 2], footprint: f64) -> f64 {
         let mut result = 0.0;
         let mut weight = 1.0;
 
+        // The expected value of the ridges of each octave, before weighting.
+        let (abs_mean, square_mean) = super::perlin_moments(2, self.interpolation);
+        let expected = 1.0 - 2.0 * abs_mean + square_mean;
+
         point = math::mul2(point, self.frequency);
 
         for x in 0..self.octaves {
-            // Get the value.
-            let mut signal = self.sources[x].get(point);
-
-            // Make the ridges.
-            signal = signal.abs();
-            signal = 1.0 - signal;
-
-            // Square the signal to increase the sharpness of the ridges.
-            signal *= signal;
+            // Fade out octaves finer than the footprint toward the expected
+            // value, skipping the sampling of those faded out entirely.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            let mut signal = if fade > 0.0 {
+                // Get the value.
+                let signal = self.sources[x].get(self.transforms.apply(x, point));
+
+                // Make the ridges.
+                let ridge = 1.0 - signal.abs();
+
+                // Square the signal to increase the sharpness of the ridges.
+                ridge * ridge
+            } else {
+                expected
+            };
+            signal = super::fade_toward(expected, signal, fade);
 
             // Apply the weighting from the previous octave to the signal.
             // Larger values have higher weights, producing sharp points along
@@ -173,7 +317,9 @@ impl NoiseFn<f64, 2> for RidgedMulti {
             weight = weight.clamp(0.0, 1.0);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
 
             // Add the signal to the result.
             result += signal;
@@ -183,29 +329,46 @@ impl NoiseFn<f64, 2> for RidgedMulti {
         }
 
         // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
+        scale_shift(result, 2.0 / self.scale())
     }
 }
 
 /// 3-dimensional `RidgedMulti` noise
-impl NoiseFn<f64, 3> for RidgedMulti {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
+impl<H: NoiseHasher> NoiseFn<f64, 3> for RidgedMulti<H> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 3], footprint: f64) -> f64 {
         let mut result = 0.0;
         let mut weight = 1.0;
 
+        // The expected value of the ridges of each octave, before weighting.
+        let (abs_mean, square_mean) = super::perlin_moments(3, self.interpolation);
+        let expected = 1.0 - 2.0 * abs_mean + square_mean;
+
         point = math::mul3(point, self.frequency);
 
         for x in 0..self.octaves {
-            // Get the value.
-            let mut signal = self.sources[x].get(point);
-
-            // Make the ridges.
-            signal = signal.abs();
-            signal = 1.0 - signal;
-
-            // Square the signal to increase the sharpness of the ridges.
-            signal *= signal;
+            // Fade out octaves finer than the footprint toward the expected
+            // value, skipping the sampling of those faded out entirely.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            let mut signal = if fade > 0.0 {
+                // Get the value.
+                let signal = self.sources[x].get(self.transforms.apply(x, point));
+
+                // Make the ridges.
+                let ridge = 1.0 - signal.abs();
+
+                // Square the signal to increase the sharpness of the ridges.
+                ridge * ridge
+            } else {
+                expected
+            };
+            signal = super::fade_toward(expected, signal, fade);
 
             // Apply the weighting from the previous octave to the signal.
             // Larger values have higher weights, producing sharp points along
@@ -219,7 +382,9 @@ impl NoiseFn<f64, 3> for RidgedMulti {
             weight = weight.clamp(0.0, 1.0);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
 
             // Add the signal to the result.
             result += signal;
@@ -229,29 +394,46 @@ impl NoiseFn<f64, 3> for RidgedMulti {
         }
 
         // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
+        scale_shift(result, 2.0 / self.scale())
     }
 }
 
 /// 4-dimensional `RidgedMulti` noise
-impl NoiseFn<f64, 4> for RidgedMulti {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
+impl<H: NoiseHasher> NoiseFn<f64, 4> for RidgedMulti<H> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, mut point: [f64; 4], footprint: f64) -> f64 {
         let mut result = 0.0;
         let mut weight = 1.0;
 
+        // The expected value of the ridges of each octave, before weighting.
+        let (abs_mean, square_mean) = super::perlin_moments(4, self.interpolation);
+        let expected = 1.0 - 2.0 * abs_mean + square_mean;
+
         point = math::mul4(point, self.frequency);
 
         for x in 0..self.octaves {
-            // Get the value.
-            let mut signal = self.sources[x].get(point);
-
-            // Make the ridges.
-            signal = signal.abs();
-            signal = 1.0 - signal;
-
-            // Square the signal to increase the sharpness of the ridges.
-            signal *= signal;
+            // Fade out octaves finer than the footprint toward the expected
+            // value, skipping the sampling of those faded out entirely.
+            let fade = super::octave_fade(
+                self.frequency * math::powi(self.lacunarity, x as i32),
+                footprint,
+            );
+            let mut signal = if fade > 0.0 {
+                // Get the value.
+                let signal = self.sources[x].get(self.transforms.apply(x, point));
+
+                // Make the ridges.
+                let ridge = 1.0 - signal.abs();
+
+                // Square the signal to increase the sharpness of the ridges.
+                ridge * ridge
+            } else {
+                expected
+            };
+            signal = super::fade_toward(expected, signal, fade);
 
             // Apply the weighting from the previous octave to the signal.
             // Larger values have higher weights, producing sharp points along
@@ -265,7 +447,9 @@ impl NoiseFn<f64, 4> for RidgedMulti {
             weight = weight.clamp(0.0, 1.0);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self
+                .spectrum
+                .amplitude(x, self.persistence, self.lacunarity);
 
             // Add the signal to the result.
             result += signal;
@@ -275,7 +459,91 @@ impl NoiseFn<f64, 4> for RidgedMulti {
         }
 
         // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
+        scale_shift(result, 2.0 / self.scale())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    // `mul_add` is avoided when targeting emscripten or in strict mode, which
+    // changes the rounding of the final scaling.
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_2D: [f64; 6] = [
+        -0.170308454466834,
+        0.2250737127227288,
+        -0.17539383316654278,
+        -0.9735794761059142,
+        -0.6039929537320059,
+        0.25366258255334867,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_2D: [f64; 6] = [
+        -0.17030845446683396,
+        0.2250737127227287,
+        -0.17539383316654278,
+        -0.9735794761059142,
+        -0.6039929537320059,
+        0.2536625825533487,
+    ];
+
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_3D: [f64; 6] = [
+        -0.6863829349271366,
+        -0.04753147639466529,
+        0.3503989564257512,
+        0.1016764892477641,
+        -0.04141023752297541,
+        0.37689008340746805,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_3D: [f64; 6] = [
+        -0.6863829349271366,
+        -0.04753147639466526,
+        0.35039895642575125,
+        0.10167648924776418,
+        -0.04141023752297546,
+        0.376890083407468,
+    ];
+
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_4D: [f64; 6] = [
+        2.7202288021353933,
+        1.597888954873125,
+        -0.41973021054073584,
+        -0.45544392209259926,
+        -0.6698371627855062,
+        -1.0,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_4D: [f64; 6] = [
+        2.7202288021353933,
+        1.5978889548731252,
+        -0.4197302105407358,
+        -0.4554439220925992,
+        -0.6698371627855062,
+        -1.0,
+    ];
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(&RidgedMulti::new().set_seed(42), &POINTS_2D, &EXPECTED_2D);
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(&RidgedMulti::new().set_seed(42), &POINTS_3D, &EXPECTED_3D);
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(&RidgedMulti::new().set_seed(42), &POINTS_4D, &EXPECTED_4D);
+    }
+
+    #[test]
+    fn test_footprint_mean() {
+        super::super::assert_stable_mean(&RidgedMulti::new().set_seed(1), 0.02);
     }
 }
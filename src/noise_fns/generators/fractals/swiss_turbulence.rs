@@ -0,0 +1,425 @@
+use super::octaves::OctaveTransforms;
+use crate::{
+    math::{self, scale_shift},
+    noise_fns::{Interpolation, MultiFractal, NoiseFn, Perlin, Seedable, Spectrum},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+
+/// Noise function that outputs Swiss turbulence.
+///
+/// Swiss turbulence, after Giliam de Carpentier, sums inverted ridges like
+/// [`RidgedMulti`](super::RidgedMulti), but also warps the input of each
+/// octave along the accumulated gradient of the octaves before it, and
+/// weights each octave by the height reached so far. The ridges bend and
+/// branch like those of eroded alpine terrain, with smooth valleys and
+/// detailed peaks.
+///
+/// The gradient of each octave is estimated by sampling it at neighbouring
+/// points, so this noise function is several times slower than
+/// [`RidgedMulti`](super::RidgedMulti).
+#[derive(Clone, Debug)]
+pub struct SwissTurbulence<H = PermutationTable> {
+    /// Total number of frequency octaves to generate the noise with.
+    ///
+    /// The number of octaves control the _amount of detail_ in the noise
+    /// function. Adding more octaves increases the detail, with the drawback
+    /// of increasing the calculation time.
+    pub octaves: usize,
+
+    /// The number of cycles per unit length that the noise function outputs.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the frequency increases for
+    /// each successive octave in the noise function.
+    pub lacunarity: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave in the noise function.
+    pub persistence: f64,
+
+    /// How far the input of each octave is displaced along the accumulated
+    /// gradient of the previous octaves.
+    pub warp: f64,
+
+    seed: u64,
+    sources: Vec<Perlin<H>>,
+    interpolation: Interpolation,
+    spectrum: Spectrum,
+    transforms: OctaveTransforms,
+}
+
+impl SwissTurbulence {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = 6;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+    pub const DEFAULT_WARP: f64 = 0.15;
+    pub const MAX_OCTAVES: usize = 32;
+
+    pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> SwissTurbulence<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points of its octaves.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            seed,
+            octaves: SwissTurbulence::DEFAULT_OCTAVE_COUNT,
+            frequency: SwissTurbulence::DEFAULT_FREQUENCY,
+            lacunarity: SwissTurbulence::DEFAULT_LACUNARITY,
+            persistence: SwissTurbulence::DEFAULT_PERSISTENCE,
+            warp: SwissTurbulence::DEFAULT_WARP,
+            sources: super::build_sources(
+                seed,
+                SwissTurbulence::DEFAULT_OCTAVE_COUNT,
+                Interpolation::Quintic,
+            ),
+            interpolation: Interpolation::Quintic,
+            spectrum: Spectrum::Persistence,
+            transforms: OctaveTransforms::default(),
+        }
+    }
+
+    pub fn set_warp(self, warp: f64) -> Self {
+        Self { warp, ..self }
+    }
+
+    /// Sets the curve used by every octave to blend between the corners of
+    /// each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            sources: self
+                .sources
+                .into_iter()
+                .map(|source| source.set_interpolation(interpolation))
+                .collect(),
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Sets how the amplitude of each octave is chosen.
+    ///
+    /// The amplitude of each octave is still weighted by the height reached
+    /// by the octaves before it, and the result is scaled by the sum of the
+    /// amplitudes of all the octaves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` is an empty list of amplitudes.
+    pub fn set_spectrum(self, spectrum: Spectrum) -> Self {
+        spectrum.assert_valid();
+
+        Self { spectrum, ..self }
+    }
+
+    pub fn spectrum(&self) -> &Spectrum {
+        &self.spectrum
+    }
+
+    /// Returns the amplitude of the given octave under the current spectrum.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        self.spectrum
+            .amplitude(octave, self.persistence, self.lacunarity)
+    }
+
+    /// Sets the angle, in degrees, by which the input of each octave is
+    /// rotated, starting with the coarsest. Octaves past the end of the list
+    /// use its last angle.
+    ///
+    /// Turning the octaves by different angles keeps the axis-aligned
+    /// artifacts of their lattices from lining up.
+    pub fn set_octave_rotations(self, rotations: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                rotations,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_rotations(&self) -> &[f64] {
+        &self.transforms.rotations
+    }
+
+    /// Sets the offset added to every coordinate of the input of each octave,
+    /// starting with the coarsest. Octaves past the end of the list use its
+    /// last offset.
+    pub fn set_octave_offsets(self, offsets: Vec<f64>) -> Self {
+        Self {
+            transforms: OctaveTransforms {
+                offsets,
+                ..self.transforms
+            },
+            ..self
+        }
+    }
+
+    pub fn octave_offsets(&self) -> &[f64] {
+        &self.transforms.offsets
+    }
+}
+
+impl Default for SwissTurbulence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: NoiseHasher> MultiFractal for SwissTurbulence<H> {
+    fn set_octaves(self, mut octaves: usize) -> Self {
+        if self.octaves == octaves {
+            return self;
+        }
+
+        octaves = octaves.clamp(1, SwissTurbulence::MAX_OCTAVES);
+        Self {
+            octaves,
+            sources: super::build_sources(self.seed, octaves, self.interpolation),
+            ..self
+        }
+    }
+
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self { lacunarity, ..self }
+    }
+
+    fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            ..self
+        }
+    }
+
+    fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    fn persistence(&self) -> f64 {
+        self.persistence
+    }
+}
+
+impl<H: NoiseHasher> Seedable for SwissTurbulence<H> {
+    fn set_seed(self, seed: u64) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            sources: super::build_sources(seed, self.octaves, self.interpolation),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// n-dimensional `SwissTurbulence` noise
+impl<H, const DIM: usize> NoiseFn<f64, DIM> for SwissTurbulence<H>
+where
+    H: NoiseHasher,
+    Perlin<H>: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; DIM], footprint: f64) -> f64 {
+        let mut result = 0.0;
+        let mut weight = 1.0;
+        let mut slope = [0.0; DIM];
+
+        // The expected value of the ridges of each octave.
+        let expected = 1.0 - super::perlin_moments(DIM, self.interpolation).0;
+
+        for x in 0..self.octaves {
+            let frequency = self.frequency * math::powi(self.lacunarity, x as i32);
+            let amplitude = self.octave_amplitude(x) * weight;
+
+            // Fade out octaves finer than the footprint toward the expected
+            // value, skipping the sampling of those faded out entirely. Their
+            // slope fades toward zero, its expected value.
+            let fade = super::octave_fade(frequency, footprint);
+            if fade > 0.0 {
+                // Warp the point along the slope of the previous octaves.
+                let mut warped = point;
+                for (w, s) in warped.iter_mut().zip(&slope) {
+                    *w = (*w + self.warp * s) * frequency;
+                }
+
+                // Get the signal and its gradient, and make the ridges.
+                let (signal, gradient) = super::value_and_gradient(
+                    |point| self.sources[x].get(self.transforms.apply(x, point)),
+                    warped,
+                );
+                result += super::fade_toward(expected, 1.0 - signal.abs(), fade) * amplitude;
+
+                // Accumulate the gradient of the ridges, which points away
+                // from them.
+                for (s, g) in slope.iter_mut().zip(&gradient) {
+                    *s -= signal * g * amplitude * fade;
+                }
+            } else {
+                result += expected * amplitude;
+            }
+
+            // Weight the next octave by the height reached so far, keeping
+            // the valleys smooth.
+            weight *= result.clamp(0.0, 1.0);
+        }
+
+        // Scale and shift the result into the [-1,1] range
+        let total_amplitude = self
+            .spectrum
+            .total(self.octaves, self.persistence, self.lacunarity);
+        scale_shift(result, 2.0 / total_amplitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    // `mul_add` is avoided when targeting emscripten or in strict mode, which
+    // changes the rounding of the final scaling.
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_2D: [f64; 6] = [
+        0.20786177152234353,
+        0.6761951260590803,
+        0.1878306792424059,
+        -0.7996900895499419,
+        -0.27322915302860457,
+        0.6390810258480807,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_2D: [f64; 6] = [
+        0.20786177152234364,
+        0.6761951260590802,
+        0.18783067924240582,
+        -0.7996900895499419,
+        -0.2732291530286046,
+        0.6390810258480806,
+    ];
+
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_3D: [f64; 6] = [
+        -0.16811762404539088,
+        0.4383621103779578,
+        0.754993934736852,
+        0.5359554648210118,
+        0.5098829641622572,
+        0.6769176674998696,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_3D: [f64; 6] = [
+        -0.16811762404539088,
+        0.4383621103779578,
+        0.754993934736852,
+        0.5359554648210116,
+        0.5098829641622573,
+        0.6769176674998696,
+    ];
+
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_4D: [f64; 6] = [
+        0.4467022845048917,
+        -0.06123298189003978,
+        -0.2957615380980121,
+        0.267349412381795,
+        -0.347445719911806,
+        -1.0,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_4D: [f64; 6] = [
+        0.4467022845048916,
+        -0.061232981890039784,
+        -0.2957615380980121,
+        0.267349412381795,
+        -0.34744571991180606,
+        -1.0,
+    ];
+
+    #[test]
+    fn test_no_warp_sums_ridges() {
+        // Without the warp, each octave adds its ridges, weighted by the
+        // persistence and by the height reached by the octaves before it.
+        let swiss = SwissTurbulence::new().set_seed(5).set_warp(0.0);
+        let point = [0.3, 1.7];
+
+        let mut sum = 0.0;
+        let mut amplitude = 1.0;
+        for x in 0..swiss.octaves {
+            let frequency = swiss.frequency * math::powi(swiss.lacunarity, x as i32);
+            let source = Perlin::new(crate::mix_seed(5, x as u64));
+            let signal = source.get([point[0] * frequency, point[1] * frequency]);
+
+            sum += (1.0 - signal.abs()) * amplitude;
+            amplitude *= swiss.persistence * sum.clamp(0.0, 1.0);
+        }
+
+        let total_amplitude = (0..swiss.octaves)
+            .map(|x| math::powi(swiss.persistence, x as i32))
+            .sum::<f64>();
+        let expected = sum * 2.0 / total_amplitude - 1.0;
+
+        assert!((swiss.get(point) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_footprint_mean() {
+        super::super::assert_stable_mean(&SwissTurbulence::new(), 0.02);
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &SwissTurbulence::new().set_seed(42),
+            &POINTS_2D,
+            &EXPECTED_2D,
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &SwissTurbulence::new().set_seed(42),
+            &POINTS_3D,
+            &EXPECTED_3D,
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &SwissTurbulence::new().set_seed(42),
+            &POINTS_4D,
+            &EXPECTED_4D,
+        );
+    }
+}
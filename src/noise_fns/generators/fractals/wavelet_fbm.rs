@@ -0,0 +1,250 @@
+use super::super::wavelet::normalize;
+use crate::noise_fns::{NoiseFn, Seedable, Wavelet};
+
+/// Noise function that outputs fBm built from octaves of [`Wavelet`] noise.
+///
+/// As each octave of wavelet noise is band-limited, octaves carrying detail
+/// finer than the spacing between samples can be left out without changing
+/// the rest of the spectrum. Setting the [`footprint`](Self::set_footprint)
+/// to that spacing fades out the octaves above its Nyquist limit, which
+/// removes the shimmering of minified fractals and saves their evaluation.
+///
+/// The octaves share one set of tiles, each octave doubling the frequency of
+/// the previous one, so unlike [`Fbm`](super::Fbm) the lacunarity is fixed at
+/// 2.
+#[derive(Clone, Debug)]
+pub struct WaveletFbm {
+    /// Total number of frequency octaves to generate the noise with.
+    pub octaves: usize,
+
+    /// The highest frequency of the first octave, in cycles per unit length.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave in the noise function.
+    pub persistence: f64,
+
+    footprint: f64,
+    source: Wavelet,
+}
+
+impl WaveletFbm {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = 6;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+    pub const DEFAULT_FOOTPRINT: f64 = 0.0;
+    pub const MAX_OCTAVES: usize = 32;
+
+    pub fn new(seed: u64) -> Self {
+        Self {
+            octaves: Self::DEFAULT_OCTAVE_COUNT,
+            frequency: Self::DEFAULT_FREQUENCY,
+            persistence: Self::DEFAULT_PERSISTENCE,
+            footprint: Self::DEFAULT_FOOTPRINT,
+            source: Wavelet::new(seed),
+        }
+    }
+
+    pub fn set_octaves(self, octaves: usize) -> Self {
+        Self {
+            octaves: octaves.clamp(1, Self::MAX_OCTAVES),
+            ..self
+        }
+    }
+
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    pub fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            ..self
+        }
+    }
+
+    /// Sets the spacing between the samples that will be taken of the noise
+    /// function, such as the size of a pixel in its input space.
+    ///
+    /// Octaves whose highest frequency exceeds the Nyquist limit of half a
+    /// cycle per footprint are faded out, until octaves lying wholly above it
+    /// are dropped. A footprint of zero, the default, keeps every octave.
+    pub fn set_footprint(self, footprint: f64) -> Self {
+        Self {
+            footprint: footprint.max(0.0),
+            ..self
+        }
+    }
+
+    pub fn footprint(&self) -> f64 {
+        self.footprint
+    }
+
+    /// Evaluates fBm of 3-dimensional noise projected onto the plane through
+    /// the point with the given normal, which must be normalized. See
+    /// [`Wavelet::get_projected`].
+    pub fn get_projected(&self, point: [f64; 3], normal: [f64; 3]) -> f64 {
+        self.sum_octaves(self.footprint, |scale, offset| {
+            let mut point = point;
+            for (p, o) in point.iter_mut().zip(&offset) {
+                *p = *p * scale + o;
+            }
+            self.source.projected_value(point, normal)
+        })
+    }
+
+    /// Sums the octaves, given a function evaluating noise with unit variance
+    /// at the point scaled and then offset by the given amounts.
+    fn sum_octaves<F>(&self, footprint: f64, octave_value: F) -> f64
+    where
+        F: Fn(f64, [f64; 3]) -> f64,
+    {
+        let mut result = 0.0;
+        let mut total_energy = 0.0;
+        let mut amplitude = 1.0;
+        // The noise carries most of its energy between 0.25 and 0.5 cycles
+        // per unit, so scale the input to place the top of the band at the
+        // frequency.
+        let mut scale = 2.0 * self.frequency;
+
+        for octave in 0..self.octaves {
+            total_energy += amplitude * amplitude;
+
+            // The octave spans frequencies from half of its top frequency to
+            // all of it, so it lies wholly above the Nyquist limit once it is
+            // dropped.
+            let weight = super::octave_fade(scale / 2.0, footprint);
+            if weight > 0.0 {
+                // Offset each octave, so they do not all sample the same part
+                // of the tiles at the origin.
+                let offset = [
+                    octave as f64 * OCTAVE_OFFSET[0],
+                    octave as f64 * OCTAVE_OFFSET[1],
+                    octave as f64 * OCTAVE_OFFSET[2],
+                ];
+                result += weight * amplitude * octave_value(scale, offset);
+            }
+
+            amplitude *= self.persistence;
+            scale *= 2.0;
+        }
+
+        // Normalize by the energy of all octaves, so that fading octaves out
+        // leaves the amplitude of the others unchanged.
+        normalize(result / total_energy.sqrt())
+    }
+}
+
+const OCTAVE_OFFSET: [f64; 3] = [5.3829, 11.7013, 3.1967];
+
+impl Default for WaveletFbm {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl Seedable for WaveletFbm {
+    fn set_seed(self, seed: u64) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u64 {
+        self.source.seed()
+    }
+}
+
+impl NoiseFn<f64, 2> for WaveletFbm {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    /// The octaves are faded out for the larger of the given footprint and
+    /// the one set on the noise function.
+    fn get_with_footprint(&self, point: [f64; 2], footprint: f64) -> f64 {
+        let footprint = footprint.max(self.footprint);
+        self.sum_octaves(footprint, |scale, offset| {
+            self.source
+                .value_2d([point[0] * scale + offset[0], point[1] * scale + offset[1]])
+        })
+    }
+}
+
+impl NoiseFn<f64, 3> for WaveletFbm {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    /// The octaves are faded out for the larger of the given footprint and
+    /// the one set on the noise function.
+    fn get_with_footprint(&self, point: [f64; 3], footprint: f64) -> f64 {
+        let footprint = footprint.max(self.footprint);
+        self.sum_octaves(footprint, |scale, offset| {
+            self.source.value_3d([
+                point[0] * scale + offset[0],
+                point[1] * scale + offset[1],
+                point[2] * scale + offset[2],
+            ])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D};
+
+    #[test]
+    fn test_footprint() {
+        let noise = WaveletFbm::new(4).set_octaves(4);
+        let first_octave = noise.clone().set_octaves(1);
+        let energy = (1.0 + 0.25 + 0.0625 + 0.015625_f64).sqrt();
+
+        // The second octave reaches 2 cycles per unit, so a footprint of 0.5
+        // drops it and every later octave, leaving the first unscaled.
+        let coarse = noise.clone().set_footprint(0.5);
+        for i in 0..20 {
+            let point = [i as f64 * 0.77, i as f64 * 0.31];
+            let expected = first_octave.get(point);
+            if expected.abs() < 1.0 {
+                assert!((coarse.get(point) - expected / energy).abs() < 1e-12);
+            }
+            assert!((-1.0..=1.0).contains(&noise.get(point)));
+        }
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &WaveletFbm::new(42),
+            &POINTS_2D,
+            &[
+                -0.43550797659892576,
+                0.14467383029519457,
+                -0.5390907422247478,
+                -0.1379061593748584,
+                0.49478434279728706,
+                0.19527793639714527,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &WaveletFbm::new(42),
+            &POINTS_3D,
+            &[
+                -0.7085425353269387,
+                0.11941610055918607,
+                -0.1639422243410132,
+                0.2184131017082713,
+                -0.41634456533735625,
+                0.33639452763810684,
+            ],
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use crate::{
+    math, mix_seed,
+    noise_fns::{NoiseFn, Seedable},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+use std::f64::consts::PI;
+
+/// Noise function that outputs 2/3-dimensional Gabor noise.
+///
+/// Gabor noise is sparse convolution noise: the sum of many Gabor kernels,
+/// each a sinusoid windowed by a Gaussian, centred on random impulses scattered
+/// through space with a Poisson distribution. As the spectrum of the noise is
+/// that of the kernel, its frequency, orientation and bandwidth can be
+/// controlled directly, which suits band-limited and anisotropic textures such
+/// as brushed metal, wood grain and fabric.
+///
+/// Space is divided into cells as wide as the kernels, and the number,
+/// positions and weights of the impulses in each cell are derived from hashes
+/// of the cell's coordinates, like the seed points of
+/// [`Worley`](super::Worley).
+///
+/// The output is scaled so that three standard deviations of the noise map to
+/// the range \[-1, 1\], and clamped to it.
+#[derive(Clone, Copy, Debug)]
+pub struct Gabor<H = PermutationTable> {
+    frequency: f64,
+    orientation: f64,
+    bandwidth: f64,
+    impulse_density: f64,
+    isotropic: bool,
+    seed: u64,
+    hasher: H,
+}
+
+impl Gabor {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_ORIENTATION: f64 = 0.0;
+    pub const DEFAULT_BANDWIDTH: f64 = 0.5;
+    pub const DEFAULT_IMPULSE_DENSITY: f64 = 16.0;
+
+    pub fn new(seed: u64) -> Self {
+        Self::with_hasher(seed)
+    }
+}
+
+impl<H: NoiseHasher> Gabor<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// cells holding the impulses.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            frequency: Gabor::DEFAULT_FREQUENCY,
+            orientation: Gabor::DEFAULT_ORIENTATION,
+            bandwidth: Gabor::DEFAULT_BANDWIDTH,
+            impulse_density: Gabor::DEFAULT_IMPULSE_DENSITY,
+            isotropic: false,
+            seed,
+            hasher: H::from_seed(seed),
+        }
+    }
+
+    /// Sets the frequency of the sinusoid of the kernels, in cycles per unit
+    /// length.
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    /// Sets the angle, in degrees, of the direction in which the sinusoid of
+    /// the kernels oscillates, turning the _x_ axis towards the _y_ axis. The
+    /// stripes of the noise run perpendicular to it.
+    ///
+    /// This has no effect on isotropic noise.
+    pub fn set_orientation(self, orientation: f64) -> Self {
+        Self {
+            orientation,
+            ..self
+        }
+    }
+
+    pub fn orientation(&self) -> f64 {
+        self.orientation
+    }
+
+    /// Sets the bandwidth of the kernels, which is the inverse of the width of
+    /// their Gaussian window.
+    ///
+    /// A higher bandwidth gives smaller kernels, spreading the spectrum of the
+    /// noise over a wider range of frequencies and directions, while a lower
+    /// one gives longer, more regular stripes at the cost of speed, as the
+    /// cells grow with the kernels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bandwidth is not positive.
+    pub fn set_bandwidth(self, bandwidth: f64) -> Self {
+        assert!(bandwidth > 0.0, "bandwidth must be positive");
+
+        Self { bandwidth, ..self }
+    }
+
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Sets the mean number of impulses in each cell. Higher densities give
+    /// smoother, more Gaussian noise, while lower ones are faster but show the
+    /// individual kernels.
+    pub fn set_impulse_density(self, impulse_density: f64) -> Self {
+        Self {
+            impulse_density: impulse_density.max(0.0),
+            ..self
+        }
+    }
+
+    pub fn impulse_density(&self) -> f64 {
+        self.impulse_density
+    }
+
+    /// Enables or disables giving each kernel a random direction instead of
+    /// the orientation, producing noise with no preferred direction.
+    pub fn set_isotropic(self, isotropic: bool) -> Self {
+        Self { isotropic, ..self }
+    }
+
+    pub fn isotropic(&self) -> bool {
+        self.isotropic
+    }
+
+    /// Returns the radius beyond which the kernels are truncated, where their
+    /// Gaussian window has fallen below 5% of its peak. This is also the width
+    /// of the cells.
+    fn kernel_radius(&self) -> f64 {
+        (TRUNCATION / PI).sqrt() / self.bandwidth
+    }
+
+    fn gabor<const DIM: usize>(&self, point: [f64; DIM]) -> f64 {
+        let radius = self.kernel_radius();
+
+        let mut cell = [0; DIM];
+        for (c, p) in cell.iter_mut().zip(&point) {
+            *c = (p / radius).floor() as isize;
+        }
+
+        let angle = self.orientation.to_radians();
+        let mut direction = [0.0; DIM];
+        direction[0] = math::cos(angle);
+        direction[1] = math::sin(angle);
+
+        let mut sum = 0.0;
+        for index in 0..3_usize.pow(DIM as u32) {
+            let mut neighbour = cell;
+            let mut remainder = index;
+            for c in neighbour.iter_mut() {
+                *c += (remainder % 3) as isize - 1;
+                remainder /= 3;
+            }
+
+            sum += self.cell_sum(neighbour, point, radius, direction);
+        }
+
+        let deviation = self.variance::<DIM>(radius).sqrt();
+        if deviation > 0.0 {
+            (sum / (3.0 * deviation)).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Sums the kernels of the impulses in a cell at the given point.
+    fn cell_sum<const DIM: usize>(
+        &self,
+        cell: [isize; DIM],
+        point: [f64; DIM],
+        radius: f64,
+        direction: [f64; DIM],
+    ) -> f64 {
+        let mut random = CellRandom::new(self.seed, &self.hasher, &cell);
+        let count = random.poisson(self.impulse_density);
+
+        let mut sum = 0.0;
+        for _ in 0..count {
+            let mut offset = point;
+            for (o, &c) in offset.iter_mut().zip(&cell) {
+                *o -= (c as f64 + random.next_f64()) * radius;
+            }
+
+            let weight = random.next_f64() * 2.0 - 1.0;
+            let direction = if self.isotropic {
+                random.direction()
+            } else {
+                direction
+            };
+
+            let distance_squared = dot(offset, offset);
+            if distance_squared < radius * radius {
+                let envelope = math::exp(-PI * self.bandwidth * self.bandwidth * distance_squared);
+                let phase = 2.0 * PI * self.frequency * dot(offset, direction);
+                sum += weight * envelope * math::cos(phase);
+            }
+        }
+
+        sum
+    }
+
+    /// Returns the variance of the noise, ignoring the truncation of the
+    /// kernels.
+    fn variance<const DIM: usize>(&self, radius: f64) -> f64 {
+        let density = self.impulse_density / math::powi(radius, DIM as i32);
+        let kernel_energy = 0.5
+            * math::powf(2.0 * self.bandwidth * self.bandwidth, -(DIM as f64) / 2.0)
+            * (1.0
+                + math::exp(
+                    -2.0 * PI * self.frequency * self.frequency / (self.bandwidth * self.bandwidth),
+                ));
+
+        // The weights are uniform in [-1, 1], so their mean square is 1/3.
+        density * kernel_energy / 3.0
+    }
+}
+
+impl Default for Gabor {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> Seedable for Gabor<H> {
+    fn set_seed(self, seed: u64) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            hasher: H::from_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl<H: NoiseHasher> NoiseFn<f64, 2> for Gabor<H> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.gabor(point)
+    }
+}
+
+impl<H: NoiseHasher> NoiseFn<f64, 3> for Gabor<H> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.gabor(point)
+    }
+}
+
+/// Negated natural logarithm of the fraction of the peak of the Gaussian
+/// window at which the kernels are truncated, -ln(0.05).
+const TRUNCATION: f64 = 2.995_732_273_553_991;
+
+/// Upper bound on the number of impulses in a cell, guarding against very
+/// high densities.
+const MAX_IMPULSES: usize = 1024;
+
+/// Stream of random numbers describing the impulses of one cell, seeded by
+/// the cell's coordinates.
+struct CellRandom {
+    seed: u64,
+    index: u64,
+}
+
+impl CellRandom {
+    fn new(seed: u64, hasher: &dyn NoiseHasher, cell: &[isize]) -> Self {
+        // A permutation table only hashes the cell to 256 values, so the raw
+        // coordinates are mixed into the seed as well to give each cell its
+        // own stream.
+        let mut seed = mix_seed(seed, hasher.hash(cell) as u64);
+        for &c in cell {
+            seed = mix_seed(seed, c as u64);
+        }
+
+        Self { seed, index: 0 }
+    }
+
+    /// Returns a random number in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        let value = mix_seed(self.seed, self.index);
+        self.index += 1;
+
+        (value >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Returns a random count from a Poisson distribution with the given mean.
+    fn poisson(&mut self, mean: f64) -> usize {
+        let limit = math::exp(-mean);
+        let mut product = self.next_f64();
+        let mut count = 0;
+        while product > limit && count < MAX_IMPULSES {
+            product *= self.next_f64();
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Returns a random unit vector.
+    fn direction<const DIM: usize>(&mut self) -> [f64; DIM] {
+        loop {
+            let mut direction = [0.0; DIM];
+            for d in direction.iter_mut() {
+                *d = self.next_f64() * 2.0 - 1.0;
+            }
+
+            let length_squared = dot(direction, direction);
+            if length_squared > 1e-6 && length_squared <= 1.0 {
+                let length = length_squared.sqrt();
+                for d in direction.iter_mut() {
+                    *d /= length;
+                }
+
+                return direction;
+            }
+        }
+    }
+}
+
+fn dot<const DIM: usize>(a: [f64; DIM], b: [f64; DIM]) -> f64 {
+    a.iter().zip(&b).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D};
+
+    // The `strict` feature evaluates the kernels with portable implementations
+    // of `exp`, `sin` and `cos`, which round differently.
+    #[cfg(not(feature = "strict"))]
+    const EXPECTED_2D: [f64; 6] = [
+        0.7434973240807341,
+        -0.34073346360430606,
+        -0.10972948830096857,
+        0.07488831941024976,
+        -0.00015499774196819647,
+        0.49439603810502825,
+    ];
+    #[cfg(feature = "strict")]
+    const EXPECTED_2D: [f64; 6] = [
+        0.7434973240807341,
+        -0.340733463604306,
+        -0.10972948830096857,
+        0.07488831941024979,
+        -0.00015499774196825908,
+        0.49439603810502825,
+    ];
+
+    #[cfg(not(feature = "strict"))]
+    const EXPECTED_3D: [f64; 6] = [
+        0.36730614677082263,
+        -0.5756366806420042,
+        0.3453944415275434,
+        -0.2303716434269905,
+        -0.4599628333404084,
+        0.22468688472528944,
+    ];
+    #[cfg(feature = "strict")]
+    const EXPECTED_3D: [f64; 6] = [
+        0.36730614677082263,
+        -0.5756366806420042,
+        0.3453944415275434,
+        -0.2303716434269905,
+        -0.4599628333404084,
+        0.22468688472528944,
+    ];
+
+    #[test]
+    fn test_range_and_determinism() {
+        let noise = Gabor::new(7);
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for i in 0..200 {
+            let point = [i as f64 * 0.173, i as f64 * -0.091];
+            let value = noise.get(point);
+            assert!((-1.0..=1.0).contains(&value));
+            assert_eq!(value, Gabor::new(7).get(point));
+            min = min.min(value);
+            max = max.max(value);
+        }
+        assert!(max - min > 0.5);
+
+        let value = noise.get([0.3, 1.7, -2.2]);
+        assert!((-1.0..=1.0).contains(&value));
+    }
+
+    #[test]
+    fn test_cell_seeds() {
+        let noise = Gabor::new(7);
+
+        let mut seeds = std::collections::HashSet::new();
+        for x in -32..32 {
+            for y in -32..32 {
+                seeds.insert(CellRandom::new(noise.seed, &noise.hasher, &[x, y]).seed);
+            }
+        }
+
+        assert_eq!(64 * 64, seeds.len());
+    }
+
+    #[test]
+    fn test_orientation() {
+        // Stripes run perpendicular to the orientation, so the noise changes
+        // faster along it than across it.
+        let noise = Gabor::new(3).set_orientation(90.0).set_bandwidth(0.25);
+        let step = 0.1;
+        let (mut along, mut across) = (0.0, 0.0);
+        for i in 0..100 {
+            let x = i as f64 * 0.37;
+            let y = i as f64 * 0.53;
+            let value = noise.get([x, y]);
+            along += (noise.get([x, y + step]) - value).abs();
+            across += (noise.get([x + step, y]) - value).abs();
+        }
+        assert!(along > 2.0 * across);
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(&Gabor::new(42), &POINTS_2D, &EXPECTED_2D);
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(&Gabor::new(42), &POINTS_3D, &EXPECTED_3D);
+    }
+}
@@ -0,0 +1,54 @@
+//! Shared helpers for the golden-value tests of the generators.
+//!
+//! The expected values in these tests are part of the crate's determinism
+//! guarantee, so they must only be updated by a deliberate, documented change
+//! to the output of a generator.
+
+use crate::noise_fns::NoiseFn;
+
+pub(crate) const POINTS_2D: [[f64; 2]; 6] = [
+    [0.25, 0.75],
+    [-1.3, 2.7],
+    [12.34, -56.78],
+    [300.5, 0.1],
+    [-1000.125, 999.875],
+    [7.77, 7.77],
+];
+
+pub(crate) const POINTS_3D: [[f64; 3]; 6] = [
+    [0.25, 0.75, 0.5],
+    [-1.3, 2.7, -0.4],
+    [12.34, -56.78, 9.1],
+    [300.5, 0.1, -250.25],
+    [-1000.125, 999.875, 3.3],
+    [7.77, 7.77, -7.77],
+];
+
+pub(crate) const POINTS_4D: [[f64; 4]; 6] = [
+    [0.25, 0.75, 0.5, 0.125],
+    [-1.3, 2.7, -0.4, 5.5],
+    [12.34, -56.78, 9.1, -3.2],
+    [300.5, 0.1, -250.25, 42.0],
+    [-1000.125, 999.875, 3.3, -0.6],
+    [7.77, 7.77, -7.77, 7.77],
+];
+
+/// Asserts that `noise` outputs exactly the `expected` values, bit for bit, at
+/// the given points.
+pub(crate) fn assert_golden<N, const DIM: usize>(noise: &N, points: &[[f64; DIM]], expected: &[f64])
+where
+    N: NoiseFn<f64, DIM>,
+{
+    assert_eq!(points.len(), expected.len());
+
+    for (point, expected) in points.iter().zip(expected) {
+        let value = noise.get(*point);
+        assert!(
+            value.to_bits() == expected.to_bits(),
+            "output at {:?} changed: expected {:?}, got {:?}",
+            point,
+            expected,
+            value
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use crate::math::s_curve::{cubic::Cubic, quintic::Quintic};
+
+/// The curve used by lattice generators, such as [`Perlin`](super::Perlin)
+/// and [`Value`](super::Value), to blend between the values at the corners of
+/// each lattice cell.
+///
+/// The smoother curves hide the lattice better, at a small cost in speed,
+/// which makes the cheaper ones useful for preview passes. This corresponds to
+/// `NoiseQuality` in libnoise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Blends linearly. The fastest, but the slope of the output is
+    /// discontinuous at the lattice cell boundaries, showing creases.
+    Linear,
+
+    /// Blends with the cubic Hermite curve `3t² - 2t³`, whose slope is zero
+    /// at the cell boundaries. The curvature of the output is still
+    /// discontinuous there, which shows up in normal maps and derivatives.
+    Cubic,
+
+    /// Blends with the quintic curve `6t⁵ - 15t⁴ + 10t³`, whose slope and
+    /// curvature are both zero at the cell boundaries. This is the default.
+    Quintic,
+}
+
+impl Interpolation {
+    #[inline(always)]
+    pub(crate) fn map<const N: usize>(self, mut weights: [f64; N]) -> [f64; N] {
+        match self {
+            Interpolation::Linear => {}
+            Interpolation::Cubic => {
+                for weight in weights.iter_mut() {
+                    *weight = weight.map_cubic();
+                }
+            }
+            Interpolation::Quintic => {
+                for weight in weights.iter_mut() {
+                    *weight = weight.map_quintic();
+                }
+            }
+        }
+
+        weights
+    }
+}
+
+// `#[default]` on enum variants requires a newer compiler than we support.
+#[allow(clippy::derivable_impls)]
+impl Default for Interpolation {
+    fn default() -> Self {
+        Interpolation::Quintic
+    }
+}
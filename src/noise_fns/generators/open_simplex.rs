@@ -22,18 +22,26 @@ const NORM_CONSTANT_4D: f64 = 1.0 / 6.869_909_007_095_662_5;
 
 /// Noise function that outputs 2/3/4-dimensional Open Simplex noise.
 #[derive(Clone, Copy, Debug)]
-pub struct OpenSimplex {
-    seed: u32,
-    perm_table: PermutationTable,
+pub struct OpenSimplex<H = PermutationTable> {
+    seed: u64,
+    hasher: H,
 }
 
 impl OpenSimplex {
-    const DEFAULT_SEED: u32 = 0;
+    const DEFAULT_SEED: u64 = 0;
 
     pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> OpenSimplex<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points.
+    pub fn with_hasher(seed: u64) -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            seed,
+            hasher: H::from_seed(seed),
         }
     }
 }
@@ -44,22 +52,22 @@ impl Default for OpenSimplex {
     }
 }
 
-impl Seedable for OpenSimplex {
+impl<H: NoiseHasher> Seedable for OpenSimplex<H> {
     /// Sets the seed value for Open Simplex noise
-    fn set_seed(self, seed: u32) -> Self {
+    fn set_seed(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
         }
 
-        // Otherwise, regenerate the permutation table based on the new seed.
+        // Otherwise, regenerate the hasher based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            hasher: H::from_seed(seed),
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
@@ -67,14 +75,14 @@ impl Seedable for OpenSimplex {
 /// 2-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 2D.
-impl NoiseFn<f64, 2> for OpenSimplex {
+impl<H: NoiseHasher> NoiseFn<f64, 2> for OpenSimplex<H> {
     fn get(&self, point: [f64; 2]) -> f64 {
-        fn gradient(perm_table: &PermutationTable, vertex: [f64; 2], pos: [f64; 2]) -> f64 {
+        fn gradient(hasher: &dyn NoiseHasher, vertex: [f64; 2], pos: [f64; 2]) -> f64 {
             let attn = 2.0 - math::dot2(pos, pos);
             if attn > 0.0 {
-                let index = perm_table.hash(&math::to_isize2(vertex));
+                let index = hasher.hash(&math::to_isize2(vertex));
                 let vec = gradient::get2(index);
-                attn.powi(4) * math::dot2(pos, vec)
+                math::powi(attn, 4) * math::dot2(pos, vec)
             } else {
                 0.0
             }
@@ -118,12 +126,12 @@ impl NoiseFn<f64, 2> for OpenSimplex {
         // Contribution (1, 0)
         vertex = math::add2(stretched_floor, [1.0, 0.0]);
         dpos = math::sub2(pos0, [t1, t0]);
-        value += gradient(&self.perm_table, vertex, dpos);
+        value += gradient(&self.hasher, vertex, dpos);
 
         // Contribution (0, 1)
         vertex = math::add2(stretched_floor, [0.0, 1.0]);
         dpos = math::sub2(pos0, [t0, t1]);
-        value += gradient(&self.perm_table, vertex, dpos);
+        value += gradient(&self.hasher, vertex, dpos);
 
         // See the graph for an intuitive explanation; the sum of `x` and `y` is
         // only greater than `1` if we're on Region B.
@@ -139,7 +147,7 @@ impl NoiseFn<f64, 2> for OpenSimplex {
         }
 
         // Point (0, 0) or (1, 1)
-        value += gradient(&self.perm_table, vertex, dpos);
+        value += gradient(&self.hasher, vertex, dpos);
 
         value * NORM_CONSTANT_2D
     }
@@ -148,14 +156,14 @@ impl NoiseFn<f64, 2> for OpenSimplex {
 /// 3-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 3D.
-impl NoiseFn<f64, 3> for OpenSimplex {
+impl<H: NoiseHasher> NoiseFn<f64, 3> for OpenSimplex<H> {
     fn get(&self, point: [f64; 3]) -> f64 {
-        fn gradient(perm_table: &PermutationTable, vertex: [f64; 3], pos: [f64; 3]) -> f64 {
+        fn gradient(hasher: &dyn NoiseHasher, vertex: [f64; 3], pos: [f64; 3]) -> f64 {
             let attn = 2.0 - math::dot3(pos, pos);
             if attn > 0.0 {
-                let index = perm_table.hash(&math::to_isize3(vertex));
+                let index = hasher.hash(&math::to_isize3(vertex));
                 let vec = gradient::get3(index);
-                attn.powi(4) * math::dot3(pos, vec)
+                math::powi(attn, 4) * math::dot3(pos, vec)
             } else {
                 0.0
             }
@@ -196,22 +204,22 @@ impl NoiseFn<f64, 3> for OpenSimplex {
             // Contribution at (0, 0, 0)
             vertex = math::add3(stretched_floor, [0.0, 0.0, 0.0]);
             dpos = math::sub3(pos0, [0.0, 0.0, 0.0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (1, 0, 0)
             vertex = math::add3(stretched_floor, [1.0, 0.0, 0.0]);
             dpos = math::sub3(pos0, [t1, t0, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (0, 1, 0)
             vertex = math::add3(stretched_floor, [0.0, 1.0, 0.0]);
             dpos = math::sub3(pos0, [t0, t1, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (0, 0, 1)
             vertex = math::add3(stretched_floor, [0.0, 0.0, 1.0]);
             dpos = math::sub3(pos0, [t0, t0, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
         } else if region_sum >= 2.0 {
             // We're inside the tetrahedron (3-Simplex) at (1, 1, 1)
             let t0 = 2.0 * SQUISH_CONSTANT_3D;
@@ -221,22 +229,22 @@ impl NoiseFn<f64, 3> for OpenSimplex {
             // Contribution at (1, 1, 0)
             vertex = math::add3(stretched_floor, [1.0, 1.0, 0.0]);
             dpos = math::sub3(pos0, [t1, t1, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (1, 0, 1)
             vertex = math::add3(stretched_floor, [1.0, 0.0, 1.0]);
             dpos = math::sub3(pos0, [t1, t0, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (0, 1, 1)
             vertex = math::add3(stretched_floor, [0.0, 1.0, 1.0]);
             dpos = math::sub3(pos0, [t0, t1, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (1, 1, 1)
             vertex = math::add3(stretched_floor, [1.0, 1.0, 1.0]);
             dpos = math::sub3(pos0, [t2, t2, t2]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
         } else {
             // We're inside the octahedron (Rectified 3-Simplex) inbetween.
             let t0 = SQUISH_CONSTANT_3D;
@@ -247,32 +255,32 @@ impl NoiseFn<f64, 3> for OpenSimplex {
             // Contribution at (1, 0, 0)
             vertex = math::add3(stretched_floor, [1.0, 0.0, 0.0]);
             dpos = math::sub3(pos0, [t1, t0, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (0, 1, 0)
             vertex = math::add3(stretched_floor, [0.0, 1.0, 0.0]);
             dpos = math::sub3(pos0, [t0, t1, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (0, 0, 1)
             vertex = math::add3(stretched_floor, [0.0, 0.0, 1.0]);
             dpos = math::sub3(pos0, [t0, t0, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (1, 1, 0)
             vertex = math::add3(stretched_floor, [1.0, 1.0, 0.0]);
             dpos = math::sub3(pos0, [t3, t3, t2]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (1, 0, 1)
             vertex = math::add3(stretched_floor, [1.0, 0.0, 1.0]);
             dpos = math::sub3(pos0, [t3, t2, t3]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
 
             // Contribution at (0, 1, 1)
             vertex = math::add3(stretched_floor, [0.0, 1.0, 1.0]);
             dpos = math::sub3(pos0, [t2, t3, t3]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            value += gradient(&self.hasher, vertex, dpos);
         }
 
         value * NORM_CONSTANT_3D
@@ -282,15 +290,15 @@ impl NoiseFn<f64, 3> for OpenSimplex {
 /// 4-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 4D.
-impl NoiseFn<f64, 4> for OpenSimplex {
+impl<H: NoiseHasher> NoiseFn<f64, 4> for OpenSimplex<H> {
     fn get(&self, point: [f64; 4]) -> f64 {
         #[inline(always)]
-        fn gradient(perm_table: &PermutationTable, vertex: [f64; 4], pos: [f64; 4]) -> f64 {
+        fn gradient(hasher: &dyn NoiseHasher, vertex: [f64; 4], pos: [f64; 4]) -> f64 {
             let attn = 2.0 - math::dot4(pos, pos);
             if attn > 0.0 {
-                let index = perm_table.hash(&math::to_isize4(vertex));
+                let index = hasher.hash(&math::to_isize4(vertex));
                 let vec = gradient::get4(index);
-                attn.powi(4) * math::dot4(pos, vec)
+                math::powi(attn, 4) * math::dot4(pos, vec)
             } else {
                 0.0
             }
@@ -325,7 +333,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             // We're inside the pentachoron (4-Simplex) at (0, 0, 0, 0)
 
             // Contribution at (0, 0, 0, 0)
-            value += gradient(&self.perm_table, stretched_floor, pos0);
+            value += gradient(&self.hasher, stretched_floor, pos0);
 
             // Contribution at (1, 0, 0, 0)
             let pos1;
@@ -340,7 +348,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
                         SQUISH_CONSTANT_4D,
                     ],
                 );
-                value += gradient(&self.perm_table, vertex, pos1);
+                value += gradient(&self.hasher, vertex, pos1);
             }
 
             // Contribution at (0, 1, 0, 0)
@@ -348,7 +356,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 0.0, 0.0]);
                 pos2 = [pos1[0] + 1.0, pos1[1] - 1.0, pos1[2], pos1[3]];
-                value += gradient(&self.perm_table, vertex, pos2);
+                value += gradient(&self.hasher, vertex, pos2);
             }
 
             // Contribution at (0, 0, 1, 0)
@@ -356,7 +364,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 1.0, 0.0]);
                 pos3 = [pos2[0], pos1[1], pos1[2] - 1.0, pos1[3]];
-                value += gradient(&self.perm_table, vertex, pos3);
+                value += gradient(&self.hasher, vertex, pos3);
             }
 
             // Contribution at (0, 0, 0, 1)
@@ -364,7 +372,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 0.0, 1.0]);
                 pos4 = [pos2[0], pos1[1], pos1[2], pos1[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos4);
+                value += gradient(&self.hasher, vertex, pos4);
             }
         } else if region_sum >= 3.0 {
             // We're inside the pentachoron (4-Simplex) at (1, 1, 1, 1)
@@ -383,7 +391,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
                         squish_constant_3,
                     ],
                 );
-                value += gradient(&self.perm_table, vertex, pos4);
+                value += gradient(&self.hasher, vertex, pos4);
             }
 
             // Contribution at (1, 1, 0, 1)
@@ -391,7 +399,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 1.0, 0.0, 1.0]);
                 pos3 = [pos4[0], pos4[1], pos4[2] + 1.0, pos4[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos3);
+                value += gradient(&self.hasher, vertex, pos3);
             }
 
             // Contribution at (1, 0, 1, 1)
@@ -399,7 +407,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 1.0, 1.0]);
                 pos2 = [pos4[0], pos4[1] + 1.0, pos4[2], pos3[3]];
-                value += gradient(&self.perm_table, vertex, pos2);
+                value += gradient(&self.hasher, vertex, pos2);
             }
 
             // Contribution at (0, 1, 1, 1)
@@ -407,7 +415,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 1.0, 1.0]);
                 pos1 = [pos0[0] - squish_constant_3, pos4[1], pos4[2], pos3[3]];
-                value += gradient(&self.perm_table, vertex, pos1);
+                value += gradient(&self.hasher, vertex, pos1);
             }
 
             // Contribution at (1, 1, 1, 1)
@@ -417,7 +425,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
                 pos0[1] = pos4[1] - SQUISH_CONSTANT_4D;
                 pos0[2] = pos4[2] - SQUISH_CONSTANT_4D;
                 pos0[3] = pos3[3] - SQUISH_CONSTANT_4D;
-                value += gradient(&self.perm_table, vertex, pos0);
+                value += gradient(&self.hasher, vertex, pos0);
             }
         } else if region_sum <= 2.0 {
             // We're inside the first dispentachoron (Rectified 4-Simplex)
@@ -435,7 +443,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
                         SQUISH_CONSTANT_4D,
                     ],
                 );
-                value += gradient(&self.perm_table, vertex, pos1);
+                value += gradient(&self.hasher, vertex, pos1);
             }
 
             // Contribution at (0, 1, 0, 0)
@@ -443,7 +451,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 0.0, 0.0]);
                 pos2 = [pos1[0] + 1.0, pos1[1] - 1.0, pos1[2], pos1[3]];
-                value += gradient(&self.perm_table, vertex, pos2);
+                value += gradient(&self.hasher, vertex, pos2);
             }
 
             // Contribution at (0, 0, 1, 0)
@@ -451,7 +459,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 1.0, 0.0]);
                 pos3 = [pos2[0], pos1[1], pos1[2] - 1.0, pos1[3]];
-                value += gradient(&self.perm_table, vertex, pos3);
+                value += gradient(&self.hasher, vertex, pos3);
             }
 
             // Contribution at (0, 0, 0, 1)
@@ -459,7 +467,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 0.0, 1.0]);
                 pos4 = [pos2[0], pos1[1], pos1[2], pos1[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos4);
+                value += gradient(&self.hasher, vertex, pos4);
             }
 
             // Contribution at (1, 1, 0, 0)
@@ -472,7 +480,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
                     pos1[2] - SQUISH_CONSTANT_4D,
                     pos1[3] - SQUISH_CONSTANT_4D,
                 ];
-                value += gradient(&self.perm_table, vertex, pos5);
+                value += gradient(&self.hasher, vertex, pos5);
             }
 
             // Contribution at (1, 0, 1, 0)
@@ -480,7 +488,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 1.0, 0.0]);
                 pos6 = [pos5[0], pos5[1] + 1.0, pos5[2] - 1.0, pos5[3]];
-                value += gradient(&self.perm_table, vertex, pos6);
+                value += gradient(&self.hasher, vertex, pos6);
             }
 
             // Contribution at (1, 0, 0, 1)
@@ -488,7 +496,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 0.0, 1.0]);
                 pos7 = [pos5[0], pos6[1], pos5[2], pos5[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos7);
+                value += gradient(&self.hasher, vertex, pos7);
             }
 
             // Contribution at (0, 1, 1, 0)
@@ -496,7 +504,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 1.0, 0.0]);
                 pos8 = [pos5[0] + 1.0, pos5[1], pos6[2], pos5[3]];
-                value += gradient(&self.perm_table, vertex, pos8);
+                value += gradient(&self.hasher, vertex, pos8);
             }
 
             // Contribution at (0, 1, 0, 1)
@@ -504,7 +512,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 0.0, 1.0]);
                 pos9 = [pos8[0], pos5[1], pos5[2], pos7[3]];
-                value += gradient(&self.perm_table, vertex, pos9);
+                value += gradient(&self.hasher, vertex, pos9);
             }
 
             // Contribution at (0, 0, 1, 1)
@@ -512,7 +520,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 1.0, 1.0]);
                 pos10 = [pos8[0], pos6[1], pos6[2], pos7[3]];
-                value += gradient(&self.perm_table, vertex, pos10);
+                value += gradient(&self.hasher, vertex, pos10);
             }
         } else {
             // We're inside the second dispentachoron (Rectified 4-Simplex)
@@ -531,7 +539,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
                         squish_constant_3,
                     ],
                 );
-                value += gradient(&self.perm_table, vertex, pos4);
+                value += gradient(&self.hasher, vertex, pos4);
             }
 
             // Contribution at (1, 1, 0, 1)
@@ -539,7 +547,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 1.0, 0.0, 1.0]);
                 pos3 = [pos4[0], pos4[1], pos4[2] + 1.0, pos4[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos3);
+                value += gradient(&self.hasher, vertex, pos3);
             }
 
             // Contribution at (1, 0, 1, 1)
@@ -547,7 +555,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 1.0, 1.0]);
                 pos2 = [pos4[0], pos4[1] + 1.0, pos4[2], pos3[3]];
-                value += gradient(&self.perm_table, vertex, pos2);
+                value += gradient(&self.hasher, vertex, pos2);
             }
 
             // Contribution at (0, 1, 1, 1)
@@ -555,7 +563,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 1.0, 1.0]);
                 pos1 = [pos4[0] + 1.0, pos4[1], pos4[2], pos3[3]];
-                value += gradient(&self.perm_table, vertex, pos1);
+                value += gradient(&self.hasher, vertex, pos1);
             }
 
             // Contribution at (1, 1, 0, 0)
@@ -568,7 +576,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
                     pos3[2] + SQUISH_CONSTANT_4D,
                     pos4[3] + SQUISH_CONSTANT_4D,
                 ];
-                value += gradient(&self.perm_table, vertex, pos5);
+                value += gradient(&self.hasher, vertex, pos5);
             }
 
             // Contribution at (1, 0, 1, 0)
@@ -576,7 +584,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 1.0, 0.0]);
                 pos6 = [pos5[0], pos5[1] + 1.0, pos5[2] - 1.0, pos5[3]];
-                value += gradient(&self.perm_table, vertex, pos6);
+                value += gradient(&self.hasher, vertex, pos6);
             }
 
             // Contribution at (1, 0, 0, 1)
@@ -584,7 +592,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [1.0, 0.0, 0.0, 1.0]);
                 pos7 = [pos5[0], pos6[1], pos5[2], pos5[3] - 1.0];
-                value += gradient(&self.perm_table, vertex, pos7);
+                value += gradient(&self.hasher, vertex, pos7);
             }
 
             // Contribution at (0, 1, 1, 0)
@@ -592,7 +600,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 1.0, 0.0]);
                 pos8 = [pos5[0] + 1.0, pos5[1], pos6[2], pos5[3]];
-                value += gradient(&self.perm_table, vertex, pos8);
+                value += gradient(&self.hasher, vertex, pos8);
             }
 
             // Contribution at (0, 1, 0, 1)
@@ -600,7 +608,7 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 1.0, 0.0, 1.0]);
                 pos9 = [pos8[0], pos5[1], pos5[2], pos7[3]];
-                value += gradient(&self.perm_table, vertex, pos9);
+                value += gradient(&self.hasher, vertex, pos9);
             }
 
             // Contribution at (0, 0, 1, 1)
@@ -608,10 +616,64 @@ impl NoiseFn<f64, 4> for OpenSimplex {
             {
                 let vertex = math::add4(stretched_floor, [0.0, 0.0, 1.0, 1.0]);
                 pos10 = [pos8[0], pos6[1], pos6[2], pos7[3]];
-                value += gradient(&self.perm_table, vertex, pos10);
+                value += gradient(&self.hasher, vertex, pos10);
             }
         }
 
         value * NORM_CONSTANT_4D
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &OpenSimplex::new().set_seed(42),
+            &POINTS_2D,
+            &[
+                0.3598031604767443,
+                -0.44025410786290475,
+                0.19128756310523098,
+                -0.11973929653416976,
+                -0.1879319840601502,
+                -0.36047634839886755,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &OpenSimplex::new().set_seed(42),
+            &POINTS_3D,
+            &[
+                -0.03878420402348023,
+                0.020455013529276516,
+                -0.11507886147218613,
+                -0.09558298468187312,
+                0.06590962584873193,
+                -0.12050610662973704,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &OpenSimplex::new().set_seed(42),
+            &POINTS_4D,
+            &[
+                0.3461858275178698,
+                -0.021855631979090268,
+                0.29293891946818207,
+                0.4808200464251516,
+                0.5679681569373702,
+                0.2761553256015067,
+            ],
+        );
+    }
+}
@@ -1,25 +1,47 @@
 use crate::{
-    math::{self, s_curve::quintic::Quintic},
-    noise_fns::{NoiseFn, Seedable},
+    math,
+    noise_fns::{Interpolation, NoiseFn, Seedable},
     permutationtable::{NoiseHasher, PermutationTable},
 };
 
 /// Noise function that outputs 2/3/4-dimensional Perlin noise.
 #[derive(Clone, Copy, Debug)]
-pub struct Perlin {
-    seed: u32,
-    perm_table: PermutationTable,
+pub struct Perlin<H = PermutationTable> {
+    seed: u64,
+    hasher: H,
+    interpolation: Interpolation,
 }
 
 impl Perlin {
-    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_SEED: u64 = 0;
 
-    pub fn new(seed: u32) -> Self {
+    pub fn new(seed: u64) -> Self {
+        Self::with_hasher(seed)
+    }
+}
+
+impl<H: NoiseHasher> Perlin<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points.
+    pub fn with_hasher(seed: u64) -> Self {
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            hasher: H::from_seed(seed),
+            interpolation: Interpolation::Quintic,
         }
     }
+
+    /// Sets the curve used to blend between the corners of each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
 }
 
 impl Default for Perlin {
@@ -28,35 +50,40 @@ impl Default for Perlin {
     }
 }
 
-impl Seedable for Perlin {
+impl<H: NoiseHasher> Seedable for Perlin<H> {
     /// Sets the seed value for Perlin noise
-    fn set_seed(self, seed: u32) -> Self {
+    fn set_seed(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
         }
 
-        // Otherwise, regenerate the permutation table based on the new seed.
+        // Otherwise, regenerate the hasher based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            hasher: H::from_seed(seed),
+            ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
 /// 2-dimensional perlin noise
-impl NoiseFn<f64, 2> for Perlin {
+impl<H: NoiseHasher> NoiseFn<f64, 2> for Perlin<H> {
     fn get(&self, point: [f64; 2]) -> f64 {
-        perlin_2d(&self.perm_table, point)
+        perlin_2d(&self.hasher, self.interpolation, point)
     }
 }
 
 #[inline(always)]
-pub(crate) fn perlin_2d(hasher: &dyn NoiseHasher, point: [f64; 2]) -> f64 {
+pub(crate) fn perlin_2d(
+    hasher: &dyn NoiseHasher,
+    interpolation: Interpolation,
+    point: [f64; 2],
+) -> f64 {
     // Unscaled range of linearly interpolated perlin noise should be (-sqrt(N)/2, sqrt(N)/2).
     // Need to invert this value and multiply the unscaled result by the value to get a scaled
     // range of (-1, 1).
@@ -95,7 +122,7 @@ pub(crate) fn perlin_2d(hasher: &dyn NoiseHasher, point: [f64; 2]) -> f64 {
     );
     let g11 = gradient_dot_v(hasher.hash(&far_corner), far_distance);
 
-    let [u, v] = distance.map_quintic();
+    let [u, v] = interpolation.map(distance);
 
     let unscaled_result = bilinear_interpolation(u, v, g00, g01, g10, g11);
 
@@ -118,15 +145,19 @@ fn bilinear_interpolation(u: f64, v: f64, g00: f64, g01: f64, g10: f64, g11: f64
 }
 
 /// 3-dimensional perlin noise
-impl NoiseFn<f64, 3> for Perlin {
+impl<H: NoiseHasher> NoiseFn<f64, 3> for Perlin<H> {
     fn get(&self, point: [f64; 3]) -> f64 {
-        perlin_3d(&self.perm_table, point)
+        perlin_3d(&self.hasher, self.interpolation, point)
     }
 }
 
 #[inline(always)]
 #[allow(clippy::many_single_char_names)]
-pub(crate) fn perlin_3d(hasher: &dyn NoiseHasher, point: [f64; 3]) -> f64 {
+pub(crate) fn perlin_3d(
+    hasher: &dyn NoiseHasher,
+    interpolation: Interpolation,
+    point: [f64; 3],
+) -> f64 {
     // Unscaled range of linearly interpolated perlin noise should be (-sqrt(N)/2, sqrt(N)/2).
     // Need to invert this value and multiply the unscaled result by the value to get a scaled
     // range of (-1, 1).
@@ -192,7 +223,7 @@ pub(crate) fn perlin_3d(hasher: &dyn NoiseHasher, point: [f64; 3]) -> f64 {
     );
     let g111 = gradient_dot_v(hasher.hash(&far_corner), far_distance);
 
-    let [a, b, c] = distance.map_quintic();
+    let [a, b, c] = interpolation.map(distance);
 
     let k0 = g000;
     let k1 = g100 - g000;
@@ -215,16 +246,20 @@ pub(crate) fn perlin_3d(hasher: &dyn NoiseHasher, point: [f64; 3]) -> f64 {
 }
 
 /// 4-dimensional perlin noise
-impl NoiseFn<f64, 4> for Perlin {
+impl<H: NoiseHasher> NoiseFn<f64, 4> for Perlin<H> {
     fn get(&self, point: [f64; 4]) -> f64 {
-        perlin_4d(&self.perm_table, point)
+        perlin_4d(&self.hasher, self.interpolation, point)
     }
 }
 
 #[inline(always)]
 #[rustfmt::skip]
 #[allow(clippy::many_single_char_names)]
-pub(crate) fn perlin_4d(hasher: &dyn NoiseHasher, point: [f64; 4]) -> f64 {
+pub(crate) fn perlin_4d(
+    hasher: &dyn NoiseHasher,
+    interpolation: Interpolation,
+    point: [f64; 4],
+) -> f64 {
     // Unscaled range of linearly interpolated perlin noise should be (-sqrt(N)/2, sqrt(N)/2).
     // Need to invert this value and multiply the unscaled result by the value to get a scaled
     // range of (-1, 1).
@@ -453,7 +488,7 @@ pub(crate) fn perlin_4d(hasher: &dyn NoiseHasher, point: [f64; 4]) -> f64 {
         far_distance[3]],
     );
 
-    let [a, b, c, d] = distance.map_quintic();
+    let [a, b, c, d] = interpolation.map(distance);
 
     let k0 = g0000;
     let k1 = g1000 - g0000;
@@ -496,3 +531,74 @@ pub(crate) fn perlin_4d(hasher: &dyn NoiseHasher, point: [f64; 4]) -> f64 {
     // outliers and return it.
     scaled_result.clamp(-1.0, 1.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &Perlin::new(42),
+            &POINTS_2D,
+            &[
+                0.5910215477455981,
+                0.20830124750443021,
+                0.3560374505488812,
+                0.5717382589961949,
+                -0.30320427013668977,
+                -0.7131376032405851,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &Perlin::new(42),
+            &POINTS_3D,
+            &[
+                -0.39084798873430576,
+                0.37788916622259305,
+                -0.07607829161431803,
+                -0.022815168629762385,
+                0.2816619407956748,
+                0.2096793072146638,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &Perlin::new(42),
+            &POINTS_4D,
+            &[
+                0.265267504248186,
+                1.0,
+                -0.28827785497574293,
+                -0.4794905664062499,
+                -1.0,
+                0.5888626197529285,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let quintic = Perlin::new(42);
+        let linear = quintic.set_interpolation(Interpolation::Linear);
+        let cubic = quintic.set_interpolation(Interpolation::Cubic);
+
+        // All curves agree on the lattice points and at the cell centres.
+        for &point in &[[3.0, -2.0, 5.0], [0.5, 1.5, -0.5]] {
+            assert_eq!(linear.get(point), quintic.get(point));
+            assert_eq!(cubic.get(point), quintic.get(point));
+        }
+
+        let point = [0.3, 1.2, -0.7];
+        assert_ne!(linear.get(point), quintic.get(point));
+        assert_ne!(cubic.get(point), quintic.get(point));
+    }
+}
@@ -9,18 +9,18 @@ use crate::{
 /// THis is a variant of original perlin noise, based on the principles of simplex noise to
 /// calculate the values at a point using wavelets instead of interpolated gradients.
 #[derive(Clone, Copy, Debug)]
-pub struct Perlin {
-    seed: u32,
-    perm_table: PermutationTable,
+pub struct Perlin<H = PermutationTable> {
+    seed: u64,
+    hasher: H,
 }
 
 impl Perlin {
-    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_SEED: u64 = 0;
 
     pub fn new() -> Self {
         Self {
             seed: Self::DEFAULT_SEED,
-            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            hasher: PermutationTable::new(Self::DEFAULT_SEED),
         }
     }
 }
@@ -31,36 +31,36 @@ impl Default for Perlin {
     }
 }
 
-impl Seedable for Perlin {
+impl<H: NoiseHasher> Seedable for Perlin<H> {
     /// Sets the seed value for Perlin noise
-    fn set_seed(self, seed: u32) -> Self {
+    fn set_seed(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
         }
 
-        // Otherwise, regenerate the permutation table based on the new seed.
+        // Otherwise, regenerate the hasher based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            hasher: H::from_seed(seed),
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
 /// 2-dimensional perlin noise
-impl NoiseFn<f64, 2> for Perlin {
+impl<H: NoiseHasher> NoiseFn<f64, 2> for Perlin<H> {
     fn get(&self, point: [f64; 2]) -> f64 {
         const SCALE_FACTOR: f64 = 3.160_493_827_160_493_7;
 
         #[inline(always)]
-        fn surflet(perm_table: &PermutationTable, corner: [isize; 2], distance: [f64; 2]) -> f64 {
+        fn surflet(hasher: &dyn NoiseHasher, corner: [isize; 2], distance: [f64; 2]) -> f64 {
             let attn = 1.0 - math::dot2(distance, distance);
             if attn > 0.0 {
-                attn.powi(4) * math::dot2(distance, gradient::get2(perm_table.hash(&corner)))
+                math::powi(attn, 4) * math::dot2(distance, gradient::get2(hasher.hash(&corner)))
             } else {
                 0.0
             }
@@ -73,22 +73,22 @@ impl NoiseFn<f64, 2> for Perlin {
         let far_distance = math::sub2(near_distance, math::one2());
 
         let f00 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], near_corner[1]],
             [near_distance[0], near_distance[1]],
         );
         let f10 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], near_corner[1]],
             [far_distance[0], near_distance[1]],
         );
         let f01 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], far_corner[1]],
             [near_distance[0], far_distance[1]],
         );
         let f11 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1]],
             [far_distance[0], far_distance[1]],
         );
@@ -99,15 +99,15 @@ impl NoiseFn<f64, 2> for Perlin {
 }
 
 /// 3-dimensional perlin noise
-impl NoiseFn<f64, 3> for Perlin {
+impl<H: NoiseHasher> NoiseFn<f64, 3> for Perlin<H> {
     fn get(&self, point: [f64; 3]) -> f64 {
         const SCALE_FACTOR: f64 = 3.889_855_325_553_107_4;
 
         #[inline(always)]
-        fn surflet(perm_table: &PermutationTable, corner: [isize; 3], distance: [f64; 3]) -> f64 {
+        fn surflet(hasher: &dyn NoiseHasher, corner: [isize; 3], distance: [f64; 3]) -> f64 {
             let attn = 1.0 - math::dot3(distance, distance);
             if attn > 0.0 {
-                attn.powi(4) * math::dot3(distance, gradient::get3(perm_table.hash(&corner)))
+                math::powi(attn, 4) * math::dot3(distance, gradient::get3(hasher.hash(&corner)))
             } else {
                 0.0
             }
@@ -120,42 +120,42 @@ impl NoiseFn<f64, 3> for Perlin {
         let far_distance = math::sub3(near_distance, math::one3());
 
         let f000 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], near_corner[1], near_corner[2]],
             [near_distance[0], near_distance[1], near_distance[2]],
         );
         let f100 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], near_corner[1], near_corner[2]],
             [far_distance[0], near_distance[1], near_distance[2]],
         );
         let f010 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], far_corner[1], near_corner[2]],
             [near_distance[0], far_distance[1], near_distance[2]],
         );
         let f110 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1], near_corner[2]],
             [far_distance[0], far_distance[1], near_distance[2]],
         );
         let f001 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], near_corner[1], far_corner[2]],
             [near_distance[0], near_distance[1], far_distance[2]],
         );
         let f101 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], near_corner[1], far_corner[2]],
             [far_distance[0], near_distance[1], far_distance[2]],
         );
         let f011 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], far_corner[1], far_corner[2]],
             [near_distance[0], far_distance[1], far_distance[2]],
         );
         let f111 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1], far_corner[2]],
             [far_distance[0], far_distance[1], far_distance[2]],
         );
@@ -166,15 +166,15 @@ impl NoiseFn<f64, 3> for Perlin {
 }
 
 /// 4-dimensional perlin noise
-impl NoiseFn<f64, 4> for Perlin {
+impl<H: NoiseHasher> NoiseFn<f64, 4> for Perlin<H> {
     fn get(&self, point: [f64; 4]) -> f64 {
         const SCALE_FACTOR: f64 = 4.424_369_240_215_691;
 
         #[inline(always)]
-        fn surflet(perm_table: &PermutationTable, corner: [isize; 4], distance: [f64; 4]) -> f64 {
+        fn surflet(hasher: &dyn NoiseHasher, corner: [isize; 4], distance: [f64; 4]) -> f64 {
             let attn = 1.0 - math::dot4(distance, distance);
             if attn > 0.0 {
-                attn.powi(4) * math::dot4(distance, gradient::get4(perm_table.hash(&corner)))
+                math::powi(attn, 4) * math::dot4(distance, gradient::get4(hasher.hash(&corner)))
             } else {
                 0.0
             }
@@ -187,7 +187,7 @@ impl NoiseFn<f64, 4> for Perlin {
         let far_distance = math::sub4(near_distance, math::one4());
 
         let f0000 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [
                 near_corner[0],
                 near_corner[1],
@@ -202,7 +202,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f1000 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [
                 far_corner[0],
                 near_corner[1],
@@ -217,7 +217,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f0100 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [
                 near_corner[0],
                 far_corner[1],
@@ -232,7 +232,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f1100 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1], near_corner[2], near_corner[3]],
             [
                 far_distance[0],
@@ -242,7 +242,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f0010 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [
                 near_corner[0],
                 near_corner[1],
@@ -257,7 +257,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f1010 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], near_corner[1], far_corner[2], near_corner[3]],
             [
                 far_distance[0],
@@ -267,7 +267,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f0110 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], far_corner[1], far_corner[2], near_corner[3]],
             [
                 near_distance[0],
@@ -277,7 +277,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f1110 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1], far_corner[2], near_corner[3]],
             [
                 far_distance[0],
@@ -287,7 +287,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f0001 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [
                 near_corner[0],
                 near_corner[1],
@@ -302,7 +302,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f1001 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], near_corner[1], near_corner[2], far_corner[3]],
             [
                 far_distance[0],
@@ -312,7 +312,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f0101 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], far_corner[1], near_corner[2], far_corner[3]],
             [
                 near_distance[0],
@@ -322,7 +322,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f1101 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1], near_corner[2], far_corner[3]],
             [
                 far_distance[0],
@@ -332,7 +332,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f0011 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], near_corner[1], far_corner[2], far_corner[3]],
             [
                 near_distance[0],
@@ -342,7 +342,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f1011 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], near_corner[1], far_corner[2], far_corner[3]],
             [
                 far_distance[0],
@@ -352,7 +352,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f0111 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], far_corner[1], far_corner[2], far_corner[3]],
             [
                 near_distance[0],
@@ -362,7 +362,7 @@ impl NoiseFn<f64, 4> for Perlin {
             ],
         );
         let f1111 = surflet(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1], far_corner[2], far_corner[3]],
             [
                 far_distance[0],
@@ -90,18 +90,26 @@ const LATTICE_LOOKUP_3D: [[i8; 3]; 4 * 16] =
 
 /// Noise function that outputs 2/3-dimensional Super Simplex noise.
 #[derive(Clone, Copy, Debug)]
-pub struct SuperSimplex {
-    seed: u32,
-    perm_table: PermutationTable,
+pub struct SuperSimplex<H = PermutationTable> {
+    seed: u64,
+    hasher: H,
 }
 
 impl SuperSimplex {
-    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_SEED: u64 = 0;
 
     pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> SuperSimplex<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points.
+    pub fn with_hasher(seed: u64) -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            seed,
+            hasher: H::from_seed(seed),
         }
     }
 }
@@ -112,28 +120,28 @@ impl Default for SuperSimplex {
     }
 }
 
-impl Seedable for SuperSimplex {
+impl<H: NoiseHasher> Seedable for SuperSimplex<H> {
     /// Sets the seed value for Super Simplex noise
-    fn set_seed(self, seed: u32) -> Self {
+    fn set_seed(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
         }
 
-        // Otherwise, regenerate the permutation table based on the new seed.
+        // Otherwise, regenerate the hasher based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            hasher: H::from_seed(seed),
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
 /// 2-dimensional Super Simplex noise
-impl NoiseFn<f64, 2> for SuperSimplex {
+impl<H: NoiseHasher> NoiseFn<f64, 2> for SuperSimplex<H> {
     fn get(&self, point: [f64; 2]) -> f64 {
         let mut value = 0.0;
 
@@ -165,8 +173,8 @@ impl NoiseFn<f64, 2> for SuperSimplex {
             let attn = (2.0 / 3.0) - math::dot2(dpos, dpos);
             if attn > 0.0 {
                 let lattice_point = math::add2(simplex_base_point_i, math::cast2(lattice_lookup.0));
-                let gradient = gradient::get2(self.perm_table.hash(&lattice_point));
-                value += attn.powi(4) * math::dot2(gradient, dpos);
+                let gradient = gradient::get2(self.hasher.hash(&lattice_point));
+                value += math::powi(attn, 4) * math::dot2(gradient, dpos);
             }
         }
 
@@ -175,7 +183,7 @@ impl NoiseFn<f64, 2> for SuperSimplex {
 }
 
 /// 3-dimensional Super Simplex noise
-impl NoiseFn<f64, 3> for SuperSimplex {
+impl<H: NoiseHasher> NoiseFn<f64, 3> for SuperSimplex<H> {
     fn get(&self, point: [f64; 3]) -> f64 {
         let mut value = 0.0;
 
@@ -230,8 +238,8 @@ impl NoiseFn<f64, 3> for SuperSimplex {
             let attn = 0.75 - math::dot3(dpos, dpos);
             if attn > 0.0 {
                 let lattice_point = math::add3(simplex_base_point_i, math::cast3(lattice_lookup));
-                let gradient = gradient::get3(self.perm_table.hash(&lattice_point));
-                value += attn.powi(4) * math::dot3(gradient, dpos);
+                let gradient = gradient::get3(self.hasher.hash(&lattice_point));
+                value += math::powi(attn, 4) * math::dot3(gradient, dpos);
             }
         }
 
@@ -242,11 +250,49 @@ impl NoiseFn<f64, 3> for SuperSimplex {
             if attn > 0.0 {
                 let lattice_point =
                     math::add3(second_simplex_base_point_i, math::cast3(lattice_lookup));
-                let gradient = gradient::get3(self.perm_table.hash(&lattice_point));
-                value += attn.powi(4) * math::dot3(gradient, dpos);
+                let gradient = gradient::get3(self.hasher.hash(&lattice_point));
+                value += math::powi(attn, 4) * math::dot3(gradient, dpos);
             }
         }
 
         value * NORM_CONSTANT_3D
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D};
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &SuperSimplex::new().set_seed(42),
+            &POINTS_2D,
+            &[
+                -0.26571441270121426,
+                -0.3262924840798787,
+                0.18014095944640585,
+                -0.18928599682510502,
+                -0.5297623409798169,
+                0.2865147989187137,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &SuperSimplex::new().set_seed(42),
+            &POINTS_3D,
+            &[
+                0.7024735602764741,
+                0.16398524146188884,
+                0.10268451618578296,
+                0.33314622747409106,
+                0.07449352018428865,
+                -0.48960558565713413,
+            ],
+        );
+    }
+}
@@ -1,26 +1,47 @@
-use crate::math::s_curve::quintic::Quintic;
 use crate::{
     math::{self, interpolate},
-    noise_fns::{NoiseFn, Seedable},
+    noise_fns::{Interpolation, NoiseFn, Seedable},
     permutationtable::{NoiseHasher, PermutationTable},
 };
 
 /// Noise function that outputs 2/3/4-dimensional Value noise.
 #[derive(Clone, Copy, Debug)]
-pub struct Value {
-    seed: u32,
-    perm_table: PermutationTable,
+pub struct Value<H = PermutationTable> {
+    seed: u64,
+    hasher: H,
+    interpolation: Interpolation,
 }
 
 impl Value {
-    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_SEED: u64 = 0;
 
     pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> Value<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points.
+    pub fn with_hasher(seed: u64) -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            perm_table: PermutationTable::new(Self::DEFAULT_SEED),
+            seed,
+            hasher: H::from_seed(seed),
+            interpolation: Interpolation::Quintic,
         }
     }
+
+    /// Sets the curve used to blend between the corners of each lattice cell.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            interpolation,
+            ..self
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
 }
 
 impl Default for Value {
@@ -29,42 +50,43 @@ impl Default for Value {
     }
 }
 
-impl Seedable for Value {
+impl<H: NoiseHasher> Seedable for Value<H> {
     /// Sets the seed value for Value noise
-    fn set_seed(self, seed: u32) -> Self {
+    fn set_seed(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
         }
 
-        // Otherwise, regenerate the permutation table based on the new seed.
+        // Otherwise, regenerate the hasher based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            hasher: H::from_seed(seed),
+            ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
 /// 2-dimensional value noise
-impl NoiseFn<f64, 2> for Value {
+impl<H: NoiseHasher> NoiseFn<f64, 2> for Value<H> {
     fn get(&self, point: [f64; 2]) -> f64 {
-        fn get(perm_table: &PermutationTable, corner: [isize; 2]) -> f64 {
-            perm_table.hash(&corner) as f64 / 255.0
+        fn get(hasher: &dyn NoiseHasher, corner: [isize; 2]) -> f64 {
+            hasher.hash(&corner) as f64 / 255.0
         }
 
         let floored = math::map2(point, f64::floor);
         let near_corner = math::to_isize2(floored);
         let far_corner = math::add2(near_corner, math::one2());
-        let weight = math::sub2(point, floored).map_quintic();
+        let weight = self.interpolation.map(math::sub2(point, floored));
 
-        let f00 = get(&self.perm_table, [near_corner[0], near_corner[1]]);
-        let f10 = get(&self.perm_table, [far_corner[0], near_corner[1]]);
-        let f01 = get(&self.perm_table, [near_corner[0], far_corner[1]]);
-        let f11 = get(&self.perm_table, [far_corner[0], far_corner[1]]);
+        let f00 = get(&self.hasher, [near_corner[0], near_corner[1]]);
+        let f10 = get(&self.hasher, [far_corner[0], near_corner[1]]);
+        let f01 = get(&self.hasher, [near_corner[0], far_corner[1]]);
+        let f11 = get(&self.hasher, [far_corner[0], far_corner[1]]);
 
         let d0 = interpolate::linear(f00, f10, weight[0]);
         let d1 = interpolate::linear(f01, f11, weight[0]);
@@ -75,49 +97,37 @@ impl NoiseFn<f64, 2> for Value {
 }
 
 /// 3-dimensional value noise
-impl NoiseFn<f64, 3> for Value {
+impl<H: NoiseHasher> NoiseFn<f64, 3> for Value<H> {
     fn get(&self, point: [f64; 3]) -> f64 {
-        fn get(perm_table: &PermutationTable, corner: [isize; 3]) -> f64 {
-            perm_table.hash(&corner) as f64 / 255.0
+        fn get(hasher: &dyn NoiseHasher, corner: [isize; 3]) -> f64 {
+            hasher.hash(&corner) as f64 / 255.0
         }
 
         let floored = math::map3(point, f64::floor);
         let near_corner = math::to_isize3(floored);
         let far_corner = math::add3(near_corner, math::one3());
-        let weight = math::sub3(point, floored).map_quintic();
+        let weight = self.interpolation.map(math::sub3(point, floored));
 
         let f000 = get(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], near_corner[1], near_corner[2]],
         );
         let f100 = get(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], near_corner[1], near_corner[2]],
         );
         let f010 = get(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], far_corner[1], near_corner[2]],
         );
-        let f110 = get(
-            &self.perm_table,
-            [far_corner[0], far_corner[1], near_corner[2]],
-        );
+        let f110 = get(&self.hasher, [far_corner[0], far_corner[1], near_corner[2]]);
         let f001 = get(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], near_corner[1], far_corner[2]],
         );
-        let f101 = get(
-            &self.perm_table,
-            [far_corner[0], near_corner[1], far_corner[2]],
-        );
-        let f011 = get(
-            &self.perm_table,
-            [near_corner[0], far_corner[1], far_corner[2]],
-        );
-        let f111 = get(
-            &self.perm_table,
-            [far_corner[0], far_corner[1], far_corner[2]],
-        );
+        let f101 = get(&self.hasher, [far_corner[0], near_corner[1], far_corner[2]]);
+        let f011 = get(&self.hasher, [near_corner[0], far_corner[1], far_corner[2]]);
+        let f111 = get(&self.hasher, [far_corner[0], far_corner[1], far_corner[2]]);
 
         let d00 = interpolate::linear(f000, f100, weight[0]);
         let d01 = interpolate::linear(f001, f101, weight[0]);
@@ -132,19 +142,19 @@ impl NoiseFn<f64, 3> for Value {
 }
 
 /// 4-dimensional value noise
-impl NoiseFn<f64, 4> for Value {
+impl<H: NoiseHasher> NoiseFn<f64, 4> for Value<H> {
     fn get(&self, point: [f64; 4]) -> f64 {
-        fn get(perm_table: &PermutationTable, corner: [isize; 4]) -> f64 {
-            perm_table.hash(&corner) as f64 / 255.0
+        fn get(hasher: &dyn NoiseHasher, corner: [isize; 4]) -> f64 {
+            hasher.hash(&corner) as f64 / 255.0
         }
 
         let floored = math::map4(point, f64::floor);
         let near_corner = math::to_isize4(floored);
         let far_corner = math::add4(near_corner, math::one4());
-        let weight = math::sub4(point, floored).map_quintic();
+        let weight = self.interpolation.map(math::sub4(point, floored));
 
         let f0000 = get(
-            &self.perm_table,
+            &self.hasher,
             [
                 near_corner[0],
                 near_corner[1],
@@ -153,7 +163,7 @@ impl NoiseFn<f64, 4> for Value {
             ],
         );
         let f1000 = get(
-            &self.perm_table,
+            &self.hasher,
             [
                 far_corner[0],
                 near_corner[1],
@@ -162,7 +172,7 @@ impl NoiseFn<f64, 4> for Value {
             ],
         );
         let f0100 = get(
-            &self.perm_table,
+            &self.hasher,
             [
                 near_corner[0],
                 far_corner[1],
@@ -171,11 +181,11 @@ impl NoiseFn<f64, 4> for Value {
             ],
         );
         let f1100 = get(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1], near_corner[2], near_corner[3]],
         );
         let f0010 = get(
-            &self.perm_table,
+            &self.hasher,
             [
                 near_corner[0],
                 near_corner[1],
@@ -184,19 +194,19 @@ impl NoiseFn<f64, 4> for Value {
             ],
         );
         let f1010 = get(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], near_corner[1], far_corner[2], near_corner[3]],
         );
         let f0110 = get(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], far_corner[1], far_corner[2], near_corner[3]],
         );
         let f1110 = get(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1], far_corner[2], near_corner[3]],
         );
         let f0001 = get(
-            &self.perm_table,
+            &self.hasher,
             [
                 near_corner[0],
                 near_corner[1],
@@ -205,31 +215,31 @@ impl NoiseFn<f64, 4> for Value {
             ],
         );
         let f1001 = get(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], near_corner[1], near_corner[2], far_corner[3]],
         );
         let f0101 = get(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], far_corner[1], near_corner[2], far_corner[3]],
         );
         let f1101 = get(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1], near_corner[2], far_corner[3]],
         );
         let f0011 = get(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], near_corner[1], far_corner[2], far_corner[3]],
         );
         let f1011 = get(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], near_corner[1], far_corner[2], far_corner[3]],
         );
         let f0111 = get(
-            &self.perm_table,
+            &self.hasher,
             [near_corner[0], far_corner[1], far_corner[2], far_corner[3]],
         );
         let f1111 = get(
-            &self.perm_table,
+            &self.hasher,
             [far_corner[0], far_corner[1], far_corner[2], far_corner[3]],
         );
 
@@ -252,3 +262,83 @@ impl NoiseFn<f64, 4> for Value {
         d * 2.0 - 1.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    // `mul_add` is avoided when targeting emscripten or in strict mode, which
+    // changes the rounding of the interpolation.
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_2D: [f64; 6] = [
+        0.4911600449505975,
+        0.3564080123733335,
+        -0.32652517060513686,
+        0.09729474509803926,
+        -0.6254147187167522,
+        0.5558210295951855,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_2D: [f64; 6] = [
+        0.4911600449505975,
+        0.3564080123733335,
+        -0.32652517060513686,
+        0.09729474509803926,
+        -0.6254147187167521,
+        0.5558210295951853,
+    ];
+
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_3D: [f64; 6] = [
+        -0.2308459861605775,
+        -0.3916576979397691,
+        0.20158432497353962,
+        -0.3649724111519608,
+        0.36695894731557743,
+        -0.5397898514945971,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_3D: [f64; 6] = [
+        -0.2308459861605776,
+        -0.3916576979397691,
+        0.2015843249735394,
+        -0.3649724111519608,
+        0.36695894731557743,
+        -0.5397898514945971,
+    ];
+
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    const EXPECTED_4D: [f64; 6] = [
+        -0.49993264728737086,
+        0.1793137750980749,
+        -0.3211373312140233,
+        -0.405336075980392,
+        -0.18054177576107033,
+        0.4644802977657465,
+    ];
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    const EXPECTED_4D: [f64; 6] = [
+        -0.49993264728737075,
+        0.1793137750980751,
+        -0.3211373312140233,
+        -0.4053360759803921,
+        -0.18054177576107022,
+        0.4644802977657465,
+    ];
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(&Value::new().set_seed(42), &POINTS_2D, &EXPECTED_2D);
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(&Value::new().set_seed(42), &POINTS_3D, &EXPECTED_3D);
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(&Value::new().set_seed(42), &POINTS_4D, &EXPECTED_4D);
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
 };
 
 /// Noise function that outputs Worley noise.
-pub struct Worley {
+pub struct Worley<H = PermutationTable> {
     /// Specifies the distance function to use when calculating the boundaries of
     /// the cell.
     pub distance_function: Box<dyn Fn(&[f64], &[f64]) -> f64>,
@@ -17,21 +17,29 @@ pub struct Worley {
     /// Frequency of the seed points.
     pub frequency: f64,
 
-    seed: u32,
-    perm_table: PermutationTable,
+    seed: u64,
+    hasher: H,
 }
 
 impl Worley {
-    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_SEED: u64 = 0;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
 
-    pub fn new(seed: u32) -> Self {
+    pub fn new(seed: u64) -> Self {
+        Self::with_hasher(seed)
+    }
+}
+
+impl<H: NoiseHasher> Worley<H> {
+    /// Creates a new noise function with the given seed, using `H` to hash the
+    /// lattice points.
+    pub fn with_hasher(seed: u64) -> Self {
         Self {
-            perm_table: PermutationTable::new(seed),
+            hasher: H::from_seed(seed),
             seed,
             distance_function: Box::new(distance_functions::euclidean),
             return_type: ReturnType::Value,
-            frequency: Self::DEFAULT_FREQUENCY,
+            frequency: Worley::DEFAULT_FREQUENCY,
         }
     }
 
@@ -59,6 +67,70 @@ impl Worley {
     pub fn set_frequency(self, frequency: f64) -> Self {
         Self { frequency, ..self }
     }
+
+    /// Returns the seed points that lie within the given bounds, including
+    /// those on the lower bounds and excluding those on the upper bounds.
+    ///
+    /// These are the points that the output value is computed from, so
+    /// objects placed at them line up with the cells of the noise. Each cell
+    /// holds exactly one point, and the points are returned in order of their
+    /// cells, with the first axis varying fastest.
+    pub fn feature_points<const DIM: usize>(
+        &self,
+        lower: [f64; DIM],
+        upper: [f64; DIM],
+    ) -> Vec<FeaturePoint<DIM>>
+    where
+        [f64; DIM]: WorleyPoint,
+    {
+        let mut points = Vec::new();
+        if lower.iter().zip(&upper).any(|(l, u)| u <= l) {
+            return points;
+        }
+
+        // The seed point of each cell lies within half a cell of its lattice
+        // point.
+        let mut first = [0; DIM];
+        let mut last = [0; DIM];
+        for axis in 0..DIM {
+            first[axis] = (lower[axis] * self.frequency - 0.5).floor() as isize;
+            last[axis] = (upper[axis] * self.frequency + 0.5).ceil() as isize;
+        }
+
+        let mut cell = first;
+        loop {
+            let (mut position, hash) = feature_point(&self.hasher, cell);
+            for p in position.iter_mut() {
+                *p /= self.frequency;
+            }
+
+            let inside =
+                (0..DIM).all(|axis| position[axis] >= lower[axis] && position[axis] < upper[axis]);
+            if inside {
+                points.push(FeaturePoint {
+                    position,
+                    cell,
+                    hash,
+                });
+            }
+
+            // Step to the next cell.
+            let mut axis = 0;
+            loop {
+                if axis == DIM {
+                    return points;
+                }
+
+                cell[axis] += 1;
+                if cell[axis] <= last[axis] {
+                    break;
+                }
+
+                cell[axis] = first[axis];
+                axis += 1;
+            }
+        }
+    }
 }
 
 impl Default for Worley {
@@ -67,27 +139,52 @@ impl Default for Worley {
     }
 }
 
-impl Seedable for Worley {
+impl<H: NoiseHasher> Seedable for Worley<H> {
     /// Sets the seed value used by the Worley cells.
-    fn set_seed(self, seed: u32) -> Self {
+    fn set_seed(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
         }
 
-        // Otherwise, regenerate the permutation table based on the new seed.
+        // Otherwise, regenerate the hasher based on the new seed.
         Self {
-            perm_table: PermutationTable::new(seed),
+            hasher: H::from_seed(seed),
             seed,
             ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
+/// A seed point of [`Worley`] noise, as returned by
+/// [`Worley::feature_points`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeaturePoint<const DIM: usize> {
+    /// The position of the point.
+    pub position: [f64; DIM],
+
+    /// The lattice point of the cell holding the point, in the input space
+    /// scaled by the frequency.
+    pub cell: [isize; DIM],
+
+    /// The hash of the cell, from 0 to 255, which is stable for a given seed.
+    pub hash: usize,
+}
+
+impl<const DIM: usize> FeaturePoint<DIM> {
+    /// Returns the output value of [`Worley`] noise with
+    /// [`ReturnType::Value`] wherever this is the nearest point.
+    pub fn value(&self) -> f64 {
+        let value = self.hash as f64 / 255.0;
+
+        value * 2.0 - 1.0
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum ReturnType {
     Distance,
@@ -143,10 +240,10 @@ pub mod distance_functions {
     }
 }
 
-impl NoiseFn<f64, 2> for Worley {
+impl<H: NoiseHasher> NoiseFn<f64, 2> for Worley<H> {
     fn get(&self, point: [f64; 2]) -> f64 {
         worley_2d(
-            &self.perm_table,
+            &self.hasher,
             &self.distance_function,
             self.return_type,
             math::mul2(point, self.frequency),
@@ -154,6 +251,65 @@ impl NoiseFn<f64, 2> for Worley {
     }
 }
 
+/// Points in the dimensions that Worley noise is defined in, which are 2, 3
+/// and 4.
+///
+/// This trait is sealed, so it cannot be implemented outside of this crate.
+pub trait WorleyPoint: sealed::Sealed {
+    /// Returns the offset of the seed point of a cell from its lattice point,
+    /// given the hash of the cell.
+    #[doc(hidden)]
+    fn seed_offset(hash: usize) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for [f64; 2] {}
+    impl Sealed for [f64; 3] {}
+    impl Sealed for [f64; 4] {}
+}
+
+impl WorleyPoint for [f64; 2] {
+    fn seed_offset(hash: usize) -> Self {
+        get_vec2(hash)
+    }
+}
+
+impl WorleyPoint for [f64; 3] {
+    fn seed_offset(hash: usize) -> Self {
+        get_vec3(hash)
+    }
+}
+
+impl WorleyPoint for [f64; 4] {
+    fn seed_offset(hash: usize) -> Self {
+        get_vec4(hash)
+    }
+}
+
+/// Returns the seed point of the Worley cell around the given lattice point,
+/// along with the hash of the cell. The point lies within half a unit of the
+/// lattice point along each axis.
+#[inline]
+pub(crate) fn feature_point<const DIM: usize>(
+    hasher: &dyn NoiseHasher,
+    whole: [isize; DIM],
+) -> ([f64; DIM], usize)
+where
+    [f64; DIM]: WorleyPoint,
+{
+    let hash = hasher.hash(&whole);
+
+    let mut point = <[f64; DIM]>::seed_offset(hash);
+
+    for (p, &w) in point.iter_mut().zip(&whole) {
+        *p += w as f64;
+    }
+
+    (point, hash)
+}
+
 #[inline]
 fn worley_2d<F>(
     hasher: &dyn NoiseHasher,
@@ -164,11 +320,6 @@ fn worley_2d<F>(
 where
     F: Fn(&[f64], &[f64]) -> f64,
 {
-    #[inline]
-    fn get_point(hasher: &dyn NoiseHasher, whole: [isize; 2]) -> [f64; 2] {
-        math::add2(get_vec2(hasher.hash(&whole)), math::to_f64_2(whole))
-    }
-
     let cell = math::map2(point, f64::floor);
     let whole = math::to_isize2(cell);
     let frac = math::sub2(point, cell);
@@ -180,7 +331,7 @@ where
     let far = [whole[0] + (!x_half as isize), whole[1] + (!y_half as isize)];
 
     let mut seed_cell = near;
-    let seed_point = get_point(hasher, near);
+    let seed_point = feature_point(hasher, near).0;
     let mut distance = distance_function(&point, &seed_point);
 
     let x_distance = (0.5 - frac[0]) * (0.5 - frac[0]); // x-distance squared to center line
@@ -189,7 +340,7 @@ where
     macro_rules! test_point(
             [$x:expr, $y:expr] => {
                 {
-                    let cur_point = get_point(hasher, [$x, $y]);
+                    let cur_point = feature_point(hasher, [$x, $y]).0;
                     let cur_distance = distance_function(&point, &cur_point);
                     if cur_distance < distance {
                         distance = cur_distance;
@@ -237,10 +388,10 @@ fn get_vec2(index: usize) -> [f64; 2] {
     }
 }
 
-impl NoiseFn<f64, 3> for Worley {
+impl<H: NoiseHasher> NoiseFn<f64, 3> for Worley<H> {
     fn get(&self, point: [f64; 3]) -> f64 {
         worley_3d(
-            &self.perm_table,
+            &self.hasher,
             &self.distance_function,
             self.return_type,
             math::mul3(point, self.frequency),
@@ -258,10 +409,6 @@ fn worley_3d<F>(
 where
     F: Fn(&[f64], &[f64]) -> f64,
 {
-    fn get_point(hasher: &dyn NoiseHasher, whole: [isize; 3]) -> [f64; 3] {
-        math::add3(get_vec3(hasher.hash(&whole)), math::to_f64_3(whole))
-    }
-
     let cell = math::map3(point, f64::floor);
     let whole = math::to_isize3(cell);
     let frac = math::sub3(point, cell);
@@ -282,7 +429,7 @@ where
     ];
 
     let mut seed_cell = near;
-    let seed_point = get_point(hasher, near);
+    let seed_point = feature_point(hasher, near).0;
     let mut distance = distance_function(&point, &seed_point);
 
     let x_distance = (0.5 - frac[0]) * (0.5 - frac[0]); // x-distance squared to center line
@@ -292,7 +439,7 @@ where
     macro_rules! test_point(
             [$x:expr, $y:expr, $z:expr] => {
                 {
-                    let cur_point = get_point(hasher, [$x, $y, $z]);
+                    let cur_point = feature_point(hasher, [$x, $y, $z]).0;
                     let cur_distance = distance_function(&point, &cur_point);
                     if cur_distance < distance {
                         distance = cur_distance;
@@ -363,10 +510,10 @@ fn get_vec3(index: usize) -> [f64; 3] {
 }
 
 #[allow(clippy::cognitive_complexity)]
-impl NoiseFn<f64, 4> for Worley {
+impl<H: NoiseHasher> NoiseFn<f64, 4> for Worley<H> {
     fn get(&self, point: [f64; 4]) -> f64 {
         worley_4d(
-            &self.perm_table,
+            &self.hasher,
             &self.distance_function,
             self.return_type,
             math::mul4(point, self.frequency),
@@ -385,10 +532,6 @@ fn worley_4d<F>(
 where
     F: Fn(&[f64], &[f64]) -> f64,
 {
-    fn get_point(hasher: &dyn NoiseHasher, whole: [isize; 4]) -> [f64; 4] {
-        math::add4(get_vec4(hasher.hash(&whole)), math::to_f64_4(whole))
-    }
-
     let cell = math::map4(point, f64::floor);
     let whole = math::to_isize4(cell);
     let frac = math::sub4(point, cell);
@@ -409,19 +552,19 @@ where
     ];
 
     let mut seed_cell = near;
-    let seed_point = get_point(hasher, near);
+    let seed_point = feature_point(hasher, near).0;
     let mut distance = distance_function(&point, &seed_point);
 
     // get distance squared to center line for each axis
     let center_distance = frac
         .iter()
-        .map(|a| (0.5 - a).powf(2.0))
+        .map(|a| math::powf(0.5 - a, 2.0))
         .collect::<Vec<f64>>();
 
     macro_rules! test_point(
             [$x:expr, $y:expr, $z:expr, $w:expr] => {
                 {
-                    let cur_point = get_point(hasher, [$x, $y, $z, $w]);
+                    let cur_point = feature_point(hasher, [$x, $y, $z, $w]).0;
                     let cur_distance = distance_function(&point, &cur_point);
                     if cur_distance < distance {
                         distance = cur_distance;
@@ -545,3 +688,83 @@ fn get_vec4(index: usize) -> [f64; 4] {
         _ => panic!("Attempt to access 4D gradient {} of 32", index % 32),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::generators::golden::{assert_golden, POINTS_2D, POINTS_3D, POINTS_4D};
+
+    #[test]
+    fn test_feature_points() {
+        let worley = Worley::new(42).set_frequency(2.0);
+        let points = worley.feature_points([-1.0, -1.0, -1.0], [1.0, 1.0, 1.0]);
+
+        // One point per cell on average, with 2 cells per unit along each axis.
+        assert!(points.len() > 40 && points.len() < 90);
+
+        // Each point lies in its own cell, and takes its cell's value.
+        let distance = Worley::new(42)
+            .set_frequency(2.0)
+            .set_return_type(ReturnType::Distance);
+        for point in &points {
+            assert_eq!(point.value(), worley.get(point.position));
+            assert!(distance.get(point.position) < -1.0 + 1e-9);
+        }
+
+        // Splitting the region does not change the points.
+        let mut halves = worley.feature_points([-1.0, -1.0, -1.0], [0.0, 1.0, 1.0]);
+        halves.extend(worley.feature_points([0.0, -1.0, -1.0], [1.0, 1.0, 1.0]));
+        assert_eq!(points.len(), halves.len());
+        for point in &points {
+            assert!(halves.contains(point));
+        }
+    }
+
+    #[test]
+    fn test_golden_2d() {
+        assert_golden(
+            &Worley::new(42),
+            &POINTS_2D,
+            &[
+                0.5529411764705883,
+                0.4039215686274509,
+                -0.592156862745098,
+                0.15294117647058814,
+                -0.6549019607843137,
+                -0.11372549019607847,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_3d() {
+        assert_golden(
+            &Worley::new(42),
+            &POINTS_3D,
+            &[
+                -0.9529411764705882,
+                -0.7568627450980392,
+                -0.17647058823529416,
+                -0.615686274509804,
+                0.2784313725490195,
+                -0.6862745098039216,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_golden_4d() {
+        assert_golden(
+            &Worley::new(42),
+            &POINTS_4D,
+            &[
+                -0.8274509803921568,
+                0.050980392156862786,
+                -0.7176470588235294,
+                -0.6549019607843137,
+                0.12941176470588234,
+                0.5764705882352941,
+            ],
+        );
+    }
+}
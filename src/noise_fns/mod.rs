@@ -1,11 +1,14 @@
 pub use self::{
-    cache::*, combiners::*, generators::*, modifiers::*, selectors::*, transformers::*,
+    cache::*, combiners::*, ext::*, generators::*, modifiers::*, ops::*, selectors::*,
+    transformers::*,
 };
 
 mod cache;
 mod combiners;
-mod generators;
+mod ext;
+pub(crate) mod generators;
 mod modifiers;
+mod ops;
 mod selectors;
 mod transformers;
 
@@ -24,20 +27,87 @@ mod transformers;
 /// * Combining the output values from two noise functions in various ways.
 pub trait NoiseFn<T, const DIM: usize> {
     fn get(&self, point: [T; DIM]) -> f64;
+
+    /// Calculates the output value at the given point, for a point sampled
+    /// with the given footprint, which is the spacing between neighbouring
+    /// samples, such as the size of a pixel in the input space.
+    ///
+    /// Noise functions with detail finer than the footprint can fade it out
+    /// to avoid aliasing, as the fractals do with their octaves. Each octave,
+    /// the first included, is blended toward its expected value as its
+    /// frequency rises past half a cycle per footprint, and replaced by it
+    /// past a full cycle, so the mean output stays the same as the footprint
+    /// grows. Others pass the footprint on to their sources, scaled by any
+    /// transformation of the input. A footprint of zero gives the same output
+    /// as [`get`](Self::get), which is also what the default implementation
+    /// returns for any footprint.
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        let _ = footprint;
+        self.get(point)
+    }
 }
 
-impl<'a, T, M: NoiseFn<T, DIM>, const DIM: usize> NoiseFn<T, DIM> for &'a M {
+impl<'a, T, M: NoiseFn<T, DIM> + ?Sized, const DIM: usize> NoiseFn<T, DIM> for &'a M {
     #[inline]
     fn get(&self, point: [T; DIM]) -> f64 {
         M::get(*self, point)
     }
+
+    #[inline]
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        M::get_with_footprint(*self, point, footprint)
+    }
+}
+
+impl<T, M: NoiseFn<T, DIM> + ?Sized, const DIM: usize> NoiseFn<T, DIM> for Box<M> {
+    #[inline]
+    fn get(&self, point: [T; DIM]) -> f64 {
+        M::get(self, point)
+    }
+
+    #[inline]
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        M::get_with_footprint(self, point, footprint)
+    }
 }
 
 /// Trait for functions that require a seed before generating their values
+///
+/// Functions that are built from several seeded sub-functions derive the
+/// seeds of those sub-functions with [`mix_seed`](crate::mix_seed), so any
+/// `u64` value is a valid seed.
 pub trait Seedable {
     /// Set the seed for the function implementing the `Seedable` trait
-    fn set_seed(self, seed: u32) -> Self;
+    fn set_seed(self, seed: u64) -> Self;
 
     /// Getter to retrieve the seed from the function
-    fn seed(&self) -> u32;
+    fn seed(&self) -> u64;
+}
+
+/// Source for tests that outputs the footprint it is sampled with, to check
+/// the footprint a function passes on to its sources.
+#[cfg(test)]
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct FootprintProbe;
+
+#[cfg(test)]
+impl<const DIM: usize> NoiseFn<f64, DIM> for FootprintProbe {
+    fn get(&self, _point: [f64; DIM]) -> f64 {
+        0.0
+    }
+
+    fn get_with_footprint(&self, _point: [f64; DIM], footprint: f64) -> f64 {
+        footprint
+    }
+}
+
+#[cfg(test)]
+impl Seedable for FootprintProbe {
+    fn set_seed(self, _seed: u64) -> Self {
+        self
+    }
+
+    fn seed(&self) -> u64 {
+        0
+    }
 }
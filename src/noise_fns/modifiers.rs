@@ -1,9 +1,12 @@
-pub use self::{abs::*, clamp::*, curve::*, exponent::*, negate::*, scale_bias::*, terrace::*};
+pub use self::{
+    abs::*, clamp::*, curve::*, exponent::*, map::*, negate::*, scale_bias::*, terrace::*,
+};
 
 mod abs;
 mod clamp;
 mod curve;
 mod exponent;
+mod map;
 mod negate;
 mod scale_bias;
 mod terrace;
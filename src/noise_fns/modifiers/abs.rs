@@ -2,19 +2,27 @@ use crate::noise_fns::NoiseFn;
 
 /// Noise function that outputs the absolute value of the output value from the
 /// source function.
-pub struct Abs<'a, T, const DIM: usize> {
+#[derive(Clone, Debug)]
+pub struct Abs<Source> {
     /// Outputs a value.
-    pub source: &'a dyn NoiseFn<T, DIM>,
+    pub source: Source,
 }
 
-impl<'a, T, const DIM: usize> Abs<'a, T, DIM> {
-    pub fn new(source: &'a dyn NoiseFn<T, DIM>) -> Self {
+impl<Source> Abs<Source> {
+    pub fn new(source: Source) -> Self {
         Self { source }
     }
 }
 
-impl<'a, T, const DIM: usize> NoiseFn<T, DIM> for Abs<'a, T, DIM> {
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Abs<Source>
+where
+    Source: NoiseFn<T, DIM>,
+{
     fn get(&self, point: [T; DIM]) -> f64 {
-        (self.source.get(point)).abs()
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        (self.source.get_with_footprint(point, footprint)).abs()
     }
 }
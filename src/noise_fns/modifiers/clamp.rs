@@ -2,16 +2,17 @@ use crate::noise_fns::NoiseFn;
 
 /// Noise function that clamps the output value from the source function to a
 /// range of values.
-pub struct Clamp<'a, T, const DIM: usize> {
+#[derive(Clone, Debug)]
+pub struct Clamp<Source> {
     /// Outputs a value.
-    pub source: &'a dyn NoiseFn<T, DIM>,
+    pub source: Source,
 
     /// Bound of the clamping range. Default is -1.0 to 1.0.
     pub bounds: (f64, f64),
 }
 
-impl<'a, T, const DIM: usize> Clamp<'a, T, DIM> {
-    pub fn new(source: &'a dyn NoiseFn<T, DIM>) -> Self {
+impl<Source> Clamp<Source> {
+    pub fn new(source: Source) -> Self {
         Self {
             source,
             bounds: (-1.0, 1.0),
@@ -40,9 +41,16 @@ impl<'a, T, const DIM: usize> Clamp<'a, T, DIM> {
     }
 }
 
-impl<'a, T, const DIM: usize> NoiseFn<T, DIM> for Clamp<'a, T, DIM> {
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Clamp<Source>
+where
+    Source: NoiseFn<T, DIM>,
+{
     fn get(&self, point: [T; DIM]) -> f64 {
-        let value = self.source.get(point);
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        let value = self.source.get_with_footprint(point, footprint);
 
         value.clamp(self.bounds.0, self.bounds.1)
     }
@@ -14,21 +14,23 @@ use crate::{math::interpolate, noise_fns::NoiseFn};
 /// four control points to the curve. If there is less than four control
 /// points, the get() method panics. Each control point can have any input
 /// and output value, although no two control points can have the same input.
-pub struct Curve<'a, T, const DIM: usize> {
+#[derive(Clone, Debug)]
+pub struct Curve<Source> {
     /// Outputs a value.
-    pub source: &'a dyn NoiseFn<T, DIM>,
+    pub source: Source,
 
     /// Vec that stores the control points.
     control_points: Vec<ControlPoint<f64>>,
 }
 
+#[derive(Clone, Debug)]
 struct ControlPoint<T> {
     input: T,
     output: T,
 }
 
-impl<'a, T, const DIM: usize> Curve<'a, T, DIM> {
-    pub fn new(source: &'a dyn NoiseFn<T, DIM>) -> Self {
+impl<Source> Curve<Source> {
+    pub fn new(source: Source) -> Self {
         Self {
             source,
             control_points: Vec::with_capacity(4),
@@ -64,13 +66,20 @@ impl<'a, T, const DIM: usize> Curve<'a, T, DIM> {
     }
 }
 
-impl<'a, T, const DIM: usize> NoiseFn<T, DIM> for Curve<'a, T, DIM> {
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Curve<Source>
+where
+    Source: NoiseFn<T, DIM>,
+{
     fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
         // confirm that there's at least 4 control points in the vector.
         assert!(self.control_points.len() >= 4);
 
         // get output value from the source function
-        let source_value = self.source.get(point);
+        let source_value = self.source.get_with_footprint(point, footprint);
 
         // Find the first element in the control point array that has a input
         // value larger than the output value from the source function
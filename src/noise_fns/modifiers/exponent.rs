@@ -1,4 +1,7 @@
-use crate::{math::scale_shift, noise_fns::NoiseFn};
+use crate::{
+    math::{self, scale_shift},
+    noise_fns::NoiseFn,
+};
 
 /// Noise function that maps the output value from the source function onto an
 /// exponential curve.
@@ -7,17 +10,18 @@ use crate::{math::scale_shift, noise_fns::NoiseFn};
 /// this noise function first normalizes the output value (the range becomes 0.0
 /// to 1.0), maps that value onto an exponential curve, then rescales that
 /// value back to the original range.
-pub struct Exponent<'a, T, const DIM: usize> {
+#[derive(Clone, Debug)]
+pub struct Exponent<Source> {
     /// Outputs a value.
-    pub source: &'a dyn NoiseFn<T, DIM>,
+    pub source: Source,
 
     /// Exponent to apply to the output value from the source function. Default
     /// is 1.0.
     pub exponent: f64,
 }
 
-impl<'a, T, const DIM: usize> Exponent<'a, T, DIM> {
-    pub fn new(source: &'a dyn NoiseFn<T, DIM>) -> Self {
+impl<Source> Exponent<Source> {
+    pub fn new(source: Source) -> Self {
         Self {
             source,
             exponent: 1.0,
@@ -29,12 +33,19 @@ impl<'a, T, const DIM: usize> Exponent<'a, T, DIM> {
     }
 }
 
-impl<'a, T, const DIM: usize> NoiseFn<T, DIM> for Exponent<'a, T, DIM> {
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Exponent<Source>
+where
+    Source: NoiseFn<T, DIM>,
+{
     fn get(&self, point: [T; DIM]) -> f64 {
-        let mut value = self.source.get(point);
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        let mut value = self.source.get_with_footprint(point, footprint);
         value = (value + 1.0) / 2.0;
         value = value.abs();
-        value = value.powf(self.exponent);
+        value = math::powf(value, self.exponent);
         scale_shift(value, 2.0)
     }
 }
@@ -0,0 +1,35 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that maps the output value from the source function through
+/// a closure.
+#[derive(Clone, Copy, Debug)]
+pub struct Map<Source, F> {
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Closure applied to the output value from the source function.
+    pub map: F,
+}
+
+impl<Source, F> Map<Source, F>
+where
+    F: Fn(f64) -> f64,
+{
+    pub fn new(source: Source, map: F) -> Self {
+        Self { source, map }
+    }
+}
+
+impl<T, Source, F, const DIM: usize> NoiseFn<T, DIM> for Map<Source, F>
+where
+    Source: NoiseFn<T, DIM>,
+    F: Fn(f64) -> f64,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        (self.map)(self.source.get_with_footprint(point, footprint))
+    }
+}
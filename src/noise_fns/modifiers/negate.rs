@@ -1,19 +1,27 @@
 use crate::noise_fns::NoiseFn;
 
 /// Noise function that negates the output value from the source function.
-pub struct Negate<'a, T, const DIM: usize> {
+#[derive(Clone, Debug)]
+pub struct Negate<Source> {
     /// Outputs a value.
-    pub source: &'a dyn NoiseFn<T, DIM>,
+    pub source: Source,
 }
 
-impl<'a, T, const DIM: usize> Negate<'a, T, DIM> {
-    pub fn new(source: &'a dyn NoiseFn<T, DIM>) -> Self {
+impl<Source> Negate<Source> {
+    pub fn new(source: Source) -> Self {
         Negate { source }
     }
 }
 
-impl<'a, T, const DIM: usize> NoiseFn<T, DIM> for Negate<'a, T, DIM> {
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Negate<Source>
+where
+    Source: NoiseFn<T, DIM>,
+{
     fn get(&self, point: [T; DIM]) -> f64 {
-        -self.source.get(point)
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        -self.source.get_with_footprint(point, footprint)
     }
 }
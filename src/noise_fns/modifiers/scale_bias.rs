@@ -5,9 +5,10 @@ use crate::noise_fns::NoiseFn;
 ///
 /// The function retrieves the output value from the source function, multiplies
 /// it with the scaling factor, adds the bias to it, then outputs the value.
-pub struct ScaleBias<'a, T, const DIM: usize> {
+#[derive(Clone, Debug)]
+pub struct ScaleBias<Source> {
     /// Outputs a value.
-    pub source: &'a dyn NoiseFn<T, DIM>,
+    pub source: Source,
 
     /// Scaling factor to apply to the output value from the source function.
     /// The default value is 1.0.
@@ -18,8 +19,8 @@ pub struct ScaleBias<'a, T, const DIM: usize> {
     pub bias: f64,
 }
 
-impl<'a, T, const DIM: usize> ScaleBias<'a, T, DIM> {
-    pub fn new(source: &'a dyn NoiseFn<T, DIM>) -> Self {
+impl<Source> ScaleBias<Source> {
+    pub fn new(source: Source) -> Self {
         Self {
             source,
             scale: 1.0,
@@ -36,14 +37,21 @@ impl<'a, T, const DIM: usize> ScaleBias<'a, T, DIM> {
     }
 }
 
-impl<'a, T, const DIM: usize> NoiseFn<T, DIM> for ScaleBias<'a, T, DIM> {
-    #[cfg(not(target_os = "emscripten"))]
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for ScaleBias<Source>
+where
+    Source: NoiseFn<T, DIM>,
+{
     fn get(&self, point: [T; DIM]) -> f64 {
-        (self.source.get(point)).mul_add(self.scale, self.bias)
+        self.get_with_footprint(point, 0.0)
     }
 
-    #[cfg(target_os = "emscripten")]
-    fn get(&self, point: [T; DIM]) -> f64 {
-        (self.source.get(point) * self.scale) + self.bias
+    #[cfg(not(any(target_os = "emscripten", feature = "strict")))]
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        (self.source.get_with_footprint(point, footprint)).mul_add(self.scale, self.bias)
+    }
+
+    #[cfg(any(target_os = "emscripten", feature = "strict"))]
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        (self.source.get_with_footprint(point, footprint) * self.scale) + self.bias
     }
 }
@@ -23,9 +23,10 @@ use crate::{math::interpolate, noise_fns::NoiseFn};
 ///
 /// This noise function is often used to generate terrain features such as the
 /// stereotypical desert canyon.
-pub struct Terrace<'a, T, const DIM: usize> {
+#[derive(Clone, Debug)]
+pub struct Terrace<Source> {
     /// Outputs a value.
-    pub source: &'a dyn NoiseFn<T, DIM>,
+    pub source: Source,
 
     /// Determines if the terrace-forming curve between all control points is
     /// inverted.
@@ -35,8 +36,8 @@ pub struct Terrace<'a, T, const DIM: usize> {
     control_points: Vec<f64>,
 }
 
-impl<'a, T, const DIM: usize> Terrace<'a, T, DIM> {
-    pub fn new(source: &'a dyn NoiseFn<T, DIM>) -> Self {
+impl<Source> Terrace<Source> {
+    pub fn new(source: Source) -> Self {
         Terrace {
             source,
             invert_terraces: false,
@@ -84,13 +85,20 @@ impl<'a, T, const DIM: usize> Terrace<'a, T, DIM> {
     }
 }
 
-impl<'a, T, const DIM: usize> NoiseFn<T, DIM> for Terrace<'a, T, DIM> {
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Terrace<Source>
+where
+    Source: NoiseFn<T, DIM>,
+{
     fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
         // confirm that there's at least 2 control points in the vector.
         assert!(self.control_points.len() >= 2);
 
         // get output value from the source function
-        let source_value = self.source.get(point);
+        let source_value = self.source.get_with_footprint(point, footprint);
 
         // Find the first element in the control point array that has a input
         // value larger than the output value from the source function
@@ -0,0 +1,220 @@
+//! Arithmetic operators for building noise function graphs as expressions.
+//!
+//! The operators take their operands by value and build the corresponding
+//! combiner or modifier:
+//!
+//! | Expression       | Result                          |
+//! |------------------|---------------------------------|
+//! | `a + b`          | `Add::new(a, b)`                |
+//! | `a - b`          | `Subtract::new(a, b)`           |
+//! | `a * b`          | `Multiply::new(a, b)`           |
+//! | `-a`             | `Negate::new(a)`                |
+//! | `a * s`, `s * a` | `ScaleBias` with a scale of `s` |
+//! | `a + s`, `s + a` | `ScaleBias` with a bias of `s`  |
+//! | `a - s`          | `ScaleBias` with a bias of `-s` |
+//!
+//! where `a` and `b` are noise functions and `s` is an `f64`.
+
+use crate::{
+    noise_fns::{
+        Abs, Add, AnisotropicFbm, AnisotropicRidgedMulti, Average, BasicMulti, Billow, BiomeSelect,
+        Blend, Cache, Checkerboard, Clamp, Closure, Combine, Constant, Curve, Cylinders,
+        DerivativeFbm, Displace, Divide, Exponent, Fbm, Gabor, HybridMulti, JordanTurbulence, Map,
+        MapPoint, Max, Min, MultiSelect, Multiply, Negate, OpenSimplex, Power, RidgedMulti,
+        RotatePoint, ScaleBias, ScalePoint, Select, SmoothMax, SmoothMin, Subtract, SuperSimplex,
+        SwissTurbulence, Terrace, Transform, TranslatePoint, Turbulence, Value, Wavelet,
+        WaveletFbm, WeightedSum, Worley,
+    },
+    utils::NoiseMapSampler,
+    Perlin,
+};
+use std::ops;
+
+/// Marker trait for noise functions that can be used as the right-hand operand
+/// of the arithmetic operators implemented on the noise functions of this
+/// crate, and that get the methods of [`NoiseFnExt`](crate::NoiseFnExt).
+///
+/// Every noise function in this crate implements it, as do references to them.
+/// Implement it for your own noise functions to use them in such expressions,
+/// as in `perlin + custom`.
+pub trait NoiseOperand {}
+
+impl<M: NoiseOperand + ?Sized> NoiseOperand for &M {}
+
+macro_rules! impl_noise_ops {
+    ($([$($gen:ident),* $(; $($c:ident),*)?] $ty:ty),* $(,)?) => {$(
+        impl<$($gen,)* $($(const $c: usize),*)?> NoiseOperand for $ty {}
+
+        impl<$($gen,)* Rhs: NoiseOperand $($(, const $c: usize)*)?> ops::Add<Rhs> for $ty {
+            type Output = Add<Self, Rhs>;
+
+            fn add(self, rhs: Rhs) -> Self::Output {
+                Add::new(self, rhs)
+            }
+        }
+
+        impl<$($gen,)* Rhs: NoiseOperand $($(, const $c: usize)*)?> ops::Sub<Rhs> for $ty {
+            type Output = Subtract<Self, Rhs>;
+
+            fn sub(self, rhs: Rhs) -> Self::Output {
+                Subtract::new(self, rhs)
+            }
+        }
+
+        impl<$($gen,)* Rhs: NoiseOperand $($(, const $c: usize)*)?> ops::Mul<Rhs> for $ty {
+            type Output = Multiply<Self, Rhs>;
+
+            fn mul(self, rhs: Rhs) -> Self::Output {
+                Multiply::new(self, rhs)
+            }
+        }
+
+        impl<$($gen,)* $($(const $c: usize),*)?> ops::Neg for $ty {
+            type Output = Negate<Self>;
+
+            fn neg(self) -> Self::Output {
+                Negate::new(self)
+            }
+        }
+
+        impl<$($gen,)* $($(const $c: usize),*)?> ops::Add<f64> for $ty {
+            type Output = ScaleBias<Self>;
+
+            fn add(self, bias: f64) -> Self::Output {
+                ScaleBias::new(self).set_bias(bias)
+            }
+        }
+
+        impl<$($gen,)* $($(const $c: usize),*)?> ops::Sub<f64> for $ty {
+            type Output = ScaleBias<Self>;
+
+            fn sub(self, bias: f64) -> Self::Output {
+                ScaleBias::new(self).set_bias(-bias)
+            }
+        }
+
+        impl<$($gen,)* $($(const $c: usize),*)?> ops::Mul<f64> for $ty {
+            type Output = ScaleBias<Self>;
+
+            fn mul(self, scale: f64) -> Self::Output {
+                ScaleBias::new(self).set_scale(scale)
+            }
+        }
+
+        impl<$($gen,)* $($(const $c: usize),*)?> ops::Add<$ty> for f64 {
+            type Output = ScaleBias<$ty>;
+
+            fn add(self, source: $ty) -> Self::Output {
+                ScaleBias::new(source).set_bias(self)
+            }
+        }
+
+        impl<$($gen,)* $($(const $c: usize),*)?> ops::Mul<$ty> for f64 {
+            type Output = ScaleBias<$ty>;
+
+            fn mul(self, source: $ty) -> Self::Output {
+                ScaleBias::new(source).set_scale(self)
+            }
+        }
+    )*};
+}
+
+impl_noise_ops! {
+    // generators
+    [] Checkerboard,
+    [F] Closure<F>,
+    [] Constant,
+    [] Cylinders,
+    [H] Gabor<H>,
+    [H] OpenSimplex<H>,
+    [H] Perlin<H>,
+    [H] SuperSimplex<H>,
+    [H] Value<H>,
+    [] Wavelet,
+    [H] Worley<H>,
+    [H, C] AnisotropicFbm<H, C>,
+    [H, C] AnisotropicRidgedMulti<H, C>,
+    [H] BasicMulti<H>,
+    [H] Billow<H>,
+    [H] DerivativeFbm<H>,
+    [H] Fbm<H>,
+    [H] HybridMulti<H>,
+    [H] JordanTurbulence<H>,
+    [H] RidgedMulti<H>,
+    [H] SwissTurbulence<H>,
+    [] WaveletFbm,
+
+    // combiners
+    [Source1, Source2] Add<Source1, Source2>,
+    [Source] Average<Source>,
+    [Source1, Source2, F] Combine<Source1, Source2, F>,
+    [Source1, Source2] Divide<Source1, Source2>,
+    [Source1, Source2] Max<Source1, Source2>,
+    [Source1, Source2] Min<Source1, Source2>,
+    [Source1, Source2] Multiply<Source1, Source2>,
+    [Source1, Source2] Power<Source1, Source2>,
+    [Source1, Source2] SmoothMax<Source1, Source2>,
+    [Source1, Source2] SmoothMin<Source1, Source2>,
+    [Source1, Source2] Subtract<Source1, Source2>,
+    [Source] WeightedSum<Source>,
+
+    // modifiers
+    [Source] Abs<Source>,
+    [Source] Clamp<Source>,
+    [Source] Curve<Source>,
+    [Source] Exponent<Source>,
+    [Source, F] Map<Source, F>,
+    [Source] Negate<Source>,
+    [Source] ScaleBias<Source>,
+    [Source] Terrace<Source>,
+
+    // selectors
+    [Control1, Control2, Source] BiomeSelect<Control1, Control2, Source>,
+    [Source1, Source2, Control] Blend<Source1, Source2, Control>,
+    [Control, Source] MultiSelect<Control, Source>,
+    [Source1, Source2, Control] Select<Source1, Source2, Control>,
+
+    // transformers
+    [Source, X, Y, Z, U] Displace<Source, X, Y, Z, U>,
+    [Source, F] MapPoint<Source, F>,
+    [Source] RotatePoint<Source>,
+    [Source] ScalePoint<Source>,
+    [Source; DIM] Transform<Source, DIM>,
+    [Source] TranslatePoint<Source>,
+    [Source, F] Turbulence<Source, F>,
+
+    [Source] Cache<Source>,
+
+    // utils
+    [] NoiseMapSampler,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{noise_fn, Constant, NoiseFn, Select};
+
+    #[test]
+    fn test_operators() {
+        let a = Constant::new(2.0);
+        let b = noise_fn(|[x, _]: [f64; 2]| x);
+
+        let expr = (a + b) * 3.0 - a;
+        assert_eq!(7.0, expr.get([1.0, 0.0]));
+
+        let expr = -(a * b) + 0.5;
+        assert_eq!(-3.5, expr.get([2.0, 0.0]));
+
+        let expr = 2.0 * (a - Constant::new(1.0)) + 1.0;
+        assert_eq!(3.0, expr.get([0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_selector_operators() {
+        let control = noise_fn(|[x, _]: [f64; 2]| x);
+        let select = Select::new(Constant::new(-1.0), Constant::new(1.0), control);
+
+        let expr = select * 2.0 + Constant::new(0.5);
+        assert_eq!(-1.5, expr.get([-0.5, 0.0]));
+        assert_eq!(2.5, expr.get([0.5, 0.0]));
+    }
+}
@@ -1,4 +1,5 @@
-pub use self::{blend::*, select::*};
+pub use self::{blend::*, multi_select::*, select::*};
 
 mod blend;
+mod multi_select;
 mod select;
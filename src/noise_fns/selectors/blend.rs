@@ -5,26 +5,23 @@ use crate::{math::interpolate, noise_fns::NoiseFn};
 ///
 /// This noise function uses linear interpolation to perform the blending
 /// operation.
-pub struct Blend<'a, T, const DIM: usize> {
+#[derive(Clone, Debug)]
+pub struct Blend<Source1, Source2, Control> {
     /// Outputs one of the values to blend.
-    pub source1: &'a dyn NoiseFn<T, DIM>,
+    pub source1: Source1,
 
     /// Outputs one of the values to blend.
-    pub source2: &'a dyn NoiseFn<T, DIM>,
+    pub source2: Source2,
 
     /// Determines the weight of the blending operation. Negative values weight
     /// the blend towards the output value from the `source1` function. Positive
     /// values weight the blend towards the output value from the `source2`
     /// function.
-    pub control: &'a dyn NoiseFn<T, DIM>,
+    pub control: Control,
 }
 
-impl<'a, T, const DIM: usize> Blend<'a, T, DIM> {
-    pub fn new(
-        source1: &'a dyn NoiseFn<T, DIM>,
-        source2: &'a dyn NoiseFn<T, DIM>,
-        control: &'a dyn NoiseFn<T, DIM>,
-    ) -> Self {
+impl<Source1, Source2, Control> Blend<Source1, Source2, Control> {
+    pub fn new(source1: Source1, source2: Source2, control: Control) -> Self {
         Blend {
             source1,
             source2,
@@ -33,14 +30,22 @@ impl<'a, T, const DIM: usize> Blend<'a, T, DIM> {
     }
 }
 
-impl<'a, T, const DIM: usize> NoiseFn<T, DIM> for Blend<'a, T, DIM>
+impl<T, Source1, Source2, Control, const DIM: usize> NoiseFn<T, DIM>
+    for Blend<Source1, Source2, Control>
 where
     T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+    Control: NoiseFn<T, DIM>,
 {
     fn get(&self, point: [T; DIM]) -> f64 {
-        let lower = self.source1.get(point);
-        let upper = self.source2.get(point);
-        let control = self.control.get(point);
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        let lower = self.source1.get_with_footprint(point, footprint);
+        let upper = self.source2.get_with_footprint(point, footprint);
+        let control = self.control.get_with_footprint(point, footprint);
 
         interpolate::linear(lower, upper, control)
     }
@@ -0,0 +1,260 @@
+use crate::{
+    math::{self, s_curve::cubic::Cubic},
+    noise_fns::NoiseFn,
+};
+
+/// Noise function that outputs the value selected from any number of source
+/// functions, each chosen over its own range of output values from a control
+/// function.
+///
+/// Where the ranges of neighbouring sources meet, the falloff blends smoothly
+/// between them. If the control value lies outside every range, the source
+/// with the closest range is used.
+///
+/// The sources share a type. To select between noise functions of different
+/// types, box them as `Box<dyn NoiseFn<T, DIM>>`.
+#[derive(Clone, Debug)]
+pub struct MultiSelect<Control, Source> {
+    /// Determines which of the sources to select.
+    pub control: Control,
+
+    /// Sources and the ranges of control values over which each one is
+    /// selected, as `(lower, upper)` bounds.
+    pub sources: Vec<(Source, (f64, f64))>,
+
+    /// Edge falloff value. Default is 0.0.
+    pub falloff: f64,
+}
+
+impl<Control, Source> MultiSelect<Control, Source> {
+    pub fn new(control: Control) -> Self {
+        MultiSelect {
+            control,
+            sources: Vec::new(),
+            falloff: 0.0,
+        }
+    }
+
+    /// Adds a source that is selected when the control value lies between
+    /// `lower_bound` and `upper_bound`.
+    pub fn add_source(mut self, source: Source, lower_bound: f64, upper_bound: f64) -> Self {
+        self.sources.push((source, (lower_bound, upper_bound)));
+        self
+    }
+
+    pub fn set_falloff(self, falloff: f64) -> Self {
+        MultiSelect { falloff, ..self }
+    }
+}
+
+impl<T, Control, Source, const DIM: usize> NoiseFn<T, DIM> for MultiSelect<Control, Source>
+where
+    T: Copy,
+    Control: NoiseFn<T, DIM>,
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        let control_value = self.control.get_with_footprint(point, footprint);
+
+        select_weighted(
+            point,
+            footprint,
+            self.sources.iter().map(|(source, bounds)| {
+                (
+                    source,
+                    membership(control_value, *bounds, self.falloff),
+                    distance(control_value, *bounds),
+                )
+            }),
+        )
+    }
+}
+
+/// Noise function that selects from any number of source functions using a
+/// two-dimensional lookup on the output values of two control functions.
+///
+/// Each source covers a rectangle in control space, so the sources can be laid
+/// out like the biomes of a Whittaker diagram, with temperature and moisture
+/// as the controls. Where neighbouring rectangles meet, the falloff blends
+/// smoothly between them. If the control values lie outside every rectangle,
+/// the source with the closest rectangle is used.
+///
+/// As with [`MultiSelect`], the sources share a type.
+#[derive(Clone, Debug)]
+pub struct BiomeSelect<Control1, Control2, Source> {
+    /// Determines the first coordinate of the lookup.
+    pub control1: Control1,
+
+    /// Determines the second coordinate of the lookup.
+    pub control2: Control2,
+
+    /// Sources and the rectangles of control values over which each one is
+    /// selected, as `(lower, upper)` bounds for each of the two controls.
+    #[allow(clippy::type_complexity)]
+    pub sources: Vec<(Source, (f64, f64), (f64, f64))>,
+
+    /// Edge falloff value, applied along both controls. Default is 0.0.
+    pub falloff: f64,
+}
+
+impl<Control1, Control2, Source> BiomeSelect<Control1, Control2, Source> {
+    pub fn new(control1: Control1, control2: Control2) -> Self {
+        BiomeSelect {
+            control1,
+            control2,
+            sources: Vec::new(),
+            falloff: 0.0,
+        }
+    }
+
+    /// Adds a source that is selected when the value of `control1` lies within
+    /// `bounds1` and the value of `control2` lies within `bounds2`.
+    pub fn add_source(mut self, source: Source, bounds1: (f64, f64), bounds2: (f64, f64)) -> Self {
+        self.sources.push((source, bounds1, bounds2));
+        self
+    }
+
+    pub fn set_falloff(self, falloff: f64) -> Self {
+        BiomeSelect { falloff, ..self }
+    }
+}
+
+impl<T, Control1, Control2, Source, const DIM: usize> NoiseFn<T, DIM>
+    for BiomeSelect<Control1, Control2, Source>
+where
+    T: Copy,
+    Control1: NoiseFn<T, DIM>,
+    Control2: NoiseFn<T, DIM>,
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        let value1 = self.control1.get_with_footprint(point, footprint);
+        let value2 = self.control2.get_with_footprint(point, footprint);
+
+        select_weighted(
+            point,
+            footprint,
+            self.sources.iter().map(|(source, bounds1, bounds2)| {
+                let weight = membership(value1, *bounds1, self.falloff)
+                    * membership(value2, *bounds2, self.falloff);
+                let distance = math::hypot(distance(value1, *bounds1), distance(value2, *bounds2));
+
+                (source, weight, distance)
+            }),
+        )
+    }
+}
+
+/// Blends the sources by their weights, only evaluating those with a non-zero
+/// weight. If every weight is zero, evaluates the closest source instead.
+fn select_weighted<'a, T, Source, I, const DIM: usize>(
+    point: [T; DIM],
+    footprint: f64,
+    sources: I,
+) -> f64
+where
+    T: Copy,
+    Source: NoiseFn<T, DIM> + 'a,
+    I: Iterator<Item = (&'a Source, f64, f64)>,
+{
+    let mut value = 0.0;
+    let mut total_weight = 0.0;
+    let mut closest: Option<(&Source, f64)> = None;
+
+    for (source, weight, distance) in sources {
+        if weight > 0.0 {
+            value += source.get_with_footprint(point, footprint) * weight;
+            total_weight += weight;
+        } else if total_weight == 0.0 && distance < closest.map_or(f64::INFINITY, |(_, d)| d) {
+            closest = Some((source, distance));
+        }
+    }
+
+    if total_weight > 0.0 {
+        value / total_weight
+    } else {
+        closest.map_or(0.0, |(source, _)| {
+            source.get_with_footprint(point, footprint)
+        })
+    }
+}
+
+/// Computes how strongly a control value belongs to the range, from 0.0
+/// outside it to 1.0 inside, ramping smoothly across the falloff at each edge.
+fn membership(value: f64, (lower, upper): (f64, f64), falloff: f64) -> f64 {
+    // Keep the ramps at the two edges of the range from overlapping.
+    let falloff = falloff.min((upper - lower) * 0.5);
+
+    if falloff > 0.0 {
+        let rise = ((value - (lower - falloff)) / (2.0 * falloff)).clamp(0.0, 1.0);
+        let fall = (((upper + falloff) - value) / (2.0 * falloff)).clamp(0.0, 1.0);
+
+        rise.min(fall).map_cubic()
+    } else if value >= lower && value <= upper {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Computes how far a control value lies outside the range.
+fn distance(value: f64, (lower, upper): (f64, f64)) -> f64 {
+    (lower - value).max(value - upper).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn test_multi_select() {
+        let low = Constant::new(-1.0);
+        let high = Constant::new(1.0);
+
+        let select = |control: f64, falloff: f64| {
+            let control = Constant::new(control);
+            MultiSelect::new(&control)
+                .add_source(&low, -1.0, 0.0)
+                .add_source(&high, 0.0, 1.0)
+                .set_falloff(falloff)
+                .get([0.0, 0.0])
+        };
+
+        assert_eq!(-1.0, select(-0.5, 0.0));
+        assert_eq!(1.0, select(0.5, 0.0));
+        assert_eq!(1.0, select(5.0, 0.0));
+        assert_eq!(0.0, select(0.0, 0.25));
+        assert_eq!(-1.0, select(-0.25, 0.25));
+    }
+
+    #[test]
+    fn test_biome_select() {
+        let desert = Constant::new(1.0);
+        let tundra = Constant::new(2.0);
+        let forest = Constant::new(3.0);
+
+        let select = |temperature: f64, moisture: f64| {
+            let temperature = Constant::new(temperature);
+            let moisture = Constant::new(moisture);
+            BiomeSelect::new(&temperature, &moisture)
+                .add_source(&desert, (0.0, 1.0), (-1.0, 0.0))
+                .add_source(&tundra, (-1.0, 0.0), (-1.0, 1.0))
+                .add_source(&forest, (0.0, 1.0), (0.0, 1.0))
+                .get([0.0, 0.0])
+        };
+
+        assert_eq!(1.0, select(0.5, -0.5));
+        assert_eq!(2.0, select(-0.5, 0.5));
+        assert_eq!(3.0, select(0.5, 0.5));
+        assert_eq!(3.0, select(2.0, 2.0));
+    }
+}
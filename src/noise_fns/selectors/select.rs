@@ -5,18 +5,19 @@ use crate::{
 
 /// Noise function that outputs the value selected from one of two source
 /// functions chosen by the output value from a control function.
-pub struct Select<'a, T, const DIM: usize> {
+#[derive(Clone, Debug)]
+pub struct Select<Source1, Source2, Control> {
     /// Outputs a value.
-    pub source1: &'a dyn NoiseFn<T, DIM>,
+    pub source1: Source1,
 
     /// Outputs a value.
-    pub source2: &'a dyn NoiseFn<T, DIM>,
+    pub source2: Source2,
 
     /// Determines the value to select. If the output value from
     /// the control function is within a range of values know as the _selection
     /// range_, this noise function outputs the value from `source2`.
     /// Otherwise, this noise function outputs the value from `source1`.
-    pub control: &'a dyn NoiseFn<T, DIM>,
+    pub control: Control,
 
     /// Bounds of the selection range. Default is 0.0 to 1.0.
     pub bounds: (f64, f64),
@@ -25,12 +26,8 @@ pub struct Select<'a, T, const DIM: usize> {
     pub falloff: f64,
 }
 
-impl<'a, T, const DIM: usize> Select<'a, T, DIM> {
-    pub fn new(
-        source1: &'a dyn NoiseFn<T, DIM>,
-        source2: &'a dyn NoiseFn<T, DIM>,
-        control: &'a dyn NoiseFn<T, DIM>,
-    ) -> Self {
+impl<Source1, Source2, Control> Select<Source1, Source2, Control> {
+    pub fn new(source1: Source1, source2: Source2, control: Control) -> Self {
         Select {
             source1,
             source2,
@@ -52,40 +49,60 @@ impl<'a, T, const DIM: usize> Select<'a, T, DIM> {
     }
 }
 
-impl<'a, T, const DIM: usize> NoiseFn<T, DIM> for Select<'a, T, DIM>
+impl<T, Source1, Source2, Control, const DIM: usize> NoiseFn<T, DIM>
+    for Select<Source1, Source2, Control>
 where
     T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+    Control: NoiseFn<T, DIM>,
 {
     fn get(&self, point: [T; DIM]) -> f64 {
-        let control_value = self.control.get(point);
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        let control_value = self.control.get_with_footprint(point, footprint);
         let (lower, upper) = self.bounds;
 
         if self.falloff > 0.0 {
             match () {
-                _ if control_value < (lower - self.falloff) => self.source1.get(point),
+                _ if control_value < (lower - self.falloff) => {
+                    self.source1.get_with_footprint(point, footprint)
+                }
                 _ if control_value < (lower + self.falloff) => {
                     let lower_curve = lower - self.falloff;
                     let upper_curve = lower + self.falloff;
                     let alpha =
                         ((control_value - lower_curve) / (upper_curve - lower_curve)).map_cubic();
 
-                    interpolate::linear(self.source1.get(point), self.source2.get(point), alpha)
+                    interpolate::linear(
+                        self.source1.get_with_footprint(point, footprint),
+                        self.source2.get_with_footprint(point, footprint),
+                        alpha,
+                    )
+                }
+                _ if control_value < (upper - self.falloff) => {
+                    self.source2.get_with_footprint(point, footprint)
                 }
-                _ if control_value < (upper - self.falloff) => self.source2.get(point),
                 _ if control_value < (upper + self.falloff) => {
                     let lower_curve = upper - self.falloff;
                     let upper_curve = upper + self.falloff;
                     let alpha =
                         ((control_value - lower_curve) / (upper_curve - lower_curve)).map_cubic();
 
-                    interpolate::linear(self.source2.get(point), self.source1.get(point), alpha)
+                    interpolate::linear(
+                        self.source2.get_with_footprint(point, footprint),
+                        self.source1.get_with_footprint(point, footprint),
+                        alpha,
+                    )
                 }
-                _ => self.source1.get(point),
+                _ => self.source1.get_with_footprint(point, footprint),
             }
         } else if control_value < lower || control_value > upper {
-            self.source1.get(point)
+            self.source1.get_with_footprint(point, footprint)
         } else {
-            self.source2.get(point)
+            self.source2.get_with_footprint(point, footprint)
         }
     }
 }
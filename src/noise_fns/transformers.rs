@@ -1,7 +1,13 @@
-pub use self::{displace::*, rotate_point::*, scale_point::*, translate_point::*, turbulence::*};
+pub use self::{
+    affine::*, displace::*, map_point::*, rotate_point::*, scale_point::*, transform::*,
+    translate_point::*, turbulence::*,
+};
 
+mod affine;
 mod displace;
+mod map_point;
 mod rotate_point;
 mod scale_point;
+mod transform;
 mod translate_point;
 mod turbulence;
@@ -0,0 +1,204 @@
+use crate::math;
+use std::ops::Mul;
+
+/// An affine transformation of `DIM`-dimensional points, made of a linear
+/// part, stored as a `DIM`×`DIM` matrix, followed by a translation.
+///
+/// Together these form the `DIM`×(`DIM`+1) augmented matrix of the
+/// transformation, so `Affine<2>`, `Affine<3>` and `Affine<4>` are the 2×3,
+/// 3×4 and 4×5 matrices used by [`Transform`](super::Transform).
+///
+/// Transformations compose with [`then`](Self::then), or with `*` using the
+/// usual matrix convention that `a * b` applies `b` first. Rotation angles are
+/// given in degrees, like those of [`RotatePoint`](super::RotatePoint).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine<const DIM: usize> {
+    /// Linear part of the transformation, in row-major order.
+    pub matrix: [[f64; DIM]; DIM],
+
+    /// Translation applied after the linear part.
+    pub translation: [f64; DIM],
+}
+
+impl<const DIM: usize> Affine<DIM> {
+    pub fn new(matrix: [[f64; DIM]; DIM], translation: [f64; DIM]) -> Self {
+        Self {
+            matrix,
+            translation,
+        }
+    }
+
+    /// Returns the transformation that leaves every point unchanged.
+    pub fn identity() -> Self {
+        let mut matrix = [[0.0; DIM]; DIM];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+
+        Self::new(matrix, [0.0; DIM])
+    }
+
+    /// Returns a translation by the given offset.
+    pub fn from_translation(translation: [f64; DIM]) -> Self {
+        Self {
+            translation,
+            ..Self::identity()
+        }
+    }
+
+    /// Returns a scaling by the given factor along each axis.
+    pub fn from_scale(scale: [f64; DIM]) -> Self {
+        let mut matrix = [[0.0; DIM]; DIM];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[i] = scale[i];
+        }
+
+        Self::new(matrix, [0.0; DIM])
+    }
+
+    /// Returns a scaling by the same factor along every axis.
+    pub fn from_uniform_scale(scale: f64) -> Self {
+        Self::from_scale([scale; DIM])
+    }
+
+    /// Returns a rotation within the plane spanned by the two given axes,
+    /// turning `axis1` towards `axis2` by the given angle.
+    ///
+    /// This is the only kind of rotation that generalises to every dimension,
+    /// and any rotation in 4D can be built by composing them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either axis is not less than `DIM`, or if they are equal.
+    pub fn from_plane_rotation(axis1: usize, axis2: usize, angle: f64) -> Self {
+        assert!(axis1 < DIM && axis2 < DIM && axis1 != axis2);
+
+        let (sin, cos) = sin_cos(angle);
+        let mut affine = Self::identity();
+        affine.matrix[axis1][axis1] = cos;
+        affine.matrix[axis1][axis2] = -sin;
+        affine.matrix[axis2][axis1] = sin;
+        affine.matrix[axis2][axis2] = cos;
+
+        affine
+    }
+
+    /// Returns the transformation that applies this one, then `next`.
+    pub fn then(self, next: Self) -> Self {
+        let mut matrix = [[0.0; DIM]; DIM];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..DIM)
+                    .map(|k| next.matrix[i][k] * self.matrix[k][j])
+                    .sum();
+            }
+        }
+
+        Self::new(matrix, next.apply(self.translation))
+    }
+
+    /// Applies the transformation to a point.
+    pub fn apply(&self, point: [f64; DIM]) -> [f64; DIM] {
+        let mut result = self.translation;
+        for (value, row) in result.iter_mut().zip(&self.matrix) {
+            *value += row.iter().zip(&point).map(|(a, b)| a * b).sum::<f64>();
+        }
+
+        result
+    }
+
+    /// Returns the largest factor by which the transformation stretches any
+    /// of the axes, which scales the spacing between samples passed through
+    /// it.
+    pub(crate) fn max_stretch(&self) -> f64 {
+        (0..DIM)
+            .map(|j| self.matrix.iter().map(|row| row[j] * row[j]).sum::<f64>())
+            .fold(0.0, f64::max)
+            .sqrt()
+    }
+}
+
+impl Affine<2> {
+    /// Returns a rotation about the origin by the given angle, turning the
+    /// _x_ axis towards the _y_ axis.
+    pub fn from_angle(angle: f64) -> Self {
+        Self::from_plane_rotation(0, 1, angle)
+    }
+}
+
+impl Affine<3> {
+    /// Returns a rotation by the given angles about the _x_, _y_ and _z_ axes,
+    /// applied in that order.
+    pub fn from_euler_angles(x_angle: f64, y_angle: f64, z_angle: f64) -> Self {
+        Self::from_plane_rotation(1, 2, x_angle)
+            .then(Self::from_plane_rotation(2, 0, y_angle))
+            .then(Self::from_plane_rotation(0, 1, z_angle))
+    }
+
+    /// Returns a right-handed rotation by the given angle about the given
+    /// axis, which does not need to be normalized.
+    pub fn from_axis_angle(axis: [f64; 3], angle: f64) -> Self {
+        let length = math::dot3(axis, axis).sqrt();
+        let [x, y, z] = math::mul3(axis, 1.0 / length);
+        let (sin, cos) = sin_cos(angle);
+        let t = 1.0 - cos;
+
+        Self::new(
+            [
+                [t * x * x + cos, t * x * y - sin * z, t * x * z + sin * y],
+                [t * x * y + sin * z, t * y * y + cos, t * y * z - sin * x],
+                [t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos],
+            ],
+            [0.0; 3],
+        )
+    }
+
+    /// Returns the rotation described by a quaternion given as `[w, x, y, z]`,
+    /// which does not need to be normalized.
+    pub fn from_quaternion(quaternion: [f64; 4]) -> Self {
+        let length = math::dot4(quaternion, quaternion).sqrt();
+        let [w, x, y, z] = math::mul4(quaternion, 1.0 / length);
+
+        Self::new(
+            [
+                [
+                    1.0 - 2.0 * (y * y + z * z),
+                    2.0 * (x * y - w * z),
+                    2.0 * (x * z + w * y),
+                ],
+                [
+                    2.0 * (x * y + w * z),
+                    1.0 - 2.0 * (x * x + z * z),
+                    2.0 * (y * z - w * x),
+                ],
+                [
+                    2.0 * (x * z - w * y),
+                    2.0 * (y * z + w * x),
+                    1.0 - 2.0 * (x * x + y * y),
+                ],
+            ],
+            [0.0; 3],
+        )
+    }
+}
+
+impl<const DIM: usize> Default for Affine<DIM> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<const DIM: usize> Mul for Affine<DIM> {
+    type Output = Self;
+
+    /// Composes two transformations, applying `rhs` first.
+    fn mul(self, rhs: Self) -> Self {
+        rhs.then(self)
+    }
+}
+
+fn sin_cos(angle: f64) -> (f64, f64) {
+    let radians = angle.to_radians();
+
+    (math::sin(radians), math::cos(radians))
+}
@@ -51,15 +51,19 @@ where
     YDisplace: NoiseFn<f64, 2>,
 {
     fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 2], footprint: f64) -> f64 {
         // Get the output values from the displacement functions and add them to
         // the corresponding coordinate in the input value. Since this is a 2d
         // function, we only need the x_displace and y_displace functions.
-        let x = point[0] + self.x_displace.get(point);
-        let y = point[1] + self.y_displace.get(point);
+        let x = point[0] + self.x_displace.get_with_footprint(point, footprint);
+        let y = point[1] + self.y_displace.get_with_footprint(point, footprint);
 
         // get the output value using the offset input value instead of the
         // original input value.
-        self.source.get([x, y])
+        self.source.get_with_footprint([x, y], footprint)
     }
 }
 
@@ -72,17 +76,21 @@ where
     ZDisplace: NoiseFn<f64, 3>,
 {
     fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 3], footprint: f64) -> f64 {
         // Get the output values from the displacement functions and add them to
         // the corresponding coordinate in the input value. Since this is a 3d
         // function, we only need the x_displace, y_displace, and z_displace
         // functions. Also, panic if there is no z_displace function defined.
-        let x = point[0] + self.x_displace.get(point);
-        let y = point[1] + self.y_displace.get(point);
-        let z = point[2] + self.z_displace.get(point);
+        let x = point[0] + self.x_displace.get_with_footprint(point, footprint);
+        let y = point[1] + self.y_displace.get_with_footprint(point, footprint);
+        let z = point[2] + self.z_displace.get_with_footprint(point, footprint);
 
         // get the output value using the offset input value instead of the
         // original input value.
-        self.source.get([x, y, z])
+        self.source.get_with_footprint([x, y, z], footprint)
     }
 }
 
@@ -96,17 +104,21 @@ where
     UDisplace: NoiseFn<f64, 4>,
 {
     fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 4], footprint: f64) -> f64 {
         // Get the output values from the displacement functions and add them to
         // the corresponding coordinate in the input value. Since this is a 4d
         // function, we need all of the displace functions. Panic if there is no z-
         // or u-displace function defined.
-        let x = point[0] + self.x_displace.get(point);
-        let y = point[1] + self.y_displace.get(point);
-        let z = point[2] + self.z_displace.get(point);
-        let u = point[3] + self.u_displace.get(point);
+        let x = point[0] + self.x_displace.get_with_footprint(point, footprint);
+        let y = point[1] + self.y_displace.get_with_footprint(point, footprint);
+        let z = point[2] + self.z_displace.get_with_footprint(point, footprint);
+        let u = point[3] + self.u_displace.get_with_footprint(point, footprint);
 
         // get the output value using the offset input value instead of the
         // original input value.
-        self.source.get([x, y, z, u])
+        self.source.get_with_footprint([x, y, z, u], footprint)
     }
 }
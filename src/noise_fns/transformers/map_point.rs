@@ -0,0 +1,50 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that maps the input point through a closure before returning
+/// the output value from the source function.
+///
+/// The closure may change the type and the dimension of the point, so for
+/// example a 2-dimensional point can be wrapped onto a 3-dimensional surface
+/// before sampling a 3-dimensional source function.
+///
+/// # Example
+///
+/// ```rust
+/// use noise::{MapPoint, NoiseFn, Perlin};
+///
+/// // Sample 3D noise on the surface of a unit cylinder.
+/// let cylinder = MapPoint::new(Perlin::new(1), |[angle, height]: [f64; 2]| {
+///     [angle.cos(), angle.sin(), height]
+/// });
+///
+/// let val = cylinder.get([0.5, 2.0]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct MapPoint<Source, F> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Closure applied to the input point.
+    pub map: F,
+}
+
+impl<Source, F> MapPoint<Source, F> {
+    pub fn new(source: Source, map: F) -> Self {
+        Self { source, map }
+    }
+}
+
+impl<T, U, Source, F, const DIM: usize, const SOURCE_DIM: usize> NoiseFn<T, DIM>
+    for MapPoint<Source, F>
+where
+    Source: NoiseFn<U, SOURCE_DIM>,
+    F: Fn([T; DIM]) -> [U; SOURCE_DIM],
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [T; DIM], footprint: f64) -> f64 {
+        self.source.get_with_footprint((self.map)(point), footprint)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::noise_fns::NoiseFn;
+use crate::{math, noise_fns::NoiseFn};
 
 /// Noise function that rotates the input value around the origin before
 /// returning the output value from the source function.
@@ -82,18 +82,22 @@ where
     Source: NoiseFn<f64, 2>,
 {
     fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 2], footprint: f64) -> f64 {
         // In two dimensions, the plane is _xy_, and we rotate around the
         // z-axis.
         let x = point[0];
         let y = point[1];
         let theta = self.z_angle.to_radians();
 
-        let x2 = x * theta.cos() - y * theta.sin();
-        let y2 = x * theta.sin() + y * theta.cos();
+        let x2 = x * math::cos(theta) - y * math::sin(theta);
+        let y2 = x * math::sin(theta) + y * math::cos(theta);
 
         // get the output value using the offset input value instead of the
         // original input value.
-        self.source.get([x2, y2])
+        self.source.get_with_footprint([x2, y2], footprint)
     }
 }
 
@@ -102,14 +106,18 @@ where
     Source: NoiseFn<f64, 3>,
 {
     fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 3], footprint: f64) -> f64 {
         // In three dimensions, we could rotate around any of the x, y, or z
         // axes. Need a more complicated function to handle this case.
-        let x_cos = self.x_angle.to_radians().cos();
-        let y_cos = self.y_angle.to_radians().cos();
-        let z_cos = self.z_angle.to_radians().cos();
-        let x_sin = self.x_angle.to_radians().sin();
-        let y_sin = self.y_angle.to_radians().sin();
-        let z_sin = self.z_angle.to_radians().sin();
+        let x_cos = math::cos(self.x_angle.to_radians());
+        let y_cos = math::cos(self.y_angle.to_radians());
+        let z_cos = math::cos(self.z_angle.to_radians());
+        let x_sin = math::sin(self.x_angle.to_radians());
+        let y_sin = math::sin(self.y_angle.to_radians());
+        let z_sin = math::sin(self.z_angle.to_radians());
 
         let x1 = x_sin * y_sin * z_sin + y_cos * z_cos;
         let y1 = x_cos * z_sin;
@@ -127,7 +135,7 @@ where
 
         // get the output value using the offset input value instead of the
         // original input value.
-        self.source.get([x, y, z])
+        self.source.get_with_footprint([x, y, z], footprint)
     }
 }
 
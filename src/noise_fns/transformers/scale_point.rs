@@ -90,8 +90,17 @@ where
     Source: NoiseFn<f64, 2>,
 {
     fn get(&self, point: [f64; 2]) -> f64 {
-        self.source
-            .get([point[0] * self.x_scale, point[1] * self.y_scale])
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 2], footprint: f64) -> f64 {
+        // Scaling the input scales the spacing between samples with it.
+        let footprint = footprint * self.x_scale.abs().max(self.y_scale.abs());
+
+        self.source.get_with_footprint(
+            [point[0] * self.x_scale, point[1] * self.y_scale],
+            footprint,
+        )
     }
 }
 
@@ -100,11 +109,26 @@ where
     Source: NoiseFn<f64, 3>,
 {
     fn get(&self, point: [f64; 3]) -> f64 {
-        self.source.get([
-            point[0] * self.x_scale,
-            point[1] * self.y_scale,
-            point[2] * self.z_scale,
-        ])
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 3], footprint: f64) -> f64 {
+        // Scaling the input scales the spacing between samples with it.
+        let footprint = footprint
+            * self
+                .x_scale
+                .abs()
+                .max(self.y_scale.abs())
+                .max(self.z_scale.abs());
+
+        self.source.get_with_footprint(
+            [
+                point[0] * self.x_scale,
+                point[1] * self.y_scale,
+                point[2] * self.z_scale,
+            ],
+            footprint,
+        )
     }
 }
 
@@ -113,18 +137,37 @@ where
     Source: NoiseFn<f64, 4>,
 {
     fn get(&self, point: [f64; 4]) -> f64 {
-        self.source.get([
-            point[0] * self.x_scale,
-            point[1] * self.y_scale,
-            point[2] * self.z_scale,
-            point[3] * self.u_scale,
-        ])
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 4], footprint: f64) -> f64 {
+        // Scaling the input scales the spacing between samples with it.
+        let footprint = footprint
+            * self
+                .x_scale
+                .abs()
+                .max(self.y_scale.abs())
+                .max(self.z_scale.abs())
+                .max(self.u_scale.abs());
+
+        self.source.get_with_footprint(
+            [
+                point[0] * self.x_scale,
+                point[1] * self.y_scale,
+                point[2] * self.z_scale,
+                point[3] * self.u_scale,
+            ],
+            footprint,
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{super::super::Perlin, *};
+    use super::{
+        super::super::{FootprintProbe, Perlin},
+        *,
+    };
 
     #[test]
     fn test_pass_by_ref() {
@@ -161,4 +204,19 @@ mod tests {
 
         assert!(zero_count < 10 * 10 * 10 * 10);
     }
+
+    #[test]
+    fn test_footprint_scaled() {
+        let scaled = ScalePoint::new(FootprintProbe)
+            .set_x_scale(0.5)
+            .set_y_scale(-3.0)
+            .set_z_scale(2.0)
+            .set_u_scale(4.0);
+
+        // The footprint grows with the largest scale of the axes sampled.
+        assert_eq!(scaled.get_with_footprint([1.0, 1.0], 0.25), 0.75);
+        assert_eq!(scaled.get_with_footprint([1.0, 1.0, 1.0], 0.25), 0.75);
+        assert_eq!(scaled.get_with_footprint([1.0, 1.0, 1.0, 1.0], 0.25), 1.0);
+        assert_eq!(scaled.get([1.0, 1.0, 1.0, 1.0]), 0.0);
+    }
 }
@@ -0,0 +1,120 @@
+use crate::noise_fns::{Affine, NoiseFn};
+
+/// Noise function that applies an affine transformation to the input value
+/// before returning the output value from the source function.
+///
+/// The transformation is stored as a precomputed matrix, so unlike
+/// [`RotatePoint`](super::RotatePoint) no trigonometry is evaluated per
+/// point. Further transformations can be folded into the same matrix with
+/// [`transform`](Self::transform), so a chain of them costs no more than one.
+///
+/// # Example
+///
+/// ```rust
+/// use noise::{Affine, NoiseFn, Perlin, Transform};
+///
+/// let tilted = Transform::new(Perlin::new(1), Affine::from_euler_angles(30.0, 0.0, 45.0))
+///     .transform(Affine::from_uniform_scale(2.0))
+///     .transform(Affine::from_translation([0.5, 0.0, 0.0]));
+///
+/// let val = tilted.get([1.3, 2.7, 0.4]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Transform<Source, const DIM: usize> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Transformation applied to the input value.
+    pub affine: Affine<DIM>,
+}
+
+impl<Source, const DIM: usize> Transform<Source, DIM> {
+    pub fn new(source: Source, affine: Affine<DIM>) -> Self {
+        Self { source, affine }
+    }
+
+    pub fn set_affine(self, affine: Affine<DIM>) -> Self {
+        Self { affine, ..self }
+    }
+
+    /// Applies a further transformation after the current one, folding both
+    /// into a single matrix.
+    pub fn transform(self, affine: Affine<DIM>) -> Self {
+        Self {
+            affine: self.affine.then(affine),
+            ..self
+        }
+    }
+}
+
+impl<Source, const DIM: usize> NoiseFn<f64, DIM> for Transform<Source, DIM>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; DIM], footprint: f64) -> f64 {
+        self.source.get_with_footprint(
+            self.affine.apply(point),
+            footprint * self.affine.max_stretch(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{noise_fn, noise_fns::FootprintProbe};
+
+    fn assert_close(a: [f64; 3], b: [f64; 3]) {
+        for (a, b) in a.iter().zip(&b) {
+            assert!((a - b).abs() < 1e-12, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_rotation_constructors_agree() {
+        let point = [0.3, -1.2, 2.5];
+
+        let euler = Affine::from_euler_angles(0.0, 0.0, 90.0);
+        let axis_angle = Affine::from_axis_angle([0.0, 0.0, 2.0], 90.0);
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let quaternion = Affine::from_quaternion([half, 0.0, 0.0, half]);
+
+        assert_close([1.2, 0.3, 2.5], euler.apply(point));
+        assert_close(euler.apply(point), axis_angle.apply(point));
+        assert_close(euler.apply(point), quaternion.apply(point));
+    }
+
+    #[test]
+    fn test_transform_folds_chain() {
+        let source = noise_fn(|[x, y, z]: [f64; 3]| x + 10.0 * y + 100.0 * z);
+        let point = [0.7, 0.2, -0.4];
+
+        let rotate = Affine::from_euler_angles(10.0, 20.0, 30.0);
+        let scale = Affine::from_scale([2.0, 3.0, 4.0]);
+        let translate = Affine::from_translation([1.0, -2.0, 0.5]);
+
+        let chained = Transform::new(&source, rotate)
+            .transform(scale)
+            .transform(translate);
+        let expected = source.get(translate.apply(scale.apply(rotate.apply(point))));
+
+        assert!((chained.get(point) - expected).abs() < 1e-12);
+        assert_eq!(chained.affine, translate * scale * rotate);
+    }
+
+    #[test]
+    fn test_footprint_scaled() {
+        let rotate = Affine::from_euler_angles(10.0, 20.0, 30.0);
+        let scale = Affine::from_scale([2.0, 0.5, 3.0]);
+        let transform = Transform::new(FootprintProbe, scale).transform(rotate);
+
+        // Rotation keeps the spacing between samples, so only the largest
+        // scale stretches the footprint.
+        assert!((transform.get_with_footprint([0.7, 0.2, -0.4], 0.25) - 0.75).abs() < 1e-12);
+        assert_eq!(transform.get([0.7, 0.2, -0.4]), 0.0);
+    }
+}
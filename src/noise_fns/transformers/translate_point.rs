@@ -108,8 +108,14 @@ where
     Source: NoiseFn<f64, 2>,
 {
     fn get(&self, point: [f64; 2]) -> f64 {
-        self.source
-            .get([point[0] + self.x_translation, point[1] + self.y_translation])
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 2], footprint: f64) -> f64 {
+        self.source.get_with_footprint(
+            [point[0] + self.x_translation, point[1] + self.y_translation],
+            footprint,
+        )
     }
 }
 
@@ -118,11 +124,18 @@ where
     Source: NoiseFn<f64, 3>,
 {
     fn get(&self, point: [f64; 3]) -> f64 {
-        self.source.get([
-            point[0] + self.x_translation,
-            point[1] + self.y_translation,
-            point[2] + self.z_translation,
-        ])
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 3], footprint: f64) -> f64 {
+        self.source.get_with_footprint(
+            [
+                point[0] + self.x_translation,
+                point[1] + self.y_translation,
+                point[2] + self.z_translation,
+            ],
+            footprint,
+        )
     }
 }
 
@@ -131,11 +144,18 @@ where
     Source: NoiseFn<f64, 4>,
 {
     fn get(&self, point: [f64; 4]) -> f64 {
-        self.source.get([
-            point[0] + self.x_translation,
-            point[1] + self.y_translation,
-            point[2] + self.z_translation,
-            point[3] + self.u_translation,
-        ])
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 4], footprint: f64) -> f64 {
+        self.source.get_with_footprint(
+            [
+                point[0] + self.x_translation,
+                point[1] + self.y_translation,
+                point[2] + self.z_translation,
+                point[3] + self.u_translation,
+            ],
+            footprint,
+        )
     }
 }
@@ -1,4 +1,7 @@
-use crate::noise_fns::{Fbm, MultiFractal, NoiseFn, Seedable};
+use crate::{
+    mix_seed,
+    noise_fns::{Fbm, MultiFractal, NoiseFn, Seedable},
+};
 
 /// Noise function that randomly displaces the input value before returning the
 /// output value from the source function.
@@ -8,75 +11,174 @@ use crate::noise_fns::{Fbm, MultiFractal, NoiseFn, Seedable};
 /// retrieving the output value from the source function. To control the
 /// turbulence, an application can modify its frequency, its power, and its
 /// roughness.
+///
+/// The displacement along each axis is produced by a separate distortion
+/// function of type `F`. By default these are `Fbm` functions, but any seedable
+/// noise function can be used instead by calling `set_distortion_functions`.
 #[derive(Clone, Debug)]
-pub struct Turbulence<Source> {
+pub struct Turbulence<Source, F = Fbm> {
     /// Source function that outputs a value.
     pub source: Source,
 
     /// Frequency value for the Turbulence function.
     pub frequency: f64,
 
-    /// Controls the strength of the turbulence by affecting how much each
-    /// point is moved.
-    pub power: f64,
+    /// Controls the strength of the turbulence along the _x_ axis by
+    /// affecting how much each point is moved.
+    pub x_power: f64,
+
+    /// Controls the strength of the turbulence along the _y_ axis by
+    /// affecting how much each point is moved.
+    pub y_power: f64,
+
+    /// Controls the strength of the turbulence along the _z_ axis by
+    /// affecting how much each point is moved.
+    pub z_power: f64,
+
+    /// Controls the strength of the turbulence along the _u_ axis by
+    /// affecting how much each point is moved.
+    pub u_power: f64,
 
     /// Affects the roughness of the turbulence. Higher values are rougher.
+    ///
+    /// This is the octave count of the distortion functions, and only has an
+    /// effect when they implement `MultiFractal`.
     pub roughness: usize,
 
-    seed: u32,
-    x_distort_function: Fbm,
-    y_distort_function: Fbm,
-    z_distort_function: Fbm,
-    u_distort_function: Fbm,
+    seed: u64,
+    x_distort_function: F,
+    y_distort_function: F,
+    z_distort_function: F,
+    u_distort_function: F,
 }
 
-impl<Source> Turbulence<Source> {
-    pub const DEFAULT_SEED: u32 = 0;
-    pub const DEFAULT_FREQUENCY: f64 = 1.0;
-    pub const DEFAULT_POWER: f64 = 1.0;
-    pub const DEFAULT_ROUGHNESS: usize = 3;
-
+impl<Source> Turbulence<Source, Fbm> {
     pub fn new(source: Source) -> Self {
         Self {
             source,
             seed: Self::DEFAULT_SEED,
             frequency: Self::DEFAULT_FREQUENCY,
-            power: Self::DEFAULT_POWER,
+            x_power: Self::DEFAULT_POWER,
+            y_power: Self::DEFAULT_POWER,
+            z_power: Self::DEFAULT_POWER,
+            u_power: Self::DEFAULT_POWER,
             roughness: Self::DEFAULT_ROUGHNESS,
             x_distort_function: Fbm::new()
-                .set_seed(Self::DEFAULT_SEED)
-                .set_octaves(Self::DEFAULT_ROUGHNESS)
-                .set_frequency(Self::DEFAULT_FREQUENCY),
+                .set_seed(mix_seed(Self::DEFAULT_SEED, 0))
+                .set_octaves(Self::DEFAULT_ROUGHNESS),
             y_distort_function: Fbm::new()
-                .set_seed(Self::DEFAULT_SEED + 1)
-                .set_octaves(Self::DEFAULT_ROUGHNESS)
-                .set_frequency(Self::DEFAULT_FREQUENCY),
+                .set_seed(mix_seed(Self::DEFAULT_SEED, 1))
+                .set_octaves(Self::DEFAULT_ROUGHNESS),
             z_distort_function: Fbm::new()
-                .set_seed(Self::DEFAULT_SEED + 2)
-                .set_octaves(Self::DEFAULT_ROUGHNESS)
-                .set_frequency(Self::DEFAULT_FREQUENCY),
+                .set_seed(mix_seed(Self::DEFAULT_SEED, 2))
+                .set_octaves(Self::DEFAULT_ROUGHNESS),
             u_distort_function: Fbm::new()
-                .set_seed(Self::DEFAULT_SEED + 3)
-                .set_octaves(Self::DEFAULT_ROUGHNESS)
-                .set_frequency(Self::DEFAULT_FREQUENCY),
+                .set_seed(mix_seed(Self::DEFAULT_SEED, 3))
+                .set_octaves(Self::DEFAULT_ROUGHNESS),
         }
     }
+}
 
+impl<Source, F> Turbulence<Source, F> {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_POWER: f64 = 1.0;
+    pub const DEFAULT_ROUGHNESS: usize = 3;
+
+    /// Sets the frequency at which the distortion functions are sampled.
     pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    /// Sets the strength of the turbulence along all of the axes.
+    pub fn set_power(self, power: f64) -> Self {
         Self {
-            frequency,
-            x_distort_function: self.x_distort_function.set_frequency(frequency),
-            y_distort_function: self.y_distort_function.set_frequency(frequency),
-            z_distort_function: self.z_distort_function.set_frequency(frequency),
-            u_distort_function: self.u_distort_function.set_frequency(frequency),
+            x_power: power,
+            y_power: power,
+            z_power: power,
+            u_power: power,
             ..self
         }
     }
 
-    pub fn set_power(self, power: f64) -> Self {
-        Self { power, ..self }
+    /// Sets the strength of the turbulence along the _x_ axis.
+    pub fn set_x_power(self, x_power: f64) -> Self {
+        Self { x_power, ..self }
     }
 
+    /// Sets the strength of the turbulence along the _y_ axis.
+    pub fn set_y_power(self, y_power: f64) -> Self {
+        Self { y_power, ..self }
+    }
+
+    /// Sets the strength of the turbulence along the _z_ axis.
+    pub fn set_z_power(self, z_power: f64) -> Self {
+        Self { z_power, ..self }
+    }
+
+    /// Sets the strength of the turbulence along the _u_ axis.
+    pub fn set_u_power(self, u_power: f64) -> Self {
+        Self { u_power, ..self }
+    }
+
+    /// Sets the individual strengths of the turbulence along each axis.
+    pub fn set_all_powers(self, x_power: f64, y_power: f64, z_power: f64, u_power: f64) -> Self {
+        Self {
+            x_power,
+            y_power,
+            z_power,
+            u_power,
+            ..self
+        }
+    }
+
+    /// Replaces the functions used to displace each coordinate of the input
+    /// value.
+    ///
+    /// The new functions are reseeded with seeds derived from the seed of this
+    /// function, so each axis is displaced by a different pattern.
+    pub fn set_distortion_functions<G>(
+        self,
+        x_distort_function: G,
+        y_distort_function: G,
+        z_distort_function: G,
+        u_distort_function: G,
+    ) -> Turbulence<Source, G>
+    where
+        G: Seedable,
+    {
+        Turbulence {
+            source: self.source,
+            frequency: self.frequency,
+            x_power: self.x_power,
+            y_power: self.y_power,
+            z_power: self.z_power,
+            u_power: self.u_power,
+            roughness: self.roughness,
+            seed: self.seed,
+            x_distort_function: x_distort_function.set_seed(mix_seed(self.seed, 0)),
+            y_distort_function: y_distort_function.set_seed(mix_seed(self.seed, 1)),
+            z_distort_function: z_distort_function.set_seed(mix_seed(self.seed, 2)),
+            u_distort_function: u_distort_function.set_seed(mix_seed(self.seed, 3)),
+        }
+    }
+
+    /// Returns the functions used to displace the _x_, _y_, _z_ and _u_
+    /// coordinates of the input value, in that order.
+    pub fn distortion_functions(&self) -> [&F; 4] {
+        [
+            &self.x_distort_function,
+            &self.y_distort_function,
+            &self.z_distort_function,
+            &self.u_distort_function,
+        ]
+    }
+}
+
+impl<Source, F> Turbulence<Source, F>
+where
+    F: MultiFractal,
+{
     pub fn set_roughness(self, roughness: usize) -> Self {
         Self {
             roughness,
@@ -87,108 +189,239 @@ impl<Source> Turbulence<Source> {
             ..self
         }
     }
+
+    /// Sets the lacunarity of the distortion functions.
+    pub fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self {
+            x_distort_function: self.x_distort_function.set_lacunarity(lacunarity),
+            y_distort_function: self.y_distort_function.set_lacunarity(lacunarity),
+            z_distort_function: self.z_distort_function.set_lacunarity(lacunarity),
+            u_distort_function: self.u_distort_function.set_lacunarity(lacunarity),
+            ..self
+        }
+    }
+
+    /// Sets the persistence of the distortion functions.
+    pub fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            x_distort_function: self.x_distort_function.set_persistence(persistence),
+            y_distort_function: self.y_distort_function.set_persistence(persistence),
+            z_distort_function: self.z_distort_function.set_persistence(persistence),
+            u_distort_function: self.u_distort_function.set_persistence(persistence),
+            ..self
+        }
+    }
 }
 
-impl<Source> Seedable for Turbulence<Source> {
-    fn set_seed(self, seed: u32) -> Self {
+impl<Source, F> Seedable for Turbulence<Source, F>
+where
+    F: Seedable,
+{
+    fn set_seed(self, seed: u64) -> Self {
         Self {
             seed,
-            x_distort_function: self.x_distort_function.set_seed(seed),
-            y_distort_function: self.y_distort_function.set_seed(seed + 1),
-            z_distort_function: self.z_distort_function.set_seed(seed + 2),
-            u_distort_function: self.u_distort_function.set_seed(seed + 3),
+            x_distort_function: self.x_distort_function.set_seed(mix_seed(seed, 0)),
+            y_distort_function: self.y_distort_function.set_seed(mix_seed(seed, 1)),
+            z_distort_function: self.z_distort_function.set_seed(mix_seed(seed, 2)),
+            u_distort_function: self.u_distort_function.set_seed(mix_seed(seed, 3)),
             ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed(&self) -> u64 {
         self.seed
     }
 }
 
-impl<Source> NoiseFn<f64, 2> for Turbulence<Source>
+impl<Source, F> NoiseFn<f64, 2> for Turbulence<Source, F>
 where
     Source: NoiseFn<f64, 2>,
+    F: NoiseFn<f64, 2>,
 {
     fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 2], footprint: f64) -> f64 {
         // First, create offsets based on the input values to keep the sampled
         // points from being near a integer boundary. This is a result of
         // using perlin noise, which returns zero at integer boundaries.
-        let x0 = point[0] + 12414.0 / 65536.0;
-        let y0 = point[1] + 65124.0 / 65536.0;
+        let x0 = (point[0] + 12414.0 / 65536.0) * self.frequency;
+        let y0 = (point[1] + 65124.0 / 65536.0) * self.frequency;
 
-        let x1 = point[0] + 26519.0 / 65536.0;
-        let y1 = point[1] + 18128.0 / 65536.0;
+        let x1 = (point[0] + 26519.0 / 65536.0) * self.frequency;
+        let y1 = (point[1] + 18128.0 / 65536.0) * self.frequency;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1]) * self.power);
+        let x_distort = point[0]
+            + (self
+                .x_distort_function
+                .get_with_footprint([x0, y0], footprint * self.frequency)
+                * self.x_power);
+        let y_distort = point[1]
+            + (self
+                .y_distort_function
+                .get_with_footprint([x1, y1], footprint * self.frequency)
+                * self.y_power);
 
-        self.source.get([x_distort, y_distort])
+        self.source
+            .get_with_footprint([x_distort, y_distort], footprint)
     }
 }
 
-impl<Source> NoiseFn<f64, 3> for Turbulence<Source>
+impl<Source, F> NoiseFn<f64, 3> for Turbulence<Source, F>
 where
     Source: NoiseFn<f64, 3>,
+    F: NoiseFn<f64, 3>,
 {
     fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 3], footprint: f64) -> f64 {
         // First, create offsets based on the input values to keep the sampled
         // points from being near a integer boundary. This is a result of
         // using perlin noise, which returns zero at integer boundaries.
-        let x0 = point[0] + 12414.0 / 65536.0;
-        let y0 = point[1] + 65124.0 / 65536.0;
-        let z0 = point[2] + 31337.0 / 65536.0;
+        let x0 = (point[0] + 12414.0 / 65536.0) * self.frequency;
+        let y0 = (point[1] + 65124.0 / 65536.0) * self.frequency;
+        let z0 = (point[2] + 31337.0 / 65536.0) * self.frequency;
 
-        let x1 = point[0] + 26519.0 / 65536.0;
-        let y1 = point[1] + 18128.0 / 65536.0;
-        let z1 = point[2] + 60943.0 / 65536.0;
+        let x1 = (point[0] + 26519.0 / 65536.0) * self.frequency;
+        let y1 = (point[1] + 18128.0 / 65536.0) * self.frequency;
+        let z1 = (point[2] + 60943.0 / 65536.0) * self.frequency;
 
-        let x2 = point[0] + 53820.0 / 65536.0;
-        let y2 = point[1] + 11213.0 / 65536.0;
-        let z2 = point[2] + 44845.0 / 65536.0;
+        let x2 = (point[0] + 53820.0 / 65536.0) * self.frequency;
+        let y2 = (point[1] + 11213.0 / 65536.0) * self.frequency;
+        let z2 = (point[2] + 44845.0 / 65536.0) * self.frequency;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0, z0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1, z1]) * self.power);
-        let z_distort = point[2] + (self.z_distort_function.get([x2, y2, z2]) * self.power);
+        let x_distort = point[0]
+            + (self
+                .x_distort_function
+                .get_with_footprint([x0, y0, z0], footprint * self.frequency)
+                * self.x_power);
+        let y_distort = point[1]
+            + (self
+                .y_distort_function
+                .get_with_footprint([x1, y1, z1], footprint * self.frequency)
+                * self.y_power);
+        let z_distort = point[2]
+            + (self
+                .z_distort_function
+                .get_with_footprint([x2, y2, z2], footprint * self.frequency)
+                * self.z_power);
 
-        self.source.get([x_distort, y_distort, z_distort])
+        self.source
+            .get_with_footprint([x_distort, y_distort, z_distort], footprint)
     }
 }
 
-impl<Source> NoiseFn<f64, 4> for Turbulence<Source>
+impl<Source, F> NoiseFn<f64, 4> for Turbulence<Source, F>
 where
     Source: NoiseFn<f64, 4>,
+    F: NoiseFn<f64, 4>,
 {
     fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_with_footprint(point, 0.0)
+    }
+
+    fn get_with_footprint(&self, point: [f64; 4], footprint: f64) -> f64 {
         // First, create offsets based on the input values to keep the sampled
         // points from being near a integer boundary. This is a result of
         // using perlin noise, which returns zero at integer boundaries.
-        let x0 = point[0] + 12414.0 / 65536.0;
-        let y0 = point[1] + 65124.0 / 65536.0;
-        let z0 = point[2] + 31337.0 / 65536.0;
-        let u0 = point[3] + 57948.0 / 65536.0;
-
-        let x1 = point[0] + 26519.0 / 65536.0;
-        let y1 = point[1] + 18128.0 / 65536.0;
-        let z1 = point[2] + 60943.0 / 65536.0;
-        let u1 = point[3] + 48513.0 / 65536.0;
-
-        let x2 = point[0] + 53820.0 / 65536.0;
-        let y2 = point[1] + 11213.0 / 65536.0;
-        let z2 = point[2] + 44845.0 / 65536.0;
-        let u2 = point[3] + 39357.0 / 65536.0;
-
-        let x3 = point[0] + 18128.0 / 65536.0;
-        let y3 = point[1] + 44845.0 / 65536.0;
-        let z3 = point[2] + 12414.0 / 65536.0;
-        let u3 = point[3] + 60943.0 / 65536.0;
-
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0, z0, u0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1, z1, u1]) * self.power);
-        let z_distort = point[2] + (self.z_distort_function.get([x2, y2, z2, u2]) * self.power);
-        let u_distort = point[3] + (self.u_distort_function.get([x3, y3, z3, u3]) * self.power);
+        let x0 = (point[0] + 12414.0 / 65536.0) * self.frequency;
+        let y0 = (point[1] + 65124.0 / 65536.0) * self.frequency;
+        let z0 = (point[2] + 31337.0 / 65536.0) * self.frequency;
+        let u0 = (point[3] + 57948.0 / 65536.0) * self.frequency;
+
+        let x1 = (point[0] + 26519.0 / 65536.0) * self.frequency;
+        let y1 = (point[1] + 18128.0 / 65536.0) * self.frequency;
+        let z1 = (point[2] + 60943.0 / 65536.0) * self.frequency;
+        let u1 = (point[3] + 48513.0 / 65536.0) * self.frequency;
+
+        let x2 = (point[0] + 53820.0 / 65536.0) * self.frequency;
+        let y2 = (point[1] + 11213.0 / 65536.0) * self.frequency;
+        let z2 = (point[2] + 44845.0 / 65536.0) * self.frequency;
+        let u2 = (point[3] + 39357.0 / 65536.0) * self.frequency;
+
+        let x3 = (point[0] + 18128.0 / 65536.0) * self.frequency;
+        let y3 = (point[1] + 44845.0 / 65536.0) * self.frequency;
+        let z3 = (point[2] + 12414.0 / 65536.0) * self.frequency;
+        let u3 = (point[3] + 60943.0 / 65536.0) * self.frequency;
+
+        let x_distort = point[0]
+            + (self
+                .x_distort_function
+                .get_with_footprint([x0, y0, z0, u0], footprint * self.frequency)
+                * self.x_power);
+        let y_distort = point[1]
+            + (self
+                .y_distort_function
+                .get_with_footprint([x1, y1, z1, u1], footprint * self.frequency)
+                * self.y_power);
+        let z_distort = point[2]
+            + (self
+                .z_distort_function
+                .get_with_footprint([x2, y2, z2, u2], footprint * self.frequency)
+                * self.z_power);
+        let u_distort = point[3]
+            + (self
+                .u_distort_function
+                .get_with_footprint([x3, y3, z3, u3], footprint * self.frequency)
+                * self.u_power);
 
         self.source
-            .get([x_distort, y_distort, z_distort, u_distort])
+            .get_with_footprint([x_distort, y_distort, z_distort, u_distort], footprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        noise_fn,
+        noise_fns::{Cylinders, FootprintProbe, Worley},
+    };
+
+    #[test]
+    fn test_custom_distortion_functions() {
+        let source = Cylinders::new();
+        let turbulence = Turbulence::new(&source)
+            .set_distortion_functions(
+                Worley::default(),
+                Worley::default(),
+                Worley::default(),
+                Worley::default(),
+            )
+            .set_seed(7);
+
+        // Each axis should have been reseeded from the turbulence seed.
+        let seeds: Vec<u64> = turbulence
+            .distortion_functions()
+            .iter()
+            .map(|f| f.seed())
+            .collect();
+        assert_eq!(seeds, (0..4).map(|i| mix_seed(7, i)).collect::<Vec<_>>());
+
+        let _ = turbulence.get([0.5, 1.5, 2.5]);
+    }
+
+    #[test]
+    fn test_footprint_scaled() {
+        // The distortion functions are sampled at the turbulence frequency,
+        // so each displaces the point by its footprint times that frequency.
+        let displaced = Turbulence::new(noise_fn(|[x, y]: [f64; 2]| x + 10.0 * y))
+            .set_distortion_functions(
+                FootprintProbe,
+                FootprintProbe,
+                FootprintProbe,
+                FootprintProbe,
+            )
+            .set_frequency(2.0)
+            .set_power(0.5);
+        assert_eq!(displaced.get_with_footprint([1.0, 1.0], 0.25), 13.75);
+        assert_eq!(displaced.get([1.0, 1.0]), 11.0);
+
+        // The source is sampled at the original spacing.
+        let source = Turbulence::new(FootprintProbe).set_frequency(2.0);
+        assert_eq!(source.get_with_footprint([1.0, 1.0, 1.0], 0.25), 0.25);
     }
 }
@@ -7,12 +7,30 @@ use rand_xorshift::XorShiftRng;
 use std::fmt;
 
 const TABLE_SIZE: usize = 256;
+const LARGE_TABLE_SIZE: usize = 65_536;
 
+/// Trait for hashing the integer lattice coordinates used by noise functions.
+///
+/// Lattice-based noise functions such as `Perlin`, `Value` and `Worley` use a
+/// hasher to pick the pseudo-random gradient or value at each lattice point, so
+/// the hasher determines both the seed dependence and the period of the noise.
+///
+/// Implementations must be deterministic, and must return values in the range
+/// `0..=255`.
 pub trait NoiseHasher: Send + Sync {
+    /// Creates a new hasher from a seed value.
+    fn from_seed(seed: u64) -> Self
+    where
+        Self: Sized;
+
+    /// Hashes a set of lattice coordinates to a value in the range `0..=255`.
     fn hash(&self, to_hash: &[isize]) -> usize;
 }
 
-/// A seed table, required by all noise functions.
+/// A seed table, and the default hasher of all noise functions.
+///
+/// The table holds 256 entries, so noise functions using it repeat every 256
+/// units along each axis.
 ///
 /// Table creation is expensive, so in most circumstances you'll only want to
 /// create one of these per generator.
@@ -42,18 +60,20 @@ impl Distribution<PermutationTable> for Standard {
 }
 
 impl PermutationTable {
-    /// Deterministically generates a new permutation table based on a `u32` seed value.
+    /// Deterministically generates a new permutation table based on a `u64` seed value.
     ///
     /// Internally this uses a `XorShiftRng`, but we don't really need to worry
     /// about cryptographic security when working with procedural noise.
-    pub fn new(seed: u32) -> Self {
+    pub fn new(seed: u64) -> Self {
+        // The upper half of the seed is folded into only one of the state words, so seeds
+        // that fit in 32 bits produce the same tables as they did before seeds were widened.
+        let low = seed as u32;
+        let high = (seed >> 32) as u32;
+
         let mut real = [0; 16];
         real[0] = 1;
-        for i in 1..4 {
-            real[i * 4] = seed as u8;
-            real[(i * 4) + 1] = (seed >> 8) as u8;
-            real[(i * 4) + 2] = (seed >> 16) as u8;
-            real[(i * 4) + 3] = (seed >> 24) as u8;
+        for (i, word) in [low, low ^ high, low].iter().enumerate() {
+            real[(i + 1) * 4..(i + 2) * 4].copy_from_slice(&word.to_le_bytes());
         }
         let mut rng: XorShiftRng = SeedableRng::from_seed(real);
         rng.gen()
@@ -61,6 +81,10 @@ impl PermutationTable {
 }
 
 impl NoiseHasher for PermutationTable {
+    fn from_seed(seed: u64) -> Self {
+        Self::new(seed)
+    }
+
     fn hash(&self, to_hash: &[isize]) -> usize {
         let index = to_hash
             .iter()
@@ -77,8 +101,98 @@ impl fmt::Debug for PermutationTable {
     }
 }
 
+/// A seed table with 65536 entries.
+///
+/// Noise functions using this table repeat every 65536 units along each axis
+/// instead of every 256, at the cost of a larger and slower to create table.
+#[derive(Clone)]
+pub struct LargePermutationTable {
+    values: Box<[u16]>,
+}
+
+impl LargePermutationTable {
+    /// Deterministically generates a new permutation table based on a `u64` seed value.
+    pub fn new(seed: u64) -> Self {
+        let mut rng = XorShiftRng::seed_from_u64(seed);
+
+        let mut values: Vec<u16> = (0..LARGE_TABLE_SIZE).map(|x| x as u16).collect();
+        values.shuffle(&mut rng);
+
+        Self {
+            values: values.into_boxed_slice(),
+        }
+    }
+}
+
+impl NoiseHasher for LargePermutationTable {
+    fn from_seed(seed: u64) -> Self {
+        Self::new(seed)
+    }
+
+    fn hash(&self, to_hash: &[isize]) -> usize {
+        let index = to_hash
+            .iter()
+            .map(|&a| (a & 0xffff) as usize)
+            .reduce(|a, b| self.values[a] as usize ^ b)
+            .unwrap();
+        (self.values[index] & 0xff) as usize
+    }
+}
+
+impl fmt::Debug for LargePermutationTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LargePermutationTable {{ .. }}")
+    }
+}
+
+/// A stateless hasher based on the PCG family of permutation functions.
+///
+/// Instead of looking the coordinates up in a table, this hasher scrambles
+/// them with integer arithmetic, so noise functions using it have no period.
+/// It is also cheap to create, which makes it a good fit for generators that
+/// are reseeded often.
+#[derive(Clone, Copy, Debug)]
+pub struct PcgHasher {
+    seed: u64,
+}
+
+impl PcgHasher {
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+    const INCREMENT: u64 = 1_442_695_040_888_963_407;
+
+    /// Creates a new hasher from a `u64` seed value.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// One step of the PCG RXS-M-XS 64-bit permutation.
+    #[inline]
+    fn permute(value: u64) -> u64 {
+        let state = value
+            .wrapping_mul(Self::MULTIPLIER)
+            .wrapping_add(Self::INCREMENT);
+        let word =
+            ((state >> ((state >> 59) + 5)) ^ state).wrapping_mul(12_605_985_483_714_912_027);
+        (word >> 43) ^ word
+    }
+}
+
+impl NoiseHasher for PcgHasher {
+    fn from_seed(seed: u64) -> Self {
+        Self::new(seed)
+    }
+
+    fn hash(&self, to_hash: &[isize]) -> usize {
+        let hash = to_hash.iter().fold(Self::permute(self.seed), |hash, &a| {
+            Self::permute(hash ^ a as u64)
+        });
+        (hash >> 56) as usize
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::{NoiseFn, Perlin, Seedable};
     use rand::random;
 
@@ -93,4 +207,24 @@ mod tests {
         let perlin = Perlin::default();
         let _ = perlin.get([-1.0, 2.0, 3.0]);
     }
+
+    #[test]
+    fn test_large_table_period() {
+        let table = LargePermutationTable::new(0);
+        let small = PermutationTable::new(0);
+
+        assert_eq!(small.hash(&[1, 2]), small.hash(&[257, 2]));
+        assert_eq!(table.hash(&[1, 2]), table.hash(&[65_537, 2]));
+        assert!((0..16).any(|x| table.hash(&[x, 2]) != table.hash(&[x + 256, 2])));
+    }
+
+    #[test]
+    fn test_pcg_hasher_range() {
+        let hasher = PcgHasher::new(random());
+
+        for x in -64..64 {
+            assert!(hasher.hash(&[x, x * 3, -x]) <= 255);
+        }
+        assert!((0..16).any(|x| hasher.hash(&[x]) != hasher.hash(&[x + 256])));
+    }
 }
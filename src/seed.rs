@@ -0,0 +1,104 @@
+//! Helpers for deriving the seeds of sub-generators from a single seed.
+//!
+//! Adding small offsets to a seed (`seed + 1`, `seed + 2`, ...) overflows for
+//! large seeds and makes generators with nearby seeds share most of their
+//! sub-generators. These functions instead scramble the seed with the
+//! SplitMix64 finalizer, so every derived seed is statistically independent of
+//! its neighbours. The output of these functions is part of the crate's
+//! stability guarantee and will not change between versions.
+
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Derives the seed of the `index`th sub-generator of a generator seeded with
+/// `seed`.
+///
+/// This returns the `index`th output of a SplitMix64 generator seeded with
+/// `seed`, so consecutive indices and consecutive seeds both produce unrelated
+/// values.
+#[inline]
+pub fn mix_seed(seed: u64, index: u64) -> u64 {
+    finalize(seed.wrapping_add(index.wrapping_add(1).wrapping_mul(GOLDEN_GAMMA)))
+}
+
+/// Derives a stable sub-seed from a seed and a key, such as the name of the
+/// part of a noise graph the seed is used for.
+///
+/// ```rust
+/// use noise::derive_seed;
+///
+/// let world_seed = 0x5eed;
+/// let mountains = derive_seed(world_seed, "mountains");
+/// let rivers = derive_seed(world_seed, "rivers");
+///
+/// assert_ne!(mountains, rivers);
+/// assert_eq!(mountains, derive_seed(world_seed, "mountains"));
+/// ```
+pub fn derive_seed<K>(seed: u64, key: K) -> u64
+where
+    K: AsRef<[u8]>,
+{
+    mix_seed(seed, fnv1a(key.as_ref()))
+}
+
+/// Derives a stable sub-seed from a seed and a path of keys. This is
+/// equivalent to calling `derive_seed` once for each key in the path.
+///
+/// ```rust
+/// use noise::{derive_seed, derive_seed_path};
+///
+/// let world_seed = 0x5eed;
+///
+/// assert_eq!(
+///     derive_seed_path(world_seed, &["terrain", "mountains"]),
+///     derive_seed(derive_seed(world_seed, "terrain"), "mountains"),
+/// );
+/// ```
+pub fn derive_seed_path<I>(seed: u64, path: I) -> u64
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    path.into_iter().fold(seed, derive_seed)
+}
+
+/// The finalizer of the SplitMix64 generator.
+#[inline]
+fn finalize(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// 64-bit FNV-1a hash, which unlike the hashers in `std` is guaranteed to be
+/// stable across platforms and versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mix_seed_reference_values() {
+        // First outputs of SplitMix64 seeded with 0.
+        assert_eq!(mix_seed(0, 0), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(mix_seed(0, 1), 0x6e78_9e6a_a1b9_65f4);
+    }
+
+    #[test]
+    fn test_large_seeds_do_not_overflow() {
+        let seeds: Vec<u64> = (0..4).map(|i| mix_seed(u64::MAX, i)).collect();
+
+        for (i, a) in seeds.iter().enumerate() {
+            for b in &seeds[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+}
@@ -1,10 +1,18 @@
 #[cfg(feature = "image")]
 pub use self::image_renderer::*;
-pub use self::{color_gradient::*, noise_image::*, noise_map::*, noise_map_builder::*};
+pub use self::{
+    analysis::*, color_gradient::*, erosion::*, noise_image::*, noise_map::*, noise_map_builder::*,
+    noise_map_sampler::*, point_sampling::*, terrain_renderer::*,
+};
 
+mod analysis;
 mod color_gradient;
+mod erosion;
 #[cfg(feature = "image")]
 mod image_renderer;
 mod noise_image;
 mod noise_map;
 mod noise_map_builder;
+mod noise_map_sampler;
+mod point_sampling;
+mod terrain_renderer;
@@ -0,0 +1,504 @@
+use crate::{math, noise_fns::NoiseFn, utils::noise_map::NoiseMap};
+use std::f64::consts::PI;
+
+/// Samples a noise function over a rectangular region and computes statistics
+/// about its output.
+///
+/// The noise function is sampled on a regular grid spanning the x and y bounds,
+/// with any remaining coordinates set to zero. The region should cover many
+/// features of the noise function for the statistics to be representative.
+pub struct NoiseAnalyzer<'a, const DIM: usize> {
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    size: (usize, usize),
+    histogram_bins: usize,
+    source_module: &'a dyn NoiseFn<f64, DIM>,
+}
+
+impl<'a, const DIM: usize> NoiseAnalyzer<'a, DIM> {
+    pub const DEFAULT_SIZE: usize = 256;
+    pub const DEFAULT_HISTOGRAM_BINS: usize = 32;
+
+    pub fn new(source_module: &'a dyn NoiseFn<f64, DIM>) -> Self {
+        assert!(
+            DIM >= 2,
+            "NoiseAnalyzer requires a noise function of at least 2 dimensions"
+        );
+
+        Self {
+            x_bounds: (-16.0, 16.0),
+            y_bounds: (-16.0, 16.0),
+            size: (Self::DEFAULT_SIZE, Self::DEFAULT_SIZE),
+            histogram_bins: Self::DEFAULT_HISTOGRAM_BINS,
+            source_module,
+        }
+    }
+
+    pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
+        Self {
+            x_bounds: (lower_x_bound, upper_x_bound),
+            ..self
+        }
+    }
+
+    pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
+        Self {
+            y_bounds: (lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    /// Sets the number of samples taken along each axis.
+    ///
+    /// The power spectrum is computed much faster when both are powers of two.
+    pub fn set_size(self, width: usize, height: usize) -> Self {
+        Self {
+            size: (width, height),
+            ..self
+        }
+    }
+
+    /// Sets the number of bins in the histogram of the sampled values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `histogram_bins` is zero.
+    pub fn set_histogram_bins(self, histogram_bins: usize) -> Self {
+        assert!(histogram_bins > 0, "at least one histogram bin is required");
+
+        Self {
+            histogram_bins,
+            ..self
+        }
+    }
+
+    pub fn set_source_module(self, source_module: &'a dyn NoiseFn<f64, DIM>) -> Self {
+        Self {
+            source_module,
+            ..self
+        }
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_bounds
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_bounds
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    pub fn histogram_bins(&self) -> usize {
+        self.histogram_bins
+    }
+
+    /// Samples the noise function and computes the statistics of its output.
+    pub fn analyze(&self) -> NoiseStats {
+        let (width, height) = self.size;
+
+        let x_step = (self.x_bounds.1 - self.x_bounds.0) / width as f64;
+        let y_step = (self.y_bounds.1 - self.y_bounds.0) / height as f64;
+
+        let mut samples = Vec::with_capacity(width * height);
+        let mut point = [0.0; DIM];
+
+        for y in 0..height {
+            point[1] = self.y_bounds.0 + y_step * y as f64;
+
+            for x in 0..width {
+                point[0] = self.x_bounds.0 + x_step * x as f64;
+
+                samples.push(self.source_module.get(point));
+            }
+        }
+
+        NoiseStats::from_samples(&samples, width, height, self.histogram_bins)
+    }
+}
+
+/// Statistics about the output of a noise function, as computed by a
+/// `NoiseAnalyzer`.
+#[derive(Clone, Debug)]
+pub struct NoiseStats {
+    /// The smallest sampled value.
+    pub min: f64,
+
+    /// The largest sampled value.
+    pub max: f64,
+
+    /// The mean of the sampled values.
+    pub mean: f64,
+
+    /// The population variance of the sampled values.
+    pub variance: f64,
+
+    /// The distribution of the sampled values between `min` and `max`.
+    pub histogram: Histogram,
+
+    /// The radially averaged power spectrum of the sampled values.
+    ///
+    /// Entry `k` holds the mean power of the frequencies completing `k` cycles
+    /// across the shorter side of the sampled region, up to the Nyquist
+    /// frequency. The mean is removed before the spectrum is computed, and the
+    /// power of each frequency is normalised so that the powers of all the
+    /// individual frequencies sum to the variance. As each entry is a mean over
+    /// a ring of frequencies, the entries themselves do not.
+    pub power_spectrum: Vec<f64>,
+}
+
+impl NoiseStats {
+    /// Computes the statistics of a noise map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `histogram_bins` is zero.
+    pub fn from_noise_map(noise_map: &NoiseMap, histogram_bins: usize) -> Self {
+        assert!(histogram_bins > 0, "at least one histogram bin is required");
+
+        let (width, height) = noise_map.size();
+
+        let mut samples = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                samples.push(noise_map.get_value(x, y));
+            }
+        }
+
+        Self::from_samples(&samples, width, height, histogram_bins)
+    }
+
+    fn from_samples(samples: &[f64], width: usize, height: usize, histogram_bins: usize) -> Self {
+        let count = samples.len().max(1) as f64;
+
+        let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let mean = samples.iter().sum::<f64>() / count;
+        let variance = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / count;
+
+        Self {
+            min,
+            max,
+            mean,
+            variance,
+            histogram: Histogram::new(samples, min, max, histogram_bins),
+            power_spectrum: power_spectrum(samples, mean, width, height),
+        }
+    }
+
+    /// Returns the standard deviation of the sampled values.
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    /// Suggests the scale and bias for a `ScaleBias` that maps the sampled
+    /// range of values onto [-1, 1].
+    pub fn suggested_scale_bias(&self) -> (f64, f64) {
+        let range = self.max - self.min;
+
+        if range > 0.0 {
+            (2.0 / range, -(self.max + self.min) / range)
+        } else {
+            (1.0, -self.mean)
+        }
+    }
+
+    /// Suggests the scale and bias for a `ScaleBias` that maps the values
+    /// within `deviations` standard deviations of the mean onto [-1, 1].
+    ///
+    /// This is less sensitive to rare outliers than `suggested_scale_bias`, but
+    /// values beyond the chosen number of deviations will fall outside of
+    /// [-1, 1], so it is best paired with a `Clamp`.
+    pub fn suggested_scale_bias_for_deviations(&self, deviations: f64) -> (f64, f64) {
+        let spread = self.std_dev() * deviations;
+
+        if spread > 0.0 {
+            (1.0 / spread, -self.mean / spread)
+        } else {
+            (1.0, -self.mean)
+        }
+    }
+}
+
+/// A histogram of sampled values, with bins of equal width.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    /// The lower bound of the first bin.
+    pub min: f64,
+
+    /// The upper bound of the last bin.
+    pub max: f64,
+
+    /// The number of values in each bin.
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    fn new(samples: &[f64], min: f64, max: f64, bins: usize) -> Self {
+        let mut counts = vec![0; bins];
+        let width = (max - min) / bins as f64;
+
+        for &sample in samples {
+            let bin = if width > 0.0 {
+                ((sample - min) / width) as usize
+            } else {
+                0
+            };
+
+            // The maximum value falls on the upper bound of the last bin.
+            counts[bin.min(bins - 1)] += 1;
+        }
+
+        Self { min, max, counts }
+    }
+
+    /// Returns the width of each bin.
+    pub fn bin_width(&self) -> f64 {
+        (self.max - self.min) / self.counts.len() as f64
+    }
+
+    /// Returns the value at the center of the given bin.
+    pub fn bin_center(&self, bin: usize) -> f64 {
+        self.min + self.bin_width() * (bin as f64 + 0.5)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn from_polar(magnitude: f64, angle: f64) -> Self {
+        Self {
+            re: magnitude * math::cos(angle),
+            im: magnitude * math::sin(angle),
+        }
+    }
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            re: self.re + other.re,
+            im: self.im + other.im,
+        }
+    }
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            re: self.re - other.re,
+            im: self.im - other.im,
+        }
+    }
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+
+    fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+/// Computes the radially averaged power spectrum of a grid of samples.
+fn power_spectrum(samples: &[f64], mean: f64, width: usize, height: usize) -> Vec<f64> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    let mut grid: Vec<Complex> = samples
+        .iter()
+        .map(|&x| Complex {
+            re: x - mean,
+            im: 0.0,
+        })
+        .collect();
+
+    // Transform the rows, then the columns.
+    let mut buffer = vec![Complex::default(); width.max(height)];
+    for row in grid.chunks_mut(width) {
+        dft(row, &mut buffer[..width]);
+    }
+    let mut column = vec![Complex::default(); height];
+    for x in 0..width {
+        for y in 0..height {
+            column[y] = grid[x + y * width];
+        }
+        dft(&mut column, &mut buffer[..height]);
+        for y in 0..height {
+            grid[x + y * width] = column[y];
+        }
+    }
+
+    // Average the power of the frequencies falling in each ring of the spectrum.
+    let shortest_side = width.min(height);
+    let rings = shortest_side / 2 + 1;
+    let mut power = vec![0.0; rings];
+    let mut counts = vec![0_usize; rings];
+    let normalisation = 1.0 / (samples.len() as f64 * samples.len() as f64);
+
+    for v in 0..height {
+        let fv = signed_frequency(v, height) / height as f64;
+
+        for u in 0..width {
+            let fu = signed_frequency(u, width) / width as f64;
+
+            let radius = ((fu * fu + fv * fv).sqrt() * shortest_side as f64).round() as usize;
+            if radius < rings {
+                power[radius] += grid[u + v * width].norm_sqr() * normalisation;
+                counts[radius] += 1;
+            }
+        }
+    }
+
+    for (power, &count) in power.iter_mut().zip(&counts) {
+        if count > 0 {
+            *power /= count as f64;
+        }
+    }
+
+    power
+}
+
+/// Maps the index of a DFT bin to its signed frequency.
+fn signed_frequency(index: usize, len: usize) -> f64 {
+    if index <= len / 2 {
+        index as f64
+    } else {
+        index as f64 - len as f64
+    }
+}
+
+/// Computes the discrete Fourier transform of `values` in place, using a
+/// radix-2 FFT when the length is a power of two.
+fn dft(values: &mut [Complex], buffer: &mut [Complex]) {
+    let len = values.len();
+
+    if len.is_power_of_two() {
+        fft(values);
+        return;
+    }
+
+    for (k, out) in buffer.iter_mut().enumerate() {
+        *out = values
+            .iter()
+            .enumerate()
+            .fold(Complex::default(), |acc, (n, &x)| {
+                let angle = -2.0 * PI * ((k * n) % len) as f64 / len as f64;
+                acc.add(x.mul(Complex::from_polar(1.0, angle)))
+            });
+    }
+    values.copy_from_slice(buffer);
+}
+
+/// Iterative radix-2 Cooley-Tukey FFT.
+fn fft(values: &mut [Complex]) {
+    let len = values.len();
+
+    // Reorder the values into bit-reversed order.
+    let mut j = 0;
+    for i in 1..len {
+        let mut bit = len >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+    while size <= len {
+        let step = Complex::from_polar(1.0, -2.0 * PI / size as f64);
+
+        for start in (0..len).step_by(size) {
+            let mut twiddle = Complex { re: 1.0, im: 0.0 };
+
+            for k in 0..size / 2 {
+                let even = values[start + k];
+                let odd = values[start + k + size / 2].mul(twiddle);
+
+                values[start + k] = even.add(odd);
+                values[start + k + size / 2] = even.sub(odd);
+
+                twiddle = twiddle.mul(step);
+            }
+        }
+
+        size <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    struct Wave {
+        cycles: f64,
+    }
+
+    impl NoiseFn<f64, 2> for Wave {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            (point[0] * self.cycles * 2.0 * PI).sin()
+        }
+    }
+
+    #[test]
+    fn test_constant_stats() {
+        let constant = Constant::new(0.5);
+        let stats = NoiseAnalyzer::<3>::new(&constant)
+            .set_size(16, 16)
+            .analyze();
+
+        assert_eq!(stats.min, 0.5);
+        assert_eq!(stats.max, 0.5);
+        assert_eq!(stats.variance, 0.0);
+        assert_eq!(stats.histogram.counts[0], 256);
+        assert_eq!(stats.suggested_scale_bias(), (1.0, -0.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one histogram bin is required")]
+    fn test_zero_histogram_bins() {
+        let constant = Constant::new(0.5);
+        let _ = NoiseAnalyzer::<2>::new(&constant).set_histogram_bins(0);
+    }
+
+    #[test]
+    fn test_power_spectrum_peak() {
+        for &(width, height) in &[(64, 64), (48, 40)] {
+            let wave = Wave { cycles: 5.0 };
+            let stats = NoiseAnalyzer::new(&wave)
+                .set_x_bounds(0.0, 1.0)
+                .set_y_bounds(0.0, 1.0)
+                .set_size(width, height)
+                .analyze();
+
+            let peak = (0..stats.power_spectrum.len())
+                .max_by(|&a, &b| {
+                    stats.power_spectrum[a]
+                        .partial_cmp(&stats.power_spectrum[b])
+                        .unwrap()
+                })
+                .unwrap();
+
+            assert!((stats.variance - 0.5).abs() < 1e-9);
+            if width == height {
+                assert_eq!(peak, 5);
+            } else {
+                // The wave runs along the longer side, so it completes fewer
+                // cycles across the shorter one.
+                assert_eq!(peak, (5.0 * height as f64 / width as f64).round() as usize);
+            }
+        }
+    }
+}
@@ -1,9 +1,143 @@
+use crate::{
+    math::{self, interpolate},
+    utils::noise_map::NoiseMap,
+};
+use std::{error::Error, fmt};
+
 pub type Color = [u8; 4];
 
+/// The color space in which a `ColorGradient` blends between its points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Blends the gamma-encoded sRGB channels directly. This is the cheapest,
+    /// but darkens the middle of gradients between saturated colors.
+    Srgb,
+
+    /// Blends in linear RGB, which mixes colors the way light does.
+    LinearRgb,
+
+    /// Blends in the OKLab perceptual color space, which keeps the perceived
+    /// lightness and hue changing evenly along the gradient.
+    Oklab,
+
+    /// Blends the hue, saturation and value, taking the shorter way around the
+    /// hue circle.
+    Hsv,
+}
+
+// `#[default]` on enum variants requires a newer compiler than we support.
+#[allow(clippy::derivable_impls)]
+impl Default for ColorSpace {
+    fn default() -> Self {
+        ColorSpace::Srgb
+    }
+}
+
+/// How a `ColorGradient` picks colors between its points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GradientMode {
+    /// Blends between the two surrounding points, shaped by the easing of the
+    /// segment between them.
+    Interpolate,
+
+    /// Holds the color of the point below the position until the next point
+    /// is reached, producing hard-edged bands.
+    Step,
+
+    /// Uses the color of whichever point is closest to the position.
+    Nearest,
+}
+
+// `#[default]` on enum variants requires a newer compiler than we support.
+#[allow(clippy::derivable_impls)]
+impl Default for GradientMode {
+    fn default() -> Self {
+        GradientMode::Interpolate
+    }
+}
+
+/// The easing curve applied to the blend across a segment of a
+/// `ColorGradient` in `GradientMode::Interpolate`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Easing {
+    /// Blends at a constant rate across the segment.
+    Linear,
+
+    /// Eases in and out using the cubic `3t² - 2t³` curve.
+    SmoothStep,
+
+    /// Eases in and out using the quintic `6t⁵ - 15t⁴ + 10t³` curve, which
+    /// also has a continuous second derivative at the ends.
+    Quintic,
+
+    /// Starts slowly and speeds up towards the end of the segment.
+    EaseIn,
+
+    /// Starts quickly and slows down towards the end of the segment.
+    EaseOut,
+}
+
+impl Easing {
+    fn apply(self, t: f64) -> f64 {
+        match self {
+            Easing::Linear => t,
+            Easing::SmoothStep => t * t * (3.0 - 2.0 * t),
+            Easing::Quintic => t * t * t * (t * (t * 6.0 - 15.0) + 10.0),
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+        }
+    }
+}
+
+// `#[default]` on enum variants requires a newer compiler than we support.
+#[allow(clippy::derivable_impls)]
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::Linear
+    }
+}
+
+/// An error returned when loading a `ColorGradient` from a palette file fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GradientParseError {
+    /// The data does not start with the header of the expected format.
+    InvalidHeader,
+
+    /// The line with the given number, counting from 1, is malformed.
+    InvalidLine(usize),
+
+    /// The data does not contain any gradient points.
+    Empty,
+
+    /// The data contains fewer segments than its header declares.
+    MissingSegments { expected: usize, actual: usize },
+}
+
+impl fmt::Display for GradientParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GradientParseError::InvalidHeader => write!(f, "invalid gradient header"),
+            GradientParseError::InvalidLine(line) => {
+                write!(f, "invalid gradient data on line {}", line)
+            }
+            GradientParseError::Empty => write!(f, "gradient contains no points"),
+            GradientParseError::MissingSegments { expected, actual } => write!(
+                f,
+                "gradient declares {} segments but contains {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl Error for GradientParseError {}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct GradientPoint {
     pos: f64,
     color: Color,
+    // Easing of the segment running from this point to the next one.
+    easing: Easing,
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -30,6 +164,8 @@ impl GradientDomain {
 pub struct ColorGradient {
     gradient_points: Vec<GradientPoint>,
     domain: GradientDomain,
+    color_space: ColorSpace,
+    mode: GradientMode,
 }
 
 impl ColorGradient {
@@ -37,13 +173,171 @@ impl ColorGradient {
         let gradient = Self {
             gradient_points: Vec::new(),
             domain: GradientDomain::new(0.0, 1.0),
+            color_space: ColorSpace::Srgb,
+            mode: GradientMode::Interpolate,
         };
 
         gradient.build_grayscale_gradient()
     }
 
-    pub fn add_gradient_point(mut self, pos: f64, color: Color) -> Self {
-        let new_point = GradientPoint { pos, color };
+    /// Loads a gradient from the contents of a GIMP gradient (`.ggr`) file.
+    ///
+    /// The positions of the gradient, which range from 0.0 to 1.0, are mapped
+    /// onto [-1, 1]. Each segment is approximated by blending linearly through
+    /// its midpoint, regardless of its blending function and coloring type.
+    /// Where a segment starts with a different color than the previous one
+    /// ends, the gradient jumps from one to the other.
+    pub fn from_ggr(data: &str) -> Result<Self, GradientParseError> {
+        let mut lines = data.lines().enumerate();
+
+        match lines.next() {
+            Some((_, header)) if header.trim() == "GIMP Gradient" => {}
+            _ => return Err(GradientParseError::InvalidHeader),
+        }
+
+        let mut lines = lines.filter(|(_, line)| !line.trim().is_empty());
+        let mut next_line = lines.next();
+        if let Some((_, line)) = next_line {
+            if line.starts_with("Name:") {
+                next_line = lines.next();
+            }
+        }
+
+        let segment_count = match next_line {
+            Some((number, line)) => line
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| GradientParseError::InvalidLine(number + 1))?,
+            None => return Err(GradientParseError::Empty),
+        };
+
+        let mut gradient = Self::new().clear_gradient();
+        let mut segments = 0;
+
+        for (number, line) in lines.take(segment_count) {
+            let values = line
+                .split_whitespace()
+                .map(|value| value.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| GradientParseError::InvalidLine(number + 1))?;
+
+            if values.len() < 11 {
+                return Err(GradientParseError::InvalidLine(number + 1));
+            }
+
+            let to_color = |channels: &[f64]| -> Color {
+                let mut color = Color::default();
+                for (channel, value) in color.iter_mut().zip(channels) {
+                    *channel = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+                color
+            };
+            let left = to_color(&values[3..7]);
+            let right = to_color(&values[7..11]);
+            let middle = interpolate_color_in(left, right, 0.5, gradient.color_space);
+
+            let start = values[0] * 2.0 - 1.0;
+            gradient = match gradient.gradient_points.last() {
+                Some(last) if (last.pos - start).abs() < f64::EPSILON && last.color != left => {
+                    gradient.add_gradient_edge(start, left)
+                }
+                _ => gradient.add_gradient_point(start, left),
+            };
+            gradient = gradient
+                .add_gradient_point(values[1] * 2.0 - 1.0, middle)
+                .add_gradient_point(values[2] * 2.0 - 1.0, right);
+            segments += 1;
+        }
+
+        if segments < segment_count {
+            return Err(GradientParseError::MissingSegments {
+                expected: segment_count,
+                actual: segments,
+            });
+        }
+
+        if gradient.gradient_points.is_empty() {
+            Err(GradientParseError::Empty)
+        } else {
+            Ok(gradient)
+        }
+    }
+
+    /// Loads a gradient from comma-separated stops, one per line, in the form
+    /// `position,red,green,blue[,alpha]`, with the channels ranging from 0 to
+    /// 255. Blank lines and lines starting with `#` are ignored.
+    pub fn from_csv(data: &str) -> Result<Self, GradientParseError> {
+        let mut gradient = Self::new().clear_gradient();
+
+        for (number, line) in data.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = GradientParseError::InvalidLine(number + 1);
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            if fields.len() != 4 && fields.len() != 5 {
+                return Err(error);
+            }
+
+            let pos = fields[0].parse::<f64>().map_err(|_| error)?;
+            let mut color = [0, 0, 0, 255];
+            for (channel, field) in color.iter_mut().zip(&fields[1..]) {
+                *channel = field.parse::<u8>().map_err(|_| error)?;
+            }
+
+            gradient = gradient.add_gradient_point(pos, color);
+        }
+
+        if gradient.gradient_points.is_empty() {
+            Err(GradientParseError::Empty)
+        } else {
+            Ok(gradient)
+        }
+    }
+
+    /// Sets the color space in which the gradient blends between its points.
+    pub fn set_color_space(self, color_space: ColorSpace) -> Self {
+        Self {
+            color_space,
+            ..self
+        }
+    }
+
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
+    /// Sets how the gradient picks colors between its points.
+    pub fn set_mode(self, mode: GradientMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    pub fn mode(&self) -> GradientMode {
+        self.mode
+    }
+
+    pub fn add_gradient_point(self, pos: f64, color: Color) -> Self {
+        self.add_gradient_point_with_easing(pos, color, Easing::Linear)
+    }
+
+    /// Adds a gradient point whose segment, running up to the next point,
+    /// blends using the given easing curve.
+    pub fn add_gradient_point_with_easing(
+        mut self,
+        pos: f64,
+        color: Color,
+        easing: Easing,
+    ) -> Self {
+        let new_point = GradientPoint { pos, color, easing };
+
+        // the first point after a clear sets the domain, rather than extending the cleared one
+        if self.gradient_points.is_empty() {
+            self.domain = GradientDomain::new(pos, pos);
+            self.gradient_points.push(new_point);
+            return self;
+        }
 
         // first check to see if the position is within the domain of the gradient. if the position
         // is not within the domain, expand the domain and add the GradientPoint
@@ -74,6 +368,30 @@ impl ColorGradient {
         self
     }
 
+    /// Adds a gradient point after any existing points at the same position,
+    /// so that the gradient jumps from their color to this one there.
+    fn add_gradient_edge(mut self, pos: f64, color: Color) -> Self {
+        if pos < self.domain.min || pos > self.domain.max || self.gradient_points.is_empty() {
+            return self.add_gradient_point(pos, color);
+        }
+
+        let insertion_point = self
+            .gradient_points
+            .iter()
+            .position(|x| x.pos > pos)
+            .unwrap_or(self.gradient_points.len());
+        self.gradient_points.insert(
+            insertion_point,
+            GradientPoint {
+                pos,
+                color,
+                easing: Easing::Linear,
+            },
+        );
+
+        self
+    }
+
     fn find_insertion_point(&self, pos: f64) -> usize {
         self.gradient_points
             .iter()
@@ -122,6 +440,57 @@ impl ColorGradient {
             .add_gradient_point( 1.0, [255,   0,   0, 255])
     }
 
+    /// Builds the perceptually uniform viridis colormap, running from dark
+    /// blue through green to yellow.
+    pub fn build_viridis_gradient(self) -> Self {
+        self.build_colormap(&[
+            0x440154, 0x482878, 0x3e4989, 0x31688e, 0x26828e, 0x1f9e89, 0x35b779, 0x6ece58,
+            0xb5de2b, 0xfde725,
+        ])
+    }
+
+    /// Builds the perceptually uniform magma colormap, running from black
+    /// through purple and orange to pale yellow.
+    pub fn build_magma_gradient(self) -> Self {
+        self.build_colormap(&[
+            0x000004, 0x180f3d, 0x440f76, 0x721f81, 0x9e2f7f, 0xcd4071, 0xf1605d, 0xfd9668,
+            0xfeca8d, 0xfcfdbf,
+        ])
+    }
+
+    /// Builds the perceptually uniform inferno colormap, running from black
+    /// through purple and orange to bright yellow.
+    pub fn build_inferno_gradient(self) -> Self {
+        self.build_colormap(&[
+            0x000004, 0x1b0c41, 0x4a0c6b, 0x781c6d, 0xa52c60, 0xcf4446, 0xed6925, 0xfb9b06,
+            0xf7d13d, 0xfcffa4,
+        ])
+    }
+
+    /// Builds the perceptually uniform plasma colormap, running from dark blue
+    /// through magenta and orange to yellow.
+    pub fn build_plasma_gradient(self) -> Self {
+        self.build_colormap(&[
+            0x0d0887, 0x46039f, 0x7201a8, 0x9c179e, 0xbd3786, 0xd8576b, 0xed7953, 0xfb9f3a,
+            0xfdca26, 0xf0f921,
+        ])
+    }
+
+    /// Spreads opaque colors given as `0xRRGGBB` evenly over [-1, 1].
+    fn build_colormap(self, colors: &[u32]) -> Self {
+        let step = 2.0 / (colors.len() - 1) as f64;
+
+        colors
+            .iter()
+            .enumerate()
+            .fold(self.clear_gradient(), |gradient, (i, &color)| {
+                gradient.add_gradient_point(
+                    -1.0 + step * i as f64,
+                    [(color >> 16) as u8, (color >> 8) as u8, color as u8, 255],
+                )
+            })
+    }
+
     pub fn get_color(&self, pos: f64) -> Color {
         let mut color = Color::default();
 
@@ -131,15 +500,20 @@ impl ColorGradient {
         } else {
             match () {
                 _ if pos < self.domain.min => color = self.gradient_points.first().unwrap().color,
-                _ if pos > self.domain.max => color = self.gradient_points.last().unwrap().color,
+                _ if pos >= self.domain.max => color = self.gradient_points.last().unwrap().color,
                 _ => {
                     for points in self.gradient_points.windows(2) {
                         if (points[0].pos <= pos) && (points[1].pos > pos) {
-                            // Compute the alpha value used for linear interpolation
-                            let alpha = (pos - points[0].pos) / (points[1].pos - points[0].pos);
-
-                            // Now perform the interpolation and return.
-                            color = interpolate_color(points[0].color, points[1].color, alpha)
+                            color = match self.segment_alpha(points[0], points[1], pos) {
+                                alpha if alpha <= 0.0 => points[0].color,
+                                alpha if alpha >= 1.0 => points[1].color,
+                                alpha => interpolate_color_in(
+                                    points[0].color,
+                                    points[1].color,
+                                    alpha,
+                                    self.color_space,
+                                ),
+                            }
                         }
                     }
                 }
@@ -148,6 +522,269 @@ impl ColorGradient {
             color
         };
     }
+
+    /// Returns the color at the given position as RGBA channels ranging from
+    /// 0.0 to 1.0, without rounding them to 8 bits.
+    ///
+    /// The channels are encoded in sRGB, like those returned by `get_color`.
+    pub fn get_color_f32(&self, pos: f64) -> [f32; 4] {
+        let mut color = [0.0; 4];
+
+        if let (Some(first), Some(last)) =
+            (self.gradient_points.first(), self.gradient_points.last())
+        {
+            if pos < self.domain.min {
+                color = color_to_f32(first.color);
+            } else if pos >= self.domain.max {
+                color = color_to_f32(last.color);
+            } else {
+                for points in self.gradient_points.windows(2) {
+                    if (points[0].pos <= pos) && (points[1].pos > pos) {
+                        let alpha = self.segment_alpha(points[0], points[1], pos);
+
+                        let blended =
+                            blend(points[0].color, points[1].color, alpha, self.color_space);
+                        for (channel, value) in color.iter_mut().zip(&blended) {
+                            *channel = *value as f32;
+                        }
+                    }
+                }
+            }
+        }
+
+        color
+    }
+
+    /// Classifies every value of the noise map by the gradient point it falls
+    /// under, returning the indices of the points in row-major order.
+    ///
+    /// In `GradientMode::Nearest` a value belongs to the closest point;
+    /// otherwise it belongs to the last point at or below it. Values outside
+    /// the domain of the gradient belong to its first or last point. Indices
+    /// past 255 are clamped to 255.
+    pub fn classify(&self, noise_map: &NoiseMap) -> Vec<u8> {
+        let (width, height) = noise_map.size();
+        let mut categories = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let category = self.category(noise_map.get_value(x, y));
+                categories.push(category.min(u8::MAX as usize) as u8);
+            }
+        }
+
+        categories
+    }
+
+    fn category(&self, pos: f64) -> usize {
+        let above = self
+            .gradient_points
+            .iter()
+            .position(|point| point.pos > pos);
+
+        match above {
+            None => self.gradient_points.len().saturating_sub(1),
+            Some(0) => 0,
+            Some(i) => {
+                let (lower, upper) = (self.gradient_points[i - 1], self.gradient_points[i]);
+                if self.mode == GradientMode::Nearest && upper.pos - pos < pos - lower.pos {
+                    i
+                } else {
+                    i - 1
+                }
+            }
+        }
+    }
+
+    /// Computes how far to blend from `lower` to `upper` at the given
+    /// position, according to the mode of the gradient.
+    fn segment_alpha(&self, lower: GradientPoint, upper: GradientPoint, pos: f64) -> f64 {
+        let alpha = (pos - lower.pos) / (upper.pos - lower.pos);
+
+        match self.mode {
+            GradientMode::Interpolate => lower.easing.apply(alpha),
+            GradientMode::Step => 0.0,
+            GradientMode::Nearest => {
+                if alpha > 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+fn color_to_f32(color: Color) -> [f32; 4] {
+    let mut result = [0.0; 4];
+    for (channel, value) in result.iter_mut().zip(&color) {
+        *channel = f32::from(*value) / 255.0;
+    }
+    result
+}
+
+/// Interpolates between two colors in the given color space.
+fn interpolate_color_in(
+    color0: Color,
+    color1: Color,
+    alpha: f64,
+    color_space: ColorSpace,
+) -> Color {
+    if color_space == ColorSpace::Srgb {
+        return interpolate_color(color0, color1, alpha);
+    }
+
+    let mut color = Color::default();
+    for (channel, value) in color
+        .iter_mut()
+        .zip(&blend(color0, color1, alpha, color_space))
+    {
+        *channel = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+    color
+}
+
+/// Blends two colors in the given color space, returning sRGB-encoded RGBA
+/// channels ranging from 0.0 to 1.0. Alpha is always blended linearly.
+fn blend(color0: Color, color1: Color, alpha: f64, color_space: ColorSpace) -> [f64; 4] {
+    let to_srgb = |color: Color| {
+        [
+            f64::from(color[0]) / 255.0,
+            f64::from(color[1]) / 255.0,
+            f64::from(color[2]) / 255.0,
+        ]
+    };
+    let srgb0 = to_srgb(color0);
+    let srgb1 = to_srgb(color1);
+
+    let lerp = |a: f64, b: f64| a + (b - a) * alpha;
+    let lerp3 = |a: [f64; 3], b: [f64; 3]| [lerp(a[0], b[0]), lerp(a[1], b[1]), lerp(a[2], b[2])];
+
+    let rgb = match color_space {
+        ColorSpace::Srgb => lerp3(srgb0, srgb1),
+        ColorSpace::LinearRgb => {
+            linear_to_srgb(lerp3(srgb_to_linear(srgb0), srgb_to_linear(srgb1)))
+        }
+        ColorSpace::Oklab => linear_to_srgb(oklab_to_linear(lerp3(
+            linear_to_oklab(srgb_to_linear(srgb0)),
+            linear_to_oklab(srgb_to_linear(srgb1)),
+        ))),
+        ColorSpace::Hsv => {
+            let mut hsv0 = rgb_to_hsv(srgb0);
+            let mut hsv1 = rgb_to_hsv(srgb1);
+
+            // Greys have no hue, so take it from the other color.
+            if hsv0[1] == 0.0 {
+                hsv0[0] = hsv1[0];
+            } else if hsv1[1] == 0.0 {
+                hsv1[0] = hsv0[0];
+            }
+
+            // Go the shorter way around the hue circle.
+            if hsv1[0] - hsv0[0] > 0.5 {
+                hsv0[0] += 1.0;
+            } else if hsv0[0] - hsv1[0] > 0.5 {
+                hsv1[0] += 1.0;
+            }
+
+            let mut hsv = lerp3(hsv0, hsv1);
+            hsv[0] = hsv[0].rem_euclid(1.0);
+            hsv_to_rgb(hsv)
+        }
+    };
+
+    [
+        rgb[0].clamp(0.0, 1.0),
+        rgb[1].clamp(0.0, 1.0),
+        rgb[2].clamp(0.0, 1.0),
+        lerp(f64::from(color0[3]), f64::from(color1[3])) / 255.0,
+    ]
+}
+
+fn srgb_to_linear(mut rgb: [f64; 3]) -> [f64; 3] {
+    for c in rgb.iter_mut() {
+        *c = if *c <= 0.04045 {
+            *c / 12.92
+        } else {
+            math::powf((*c + 0.055) / 1.055, 2.4)
+        };
+    }
+    rgb
+}
+
+fn linear_to_srgb(mut rgb: [f64; 3]) -> [f64; 3] {
+    for c in rgb.iter_mut() {
+        *c = if *c <= 0.003_130_8 {
+            *c * 12.92
+        } else {
+            1.055 * math::powf(*c, 1.0 / 2.4) - 0.055
+        };
+    }
+    rgb
+}
+
+fn linear_to_oklab([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let l = math::cbrt(0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b);
+    let m = math::cbrt(0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b);
+    let s = math::cbrt(0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b);
+
+    [
+        0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s,
+        1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s,
+        0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766_0 * s,
+    ]
+}
+
+fn oklab_to_linear([l, a, b]: [f64; 3]) -> [f64; 3] {
+    let l_ = l + 0.396_337_777_4 * a + 0.215_803_757_3 * b;
+    let m_ = l - 0.105_561_345_8 * a - 0.063_854_172_8 * b;
+    let s_ = l - 0.089_484_177_5 * a - 1.291_485_548_0 * b;
+
+    let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+
+    [
+        4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s,
+        -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s,
+        -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s,
+    ]
+}
+
+/// Converts RGB to hue, saturation and value, all ranging from 0.0 to 1.0.
+fn rgb_to_hsv([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6.0) / 6.0
+    } else if max == g {
+        ((b - r) / delta + 2.0) / 6.0
+    } else {
+        ((r - g) / delta + 4.0) / 6.0
+    };
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+    [hue, saturation, max]
+}
+
+fn hsv_to_rgb([hue, saturation, value]: [f64; 3]) -> [f64; 3] {
+    let sector = hue * 6.0;
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let m = value - chroma;
+
+    let (r, g, b) = match sector as usize {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    [r + m, g + m, b + m]
 }
 
 fn interpolate_color(color0: Color, color1: Color, alpha: f64) -> Color {
@@ -155,7 +792,7 @@ fn interpolate_color(color0: Color, color1: Color, alpha: f64) -> Color {
         let c0 = (f64::from(channel0)) / 255.0;
         let c1 = (f64::from(channel1)) / 255.0;
 
-        ((c1 - c0).mul_add(alpha, c0) * 255.0) as u8
+        (interpolate::linear(c0, c1, alpha) * 255.0) as u8
     }
 
     let mut color = Color::default();
@@ -190,4 +827,144 @@ mod tests {
 
         assert_eq!([127, 127, 127, 127], gradient.get_color(0.5));
     }
+
+    #[test]
+    fn color_spaces() {
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_gradient_point(0.0, [255, 0, 0, 255])
+            .add_gradient_point(1.0, [0, 255, 0, 255]);
+
+        assert_eq!(
+            [0.5, 0.5, 0.0, 1.0],
+            gradient
+                .clone()
+                .set_color_space(ColorSpace::Srgb)
+                .get_color_f32(0.5)
+        );
+        assert_eq!(
+            [188, 188, 0, 255],
+            gradient
+                .clone()
+                .set_color_space(ColorSpace::LinearRgb)
+                .get_color(0.5)
+        );
+        assert_eq!(
+            [255, 255, 0, 255],
+            gradient.set_color_space(ColorSpace::Hsv).get_color(0.5)
+        );
+    }
+
+    #[test]
+    fn gradient_modes() {
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_gradient_point(0.0, [0, 0, 0, 255])
+            .add_gradient_point_with_easing(1.0, [100, 100, 100, 255], Easing::EaseIn)
+            .add_gradient_point(2.0, [200, 200, 200, 255]);
+
+        assert_eq!([50, 50, 50, 255], gradient.get_color(0.5));
+        assert_eq!([125, 125, 125, 255], gradient.get_color(1.5));
+
+        let gradient = gradient.set_mode(GradientMode::Step);
+        assert_eq!([0, 0, 0, 255], gradient.get_color(0.9));
+        assert_eq!([100, 100, 100, 255], gradient.get_color(1.9));
+
+        let gradient = gradient.set_mode(GradientMode::Nearest);
+        assert_eq!([100, 100, 100, 255], gradient.get_color(0.9));
+        assert_eq!([200, 200, 200, 255], gradient.get_color(1.9));
+    }
+
+    #[test]
+    fn classify_noise_map() {
+        let mut noise_map = NoiseMap::new(5, 1);
+        for (x, value) in [-1.0, 0.2, 0.7, 1.0, 3.0].iter().enumerate() {
+            noise_map.set_value(x, 0, *value);
+        }
+
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_gradient_point(0.0, [0, 0, 255, 255])
+            .add_gradient_point(1.0, [0, 255, 0, 255])
+            .add_gradient_point(2.0, [255, 255, 255, 255]);
+
+        assert_eq!(vec![0, 0, 0, 1, 2], gradient.classify(&noise_map));
+        assert_eq!(
+            vec![0, 0, 1, 1, 2],
+            gradient
+                .set_mode(GradientMode::Nearest)
+                .classify(&noise_map)
+        );
+    }
+
+    #[test]
+    fn parse_csv() {
+        let gradient =
+            ColorGradient::from_csv("# pos,r,g,b,a\n-1,0,0,0\n\n1, 255, 255, 255, 128\n").unwrap();
+
+        assert_eq!([0, 0, 0, 255], gradient.get_color(-2.0));
+        assert_eq!([255, 255, 255, 128], gradient.get_color(2.0));
+        assert_eq!(
+            Err(GradientParseError::InvalidLine(2)),
+            ColorGradient::from_csv("0,0,0,0\n1,256,0,0").map(|_| ())
+        );
+
+        // The domain covers the stops, even when they do not include 0.0.
+        let gradient = ColorGradient::from_csv("0.25,255,0,0\n0.75,0,0,255").unwrap();
+        assert_eq!([255, 0, 0, 255], gradient.get_color(0.1));
+        assert_eq!([0, 0, 255, 255], gradient.get_color(0.75));
+    }
+
+    #[test]
+    fn preset_ends() {
+        let presets = [
+            ColorGradient::new().build_viridis_gradient(),
+            ColorGradient::new().build_magma_gradient(),
+            ColorGradient::new().build_inferno_gradient(),
+            ColorGradient::new().build_plasma_gradient(),
+        ];
+
+        for gradient in &presets {
+            let first = gradient.gradient_points.first().unwrap().color;
+            let last = gradient.gradient_points.last().unwrap().color;
+
+            assert_eq!(first, gradient.get_color(-1.0));
+            assert_eq!(last, gradient.get_color(1.0));
+            assert_eq!(color_to_f32(first), gradient.get_color_f32(-1.0));
+            assert_eq!(color_to_f32(last), gradient.get_color_f32(1.0));
+        }
+    }
+
+    #[test]
+    fn parse_ggr() {
+        let data = "GIMP Gradient\nName: Test\n2\n\
+            0.0 0.25 0.5 0 0 0 1 1 0 0 1 0 0\n\
+            0.5 0.75 1.0 1 0 0 1 1 1 1 1 0 0\n";
+        let gradient = ColorGradient::from_ggr(data).unwrap();
+
+        assert_eq!([127, 0, 0, 255], gradient.get_color(-0.5));
+        assert_eq!([255, 0, 0, 255], gradient.get_color(0.0));
+        assert_eq!(
+            Err(GradientParseError::InvalidHeader),
+            ColorGradient::from_ggr("Name: Test").map(|_| ())
+        );
+        assert_eq!(
+            Err(GradientParseError::MissingSegments {
+                expected: 3,
+                actual: 2
+            }),
+            ColorGradient::from_ggr(&data.replace("\n2\n", "\n3\n")).map(|_| ())
+        );
+
+        // A segment starting with a different color than the previous one
+        // ends keeps the jump between them.
+        let data = "GIMP Gradient\n2\n\
+            0.0 0.25 0.5 0 0 0 1 0 0 0 1 0 0\n\
+            0.5 0.75 1.0 1 1 1 1 1 1 1 1 0 0\n";
+        let gradient = ColorGradient::from_ggr(data).unwrap();
+
+        assert_eq!([0, 0, 0, 255], gradient.get_color(-0.01));
+        assert_eq!([255, 255, 255, 255], gradient.get_color(0.0));
+        assert_eq!([255, 255, 255, 255], gradient.get_color(1.0));
+    }
 }
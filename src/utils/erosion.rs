@@ -0,0 +1,556 @@
+use rand::{Rng, SeedableRng};
+use rand_xorshift::XorShiftRng;
+
+use super::noise_map::NoiseMap;
+
+/// The maps optionally produced alongside an erosion pass.
+#[derive(Default)]
+pub struct ErosionMaps {
+    /// The net change in height of each cell. Positive values mark where
+    /// material was deposited, and negative values where it was eroded.
+    pub deposition: Option<NoiseMap>,
+
+    /// The total amount of water that flowed through each cell.
+    pub flow: Option<NoiseMap>,
+}
+
+/// Simulates hydraulic erosion on a `NoiseMap` treated as a height field.
+///
+/// Water droplets are dropped at random positions and run downhill, picking
+/// up sediment where they speed up and depositing it where they slow down or
+/// evaporate. The simulation is deterministic for a given seed.
+#[derive(Clone, Copy, Debug)]
+pub struct HydraulicErosion {
+    seed: u64,
+    iterations: usize,
+    max_lifetime: usize,
+    inertia: f64,
+    sediment_capacity: f64,
+    min_sediment_capacity: f64,
+    erode_speed: f64,
+    deposit_speed: f64,
+    evaporation: f64,
+    gravity: f64,
+    deposition_map_enabled: bool,
+    flow_map_enabled: bool,
+}
+
+impl HydraulicErosion {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_ITERATIONS: usize = 50_000;
+    pub const DEFAULT_MAX_LIFETIME: usize = 30;
+    pub const DEFAULT_INERTIA: f64 = 0.05;
+    pub const DEFAULT_SEDIMENT_CAPACITY: f64 = 4.0;
+    pub const DEFAULT_MIN_SEDIMENT_CAPACITY: f64 = 0.01;
+    pub const DEFAULT_ERODE_SPEED: f64 = 0.3;
+    pub const DEFAULT_DEPOSIT_SPEED: f64 = 0.3;
+    pub const DEFAULT_EVAPORATION: f64 = 0.01;
+    pub const DEFAULT_GRAVITY: f64 = 4.0;
+
+    pub fn new() -> Self {
+        Self {
+            seed: Self::DEFAULT_SEED,
+            iterations: Self::DEFAULT_ITERATIONS,
+            max_lifetime: Self::DEFAULT_MAX_LIFETIME,
+            inertia: Self::DEFAULT_INERTIA,
+            sediment_capacity: Self::DEFAULT_SEDIMENT_CAPACITY,
+            min_sediment_capacity: Self::DEFAULT_MIN_SEDIMENT_CAPACITY,
+            erode_speed: Self::DEFAULT_ERODE_SPEED,
+            deposit_speed: Self::DEFAULT_DEPOSIT_SPEED,
+            evaporation: Self::DEFAULT_EVAPORATION,
+            gravity: Self::DEFAULT_GRAVITY,
+            deposition_map_enabled: false,
+            flow_map_enabled: false,
+        }
+    }
+
+    /// Sets the seed used to place the droplets.
+    pub fn set_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Sets the number of droplets simulated.
+    pub fn set_iterations(self, iterations: usize) -> Self {
+        Self { iterations, ..self }
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Sets the maximum number of steps each droplet is simulated for.
+    pub fn set_max_lifetime(self, max_lifetime: usize) -> Self {
+        Self {
+            max_lifetime,
+            ..self
+        }
+    }
+
+    pub fn max_lifetime(&self) -> usize {
+        self.max_lifetime
+    }
+
+    /// Sets how much droplets keep their direction instead of following the
+    /// slope, between 0.0 and 1.0.
+    pub fn set_inertia(self, inertia: f64) -> Self {
+        Self {
+            inertia: inertia.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    pub fn inertia(&self) -> f64 {
+        self.inertia
+    }
+
+    /// Sets the multiplier for how much sediment a droplet can carry, relative
+    /// to its speed, volume of water and the steepness of the slope.
+    pub fn set_sediment_capacity(self, sediment_capacity: f64) -> Self {
+        Self {
+            sediment_capacity,
+            ..self
+        }
+    }
+
+    pub fn sediment_capacity(&self) -> f64 {
+        self.sediment_capacity
+    }
+
+    /// Sets the sediment capacity droplets keep on flat ground.
+    pub fn set_min_sediment_capacity(self, min_sediment_capacity: f64) -> Self {
+        Self {
+            min_sediment_capacity,
+            ..self
+        }
+    }
+
+    pub fn min_sediment_capacity(&self) -> f64 {
+        self.min_sediment_capacity
+    }
+
+    /// Sets the fraction of their free capacity droplets erode each step,
+    /// between 0.0 and 1.0.
+    pub fn set_erode_speed(self, erode_speed: f64) -> Self {
+        Self {
+            erode_speed: erode_speed.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    pub fn erode_speed(&self) -> f64 {
+        self.erode_speed
+    }
+
+    /// Sets the fraction of their excess sediment droplets deposit each step,
+    /// between 0.0 and 1.0.
+    pub fn set_deposit_speed(self, deposit_speed: f64) -> Self {
+        Self {
+            deposit_speed: deposit_speed.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    pub fn deposit_speed(&self) -> f64 {
+        self.deposit_speed
+    }
+
+    /// Sets the fraction of their water droplets lose each step, between 0.0
+    /// and 1.0.
+    pub fn set_evaporation(self, evaporation: f64) -> Self {
+        Self {
+            evaporation: evaporation.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    pub fn evaporation(&self) -> f64 {
+        self.evaporation
+    }
+
+    /// Sets how quickly droplets accelerate when running downhill.
+    pub fn set_gravity(self, gravity: f64) -> Self {
+        Self { gravity, ..self }
+    }
+
+    pub fn gravity(&self) -> f64 {
+        self.gravity
+    }
+
+    pub fn enable_deposition_map(self) -> Self {
+        Self {
+            deposition_map_enabled: true,
+            ..self
+        }
+    }
+
+    pub fn deposition_map_enabled(&self) -> bool {
+        self.deposition_map_enabled
+    }
+
+    pub fn enable_flow_map(self) -> Self {
+        Self {
+            flow_map_enabled: true,
+            ..self
+        }
+    }
+
+    pub fn flow_map_enabled(&self) -> bool {
+        self.flow_map_enabled
+    }
+
+    /// Erodes the noise map in place, returning the enabled deposition and
+    /// flow maps.
+    pub fn erode(&self, noise_map: &mut NoiseMap) -> ErosionMaps {
+        let (width, height) = noise_map.size();
+
+        let mut maps = ErosionMaps {
+            deposition: self
+                .deposition_map_enabled
+                .then(|| NoiseMap::new(width, height)),
+            flow: self.flow_map_enabled.then(|| NoiseMap::new(width, height)),
+        };
+
+        // Droplets move between the cells of the map, so it needs at least two
+        // of them along each axis.
+        if width < 2 || height < 2 {
+            return maps;
+        }
+
+        let mut rng = XorShiftRng::seed_from_u64(self.seed);
+
+        for _ in 0..self.iterations {
+            let mut position = [
+                rng.gen::<f64>() * (width - 1) as f64,
+                rng.gen::<f64>() * (height - 1) as f64,
+            ];
+            let mut direction = [0.0, 0.0];
+            let mut speed = 1.0;
+            let mut water = 1.0;
+            let mut sediment = 0.0;
+
+            for _ in 0..self.max_lifetime {
+                let cell = [position[0] as usize, position[1] as usize];
+                let offset = [position[0] - cell[0] as f64, position[1] - cell[1] as f64];
+
+                let (current_height, gradient) = height_and_gradient(noise_map, position);
+
+                if let Some(flow) = &mut maps.flow {
+                    add_bilinear(flow, cell, offset, water);
+                }
+
+                // Blend the previous direction with the downhill direction.
+                direction = [
+                    direction[0] * self.inertia - gradient[0] * (1.0 - self.inertia),
+                    direction[1] * self.inertia - gradient[1] * (1.0 - self.inertia),
+                ];
+                let length = (direction[0] * direction[0] + direction[1] * direction[1]).sqrt();
+                if length == 0.0 {
+                    break;
+                }
+                direction = [direction[0] / length, direction[1] / length];
+                position = [position[0] + direction[0], position[1] + direction[1]];
+
+                if position[0] < 0.0
+                    || position[1] < 0.0
+                    || position[0] >= (width - 1) as f64
+                    || position[1] >= (height - 1) as f64
+                {
+                    break;
+                }
+
+                let delta_height = height_and_gradient(noise_map, position).0 - current_height;
+
+                let capacity = (-delta_height * speed * water * self.sediment_capacity)
+                    .max(self.min_sediment_capacity);
+
+                let change = if delta_height > 0.0 || sediment > capacity {
+                    // Fill the pit the droplet ran into, or drop the sediment
+                    // it can no longer carry.
+                    let deposit = if delta_height > 0.0 {
+                        delta_height.min(sediment)
+                    } else {
+                        (sediment - capacity) * self.deposit_speed
+                    };
+                    sediment -= deposit;
+                    deposit
+                } else {
+                    // Never erode deeper than the cell the droplet moves to, to
+                    // avoid digging holes.
+                    let erosion = ((capacity - sediment) * self.erode_speed).min(-delta_height);
+                    sediment += erosion;
+                    -erosion
+                };
+
+                add_bilinear(noise_map, cell, offset, change);
+                if let Some(deposition) = &mut maps.deposition {
+                    add_bilinear(deposition, cell, offset, change);
+                }
+
+                speed = (speed * speed - delta_height * self.gravity)
+                    .max(0.0)
+                    .sqrt();
+                water *= 1.0 - self.evaporation;
+            }
+        }
+
+        maps
+    }
+}
+
+impl Default for HydraulicErosion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Simulates thermal erosion on a `NoiseMap` treated as a height field.
+///
+/// Wherever the height difference between neighbouring cells exceeds the
+/// talus threshold, material slides from the higher cell to the lower ones,
+/// until the slopes settle below the threshold. The total height of the map
+/// is preserved.
+#[derive(Clone, Copy, Debug)]
+pub struct ThermalErosion {
+    iterations: usize,
+    talus: f64,
+    rate: f64,
+    deposition_map_enabled: bool,
+}
+
+impl ThermalErosion {
+    pub const DEFAULT_ITERATIONS: usize = 50;
+    pub const DEFAULT_TALUS: f64 = 0.01;
+    pub const DEFAULT_RATE: f64 = 0.5;
+
+    pub fn new() -> Self {
+        Self {
+            iterations: Self::DEFAULT_ITERATIONS,
+            talus: Self::DEFAULT_TALUS,
+            rate: Self::DEFAULT_RATE,
+            deposition_map_enabled: false,
+        }
+    }
+
+    /// Sets the number of passes made over the map.
+    pub fn set_iterations(self, iterations: usize) -> Self {
+        Self { iterations, ..self }
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Sets the largest height difference between neighbouring cells that is
+    /// stable.
+    pub fn set_talus(self, talus: f64) -> Self {
+        Self { talus, ..self }
+    }
+
+    pub fn talus(&self) -> f64 {
+        self.talus
+    }
+
+    /// Sets the fraction of the excess height moved in each pass, between 0.0
+    /// and 1.0.
+    pub fn set_rate(self, rate: f64) -> Self {
+        Self {
+            rate: rate.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn enable_deposition_map(self) -> Self {
+        Self {
+            deposition_map_enabled: true,
+            ..self
+        }
+    }
+
+    pub fn deposition_map_enabled(&self) -> bool {
+        self.deposition_map_enabled
+    }
+
+    /// Erodes the noise map in place, returning the deposition map if it is
+    /// enabled. Thermal erosion never produces a flow map.
+    pub fn erode(&self, noise_map: &mut NoiseMap) -> ErosionMaps {
+        const NEIGHBOURS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+        let (width, height) = noise_map.size();
+
+        let original = self.deposition_map_enabled.then(|| {
+            (0..width * height)
+                .map(|i| noise_map.get_value(i % width, i / width))
+                .collect::<Vec<_>>()
+        });
+
+        let mut changes = vec![0.0; width * height];
+
+        for _ in 0..self.iterations {
+            changes.iter_mut().for_each(|change| *change = 0.0);
+
+            for y in 0..height {
+                for x in 0..width {
+                    let center = noise_map.get_value(x, y);
+
+                    let mut lower = [None; 4];
+                    let mut total_excess = 0.0;
+                    let mut max_excess: f64 = 0.0;
+
+                    for (neighbour, &(dx, dy)) in lower.iter_mut().zip(&NEIGHBOURS) {
+                        let (nx, ny) = (x as isize + dx, y as isize + dy);
+                        if nx < 0 || ny < 0 || nx as usize >= width || ny as usize >= height {
+                            continue;
+                        }
+                        let (nx, ny) = (nx as usize, ny as usize);
+
+                        let excess = center - noise_map.get_value(nx, ny) - self.talus;
+                        if excess > 0.0 {
+                            *neighbour = Some((nx + ny * width, excess));
+                            total_excess += excess;
+                            max_excess = max_excess.max(excess);
+                        }
+                    }
+
+                    if total_excess == 0.0 {
+                        continue;
+                    }
+
+                    // Move half of the largest excess, so the steepest slope
+                    // is at most levelled rather than inverted.
+                    let moved = self.rate * max_excess / 2.0;
+                    changes[x + y * width] -= moved;
+                    for &(index, excess) in lower.iter().flatten() {
+                        changes[index] += moved * excess / total_excess;
+                    }
+                }
+            }
+
+            for (i, change) in changes.iter().enumerate() {
+                let (x, y) = (i % width, i / width);
+                noise_map.set_value(x, y, noise_map.get_value(x, y) + change);
+            }
+        }
+
+        ErosionMaps {
+            deposition: original.map(|original| {
+                let mut deposition = NoiseMap::new(width, height);
+                for (i, original) in original.iter().enumerate() {
+                    let (x, y) = (i % width, i / width);
+                    deposition.set_value(x, y, noise_map.get_value(x, y) - original);
+                }
+                deposition
+            }),
+            flow: None,
+        }
+    }
+}
+
+impl Default for ThermalErosion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the bilinearly interpolated height and gradient of the map at a
+/// position, which must lie within the map.
+fn height_and_gradient(noise_map: &NoiseMap, position: [f64; 2]) -> (f64, [f64; 2]) {
+    let (x, y) = (position[0] as usize, position[1] as usize);
+    let (u, v) = (position[0] - x as f64, position[1] - y as f64);
+
+    let h00 = noise_map.get_value(x, y);
+    let h10 = noise_map.get_value(x + 1, y);
+    let h01 = noise_map.get_value(x, y + 1);
+    let h11 = noise_map.get_value(x + 1, y + 1);
+
+    let height =
+        h00 * (1.0 - u) * (1.0 - v) + h10 * u * (1.0 - v) + h01 * (1.0 - u) * v + h11 * u * v;
+    let gradient = [
+        (h10 - h00) * (1.0 - v) + (h11 - h01) * v,
+        (h01 - h00) * (1.0 - u) + (h11 - h10) * u,
+    ];
+
+    (height, gradient)
+}
+
+/// Spreads `amount` over the four corners of a cell, weighted by their
+/// proximity to the offset within the cell.
+fn add_bilinear(noise_map: &mut NoiseMap, cell: [usize; 2], offset: [f64; 2], amount: f64) {
+    let [x, y] = cell;
+    let [u, v] = offset;
+
+    for &(cx, cy, weight) in &[
+        (x, y, (1.0 - u) * (1.0 - v)),
+        (x + 1, y, u * (1.0 - v)),
+        (x, y + 1, (1.0 - u) * v),
+        (x + 1, y + 1, u * v),
+    ] {
+        noise_map.set_value(cx, cy, noise_map.get_value(cx, cy) + amount * weight);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cone(size: usize) -> NoiseMap {
+        let mut noise_map = NoiseMap::new(size, size);
+        let center = size as f64 / 2.0;
+        for y in 0..size {
+            for x in 0..size {
+                let distance = ((x as f64 - center).powi(2) + (y as f64 - center).powi(2)).sqrt();
+                noise_map.set_value(x, y, 1.0 - distance / center);
+            }
+        }
+        noise_map
+    }
+
+    fn values(noise_map: &NoiseMap) -> Vec<f64> {
+        let (width, height) = noise_map.size();
+        (0..width * height)
+            .map(|i| noise_map.get_value(i % width, i / width))
+            .collect()
+    }
+
+    #[test]
+    fn test_hydraulic_erosion_is_deterministic() {
+        let erosion = HydraulicErosion::new().set_iterations(500).set_seed(7);
+
+        let mut first = cone(32);
+        let mut second = cone(32);
+        let maps = erosion.enable_flow_map().erode(&mut first);
+        erosion.erode(&mut second);
+
+        assert_eq!(values(&first), values(&second));
+        assert_ne!(values(&first), values(&cone(32)));
+        assert!(maps.deposition.is_none());
+        assert!(values(&maps.flow.unwrap()).iter().any(|&flow| flow > 0.0));
+
+        let mut reseeded = cone(32);
+        erosion.set_seed(8).erode(&mut reseeded);
+        assert_ne!(values(&first), values(&reseeded));
+    }
+
+    #[test]
+    fn test_thermal_erosion_conserves_height() {
+        let mut noise_map = NoiseMap::new(9, 9);
+        noise_map.set_value(4, 4, 1.0);
+
+        let maps = ThermalErosion::new()
+            .set_iterations(200)
+            .enable_deposition_map()
+            .erode(&mut noise_map);
+
+        let total: f64 = values(&noise_map).iter().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(noise_map.get_value(4, 4) < 0.5);
+        assert!(maps.deposition.unwrap().get_value(4, 5) > 0.0);
+    }
+}
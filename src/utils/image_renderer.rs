@@ -1,4 +1,4 @@
-use crate::math::interpolate;
+use crate::math::{self, interpolate};
 use std::{self, f64::consts::SQRT_2};
 
 use super::{color_gradient::*, noise_image::*, noise_map::*};
@@ -405,10 +405,10 @@ impl LightSource {
             contrast: 1.0,
             elevation: 45.0,
             intensity: 1.0,
-            azimuth_cosine: 45.0_f64.to_radians().cos(),
-            azimuth_sine: 45.0_f64.to_radians().sin(),
-            elevation_cosine: 45.0_f64.to_radians().cos(),
-            elevation_sine: 45.0_f64.to_radians().sin(),
+            azimuth_cosine: math::cos(45.0_f64.to_radians()),
+            azimuth_sine: math::sin(45.0_f64.to_radians()),
+            elevation_cosine: math::cos(45.0_f64.to_radians()),
+            elevation_sine: math::sin(45.0_f64.to_radians()),
             recalculate_light_values: false,
         }
     }
@@ -457,10 +457,10 @@ impl LightSource {
         // Recalculate the sine and cosine of the various light values if necessary so it does not
         // have to be calculated each time this method is called.
         if self.recalculate_light_values {
-            self.azimuth_cosine = self.azimuth.to_radians().cos();
-            self.azimuth_sine = self.azimuth.to_radians().sin();
-            self.elevation_cosine = self.elevation.to_radians().cos();
-            self.elevation_sine = self.elevation.to_radians().sin();
+            self.azimuth_cosine = math::cos(self.azimuth.to_radians());
+            self.azimuth_sine = math::sin(self.azimuth.to_radians());
+            self.elevation_cosine = math::cos(self.elevation.to_radians());
+            self.elevation_sine = math::sin(self.elevation.to_radians());
 
             self.recalculate_light_values = false;
         }
@@ -1,9 +1,119 @@
-#[cfg(feature = "image")]
-use std::{self, path::Path};
+use std::{error::Error, fmt, fs, io, path::Path};
 
 const RASTER_MAX_WIDTH: u16 = 32_767;
 const RASTER_MAX_HEIGHT: u16 = 32_767;
 
+/// The channel of an image that a `NoiseMap` is loaded from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageChannel {
+    /// The brightness of the image, for grayscale images and color images
+    /// alike.
+    Luma,
+    Red,
+    Green,
+    Blue,
+    Alpha,
+}
+
+/// The sample format of a headerless raw file that a `NoiseMap` is loaded
+/// from.
+///
+/// Integer samples span their full range, and floating-point samples are
+/// expected to lie in the [0, 1] range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RawFormat {
+    /// 16-bit unsigned integers, in little-endian byte order.
+    U16Le,
+
+    /// 16-bit unsigned integers, in big-endian byte order.
+    U16Be,
+
+    /// 32-bit floating-point numbers, in little-endian byte order.
+    F32Le,
+
+    /// 32-bit floating-point numbers, in big-endian byte order.
+    F32Be,
+}
+
+impl RawFormat {
+    fn sample_size(self) -> usize {
+        match self {
+            RawFormat::U16Le | RawFormat::U16Be => 2,
+            RawFormat::F32Le | RawFormat::F32Be => 4,
+        }
+    }
+
+    /// Converts the bytes of one sample to a value in the [0, 1] range.
+    fn sample(self, bytes: &[u8]) -> f64 {
+        match self {
+            RawFormat::U16Le => u16::from_le_bytes([bytes[0], bytes[1]]) as f64 / 65_535.0,
+            RawFormat::U16Be => u16::from_be_bytes([bytes[0], bytes[1]]) as f64 / 65_535.0,
+            RawFormat::F32Le => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            RawFormat::F32Be => f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+        }
+    }
+}
+
+/// An error returned when loading a `NoiseMap` from an image or a raw file
+/// fails.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file could not be read.
+    Io(io::Error),
+
+    /// The image could not be decoded.
+    #[cfg(feature = "image")]
+    Image(image::ImageError),
+
+    /// The raw data does not hold exactly one sample per value of the map.
+    InvalidLength { expected: usize, actual: usize },
+
+    /// The map would be larger than a `NoiseMap` can hold.
+    TooLarge { width: usize, height: usize },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(error) => write!(f, "failed to read noise map: {}", error),
+            #[cfg(feature = "image")]
+            LoadError::Image(error) => write!(f, "failed to decode noise map image: {}", error),
+            LoadError::InvalidLength { expected, actual } => write!(
+                f,
+                "raw noise map data is {} bytes long, expected {}",
+                actual, expected
+            ),
+            LoadError::TooLarge { width, height } => {
+                write!(f, "noise map of {}x{} values is too large", width, height)
+            }
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Io(error) => Some(error),
+            #[cfg(feature = "image")]
+            LoadError::Image(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(error: io::Error) -> Self {
+        LoadError::Io(error)
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for LoadError {
+    fn from(error: image::ImageError) -> Self {
+        LoadError::Image(error)
+    }
+}
+
 pub struct NoiseMap {
     size: (usize, usize),
     border_value: f64,
@@ -80,6 +190,111 @@ impl NoiseMap {
         }
     }
 
+    /// Loads a map from a channel of an image, such as a painted mask or an
+    /// exported height map.
+    ///
+    /// The samples of the channel are remapped linearly so that black, or
+    /// zero, becomes the first value of `range` and white, or full intensity,
+    /// becomes the second. A range of (-1.0, 1.0) reverses the mapping of
+    /// [`write_to_file`](Self::write_to_file). 16-bit images keep their full
+    /// precision.
+    #[cfg(feature = "image")]
+    pub fn from_image(
+        image: &image::DynamicImage,
+        channel: ImageChannel,
+        range: (f64, f64),
+    ) -> Result<Self, LoadError> {
+        use image::GenericImageView;
+
+        let (width, height) = image.dimensions();
+        let (width, height) = (width as usize, height as usize);
+
+        let index = match channel {
+            ImageChannel::Luma | ImageChannel::Red => 0,
+            ImageChannel::Green => 1,
+            ImageChannel::Blue => 2,
+            ImageChannel::Alpha => 3,
+        };
+
+        // Keep 8-bit images at 8 bits, as widening them to 16 bits doesn't
+        // map full intensity onto the full 16-bit range.
+        let color = image.color();
+        let samples: Vec<f64> = if color.bytes_per_pixel() == color.channel_count() {
+            match channel {
+                ImageChannel::Luma => image.to_luma8().into_raw(),
+                _ => image.to_rgba8().pixels().map(|p| p.0[index]).collect(),
+            }
+            .into_iter()
+            .map(|s| s as f64 / 255.0)
+            .collect()
+        } else {
+            match channel {
+                ImageChannel::Luma => image.to_luma16().into_raw(),
+                _ => image.to_rgba16().pixels().map(|p| p.0[index]).collect(),
+            }
+            .into_iter()
+            .map(|s| s as f64 / 65_535.0)
+            .collect()
+        };
+
+        Self::from_samples(width, height, samples.into_iter(), range)
+    }
+
+    /// Loads a map from a channel of the image file at the given path. See
+    /// [`from_image`](Self::from_image).
+    #[cfg(feature = "image")]
+    pub fn from_image_file<P: AsRef<Path>>(
+        path: P,
+        channel: ImageChannel,
+        range: (f64, f64),
+    ) -> Result<Self, LoadError> {
+        Self::from_image(&image::open(path)?, channel, range)
+    }
+
+    /// Loads a map from headerless raw data of the given size, with the
+    /// samples in row-major order.
+    ///
+    /// The samples are remapped linearly so that 0.0 becomes the first value
+    /// of `range` and 1.0 becomes the second.
+    pub fn from_raw(
+        data: &[u8],
+        width: usize,
+        height: usize,
+        format: RawFormat,
+        range: (f64, f64),
+    ) -> Result<Self, LoadError> {
+        // Check the size first, so the expected length cannot overflow.
+        Self::check_size(width, height)?;
+
+        let expected = width * height * format.sample_size();
+        if data.len() != expected {
+            return Err(LoadError::InvalidLength {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        Self::from_samples(
+            width,
+            height,
+            data.chunks_exact(format.sample_size())
+                .map(|bytes| format.sample(bytes)),
+            range,
+        )
+    }
+
+    /// Loads a map from the headerless raw file at the given path. See
+    /// [`from_raw`](Self::from_raw).
+    pub fn from_raw_file<P: AsRef<Path>>(
+        path: P,
+        width: usize,
+        height: usize,
+        format: RawFormat,
+        range: (f64, f64),
+    ) -> Result<Self, LoadError> {
+        Self::from_raw(&fs::read(path)?, width, height, format, range)
+    }
+
     #[cfg(feature = "image")]
     pub fn write_to_file(&self, filename: &str) {
         // Create the output directory for the images, if it doesn't already exist
@@ -112,6 +327,33 @@ impl NoiseMap {
         println!("\nFinished generating {}", filename);
     }
 
+    /// Builds a map from samples in the [0, 1] range, remapping them onto
+    /// `range`.
+    fn from_samples(
+        width: usize,
+        height: usize,
+        samples: impl Iterator<Item = f64>,
+        range: (f64, f64),
+    ) -> Result<Self, LoadError> {
+        Self::check_size(width, height)?;
+
+        let mut result = Self::new(width, height);
+        for (value, sample) in result.map.iter_mut().zip(samples) {
+            *value = range.0 + sample * (range.1 - range.0);
+        }
+
+        Ok(result)
+    }
+
+    /// Checks that a map of the given size can be created.
+    fn check_size(width: usize, height: usize) -> Result<(), LoadError> {
+        if width >= RASTER_MAX_WIDTH as usize || height >= RASTER_MAX_HEIGHT as usize {
+            return Err(LoadError::TooLarge { width, height });
+        }
+
+        Ok(())
+    }
+
     fn initialize() -> Self {
         Self {
             size: (0, 0),
@@ -126,3 +368,61 @@ impl Default for NoiseMap {
         Self::initialize()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_raw() {
+        let data = [0x00, 0x00, 0xff, 0xff, 0x80, 0x00];
+        let map = NoiseMap::from_raw(&data, 3, 1, RawFormat::U16Be, (-1.0, 1.0)).unwrap();
+        assert_eq!(-1.0, map.get_value(0, 0));
+        assert_eq!(1.0, map.get_value(1, 0));
+        assert!(map.get_value(2, 0).abs() < 1e-4);
+
+        let data: Vec<u8> = [0.25f32, 0.5]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let map = NoiseMap::from_raw(&data, 1, 2, RawFormat::F32Le, (0.0, 2.0)).unwrap();
+        assert_eq!(0.5, map.get_value(0, 0));
+        assert_eq!(1.0, map.get_value(0, 1));
+
+        assert!(matches!(
+            NoiseMap::from_raw(&data, 2, 2, RawFormat::F32Le, (0.0, 1.0)),
+            Err(LoadError::InvalidLength {
+                expected: 16,
+                actual: 8
+            })
+        ));
+
+        assert!(matches!(
+            NoiseMap::from_raw(&data, usize::MAX, usize::MAX, RawFormat::F32Le, (0.0, 1.0)),
+            Err(LoadError::TooLarge { .. })
+        ));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_from_image() {
+        let image = image::DynamicImage::ImageRgba8(image::RgbaImage::from_fn(2, 1, |x, _| {
+            image::Rgba([255, 0, 51 * x as u8, 0])
+        }));
+
+        let map = NoiseMap::from_image(&image, ImageChannel::Red, (-1.0, 1.0)).unwrap();
+        assert_eq!(1.0, map.get_value(1, 0));
+
+        let map = NoiseMap::from_image(&image, ImageChannel::Blue, (0.0, 1.0)).unwrap();
+        assert_eq!(0.0, map.get_value(0, 0));
+        assert!((map.get_value(1, 0) - 0.2).abs() < 1e-12);
+
+        let gray = image::DynamicImage::ImageLuma16(image::ImageBuffer::from_pixel(
+            1,
+            1,
+            image::Luma([1u16]),
+        ));
+        let map = NoiseMap::from_image(&gray, ImageChannel::Luma, (0.0, 65_535.0)).unwrap();
+        assert!((map.get_value(0, 0) - 1.0).abs() < 1e-9);
+    }
+}
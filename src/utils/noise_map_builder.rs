@@ -1,4 +1,8 @@
-use crate::{math::interpolate, noise_fns::NoiseFn, utils::noise_map::NoiseMap};
+use crate::{
+    math::{self, interpolate},
+    noise_fns::NoiseFn,
+    utils::noise_map::NoiseMap,
+};
 
 pub trait NoiseMapBuilder<'a> {
     fn set_size(self, width: usize, height: usize) -> Self;
@@ -100,16 +104,21 @@ impl<'a> NoiseMapBuilder<'a> for CylinderMapBuilder<'a> {
         let x_step = angle_extent / width as f64;
         let y_step = height_extent / height as f64;
 
+        // The spacing between neighbouring samples on the unit cylinder.
+        let footprint = x_step.to_radians().abs().max(y_step.abs());
+
         for y in 0..height {
             let current_height = self.height_bounds.0 + y_step * y as f64;
 
             for x in 0..width {
                 let current_angle = self.angle_bounds.0 + x_step * x as f64;
 
-                let point_x = current_angle.to_radians().cos();
-                let point_z = current_angle.to_radians().sin();
+                let point_x = math::cos(current_angle.to_radians());
+                let point_z = math::sin(current_angle.to_radians());
 
-                let value = self.source_module.get([point_x, current_height, point_z]);
+                let value = self
+                    .source_module
+                    .get_with_footprint([point_x, current_height, point_z], footprint);
 
                 println!(
                     "calculated value {} at {}, {}, {}",
@@ -203,6 +212,8 @@ impl<'a> NoiseMapBuilder<'a> for PlaneMapBuilder<'a> {
         let x_step = x_extent / width as f64;
         let y_step = y_extent / height as f64;
 
+        let footprint = x_step.abs().max(y_step.abs());
+
         for y in 0..height {
             let current_y = self.y_bounds.0 + y_step * y as f64;
 
@@ -210,16 +221,19 @@ impl<'a> NoiseMapBuilder<'a> for PlaneMapBuilder<'a> {
                 let current_x = self.x_bounds.0 + x_step * x as f64;
 
                 let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y, 0.0]);
+                    let sw_value = self
+                        .source_module
+                        .get_with_footprint([current_x, current_y, 0.0], footprint);
                     let se_value = self
                         .source_module
-                        .get([current_x + x_extent, current_y, 0.0]);
+                        .get_with_footprint([current_x + x_extent, current_y, 0.0], footprint);
                     let nw_value = self
                         .source_module
-                        .get([current_x, current_y + y_extent, 0.0]);
-                    let ne_value =
-                        self.source_module
-                            .get([current_x + x_extent, current_y + y_extent, 0.0]);
+                        .get_with_footprint([current_x, current_y + y_extent, 0.0], footprint);
+                    let ne_value = self.source_module.get_with_footprint(
+                        [current_x + x_extent, current_y + y_extent, 0.0],
+                        footprint,
+                    );
 
                     let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
                     let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
@@ -229,7 +243,8 @@ impl<'a> NoiseMapBuilder<'a> for PlaneMapBuilder<'a> {
 
                     interpolate::linear(y0, y1, y_blend)
                 } else {
-                    self.source_module.get([current_x, current_y, 0.0])
+                    self.source_module
+                        .get_with_footprint([current_x, current_y, 0.0], footprint)
                 };
 
                 result_map.set_value(x, y, final_value);
@@ -324,6 +339,10 @@ impl<'a> NoiseMapBuilder<'a> for SphereMapBuilder<'a> {
         let x_step = lon_extent / width as f64;
         let y_step = lat_extent / height as f64;
 
+        // The spacing between neighbouring samples on the unit sphere, taken
+        // at the equator where the longitude step is widest.
+        let footprint = x_step.to_radians().abs().max(y_step.to_radians().abs());
+
         for y in 0..height {
             let current_lat = self.latitude_bounds.0 + y_step * y as f64;
 
@@ -332,7 +351,9 @@ impl<'a> NoiseMapBuilder<'a> for SphereMapBuilder<'a> {
 
                 let point = lat_lon_to_xyz(current_lat, current_lon);
 
-                result_map.set_value(x, y, self.source_module.get(point));
+                let value = self.source_module.get_with_footprint(point, footprint);
+
+                result_map.set_value(x, y, value);
             }
         }
 
@@ -341,10 +362,70 @@ impl<'a> NoiseMapBuilder<'a> for SphereMapBuilder<'a> {
 }
 
 fn lat_lon_to_xyz(lat: f64, lon: f64) -> [f64; 3] {
-    let r = lat.to_radians().cos();
-    let x = r * lon.to_radians().cos();
-    let y = lat.to_radians().sin();
-    let z = r * lon.to_radians().sin();
+    let r = math::cos(lat.to_radians());
+    let x = r * math::cos(lon.to_radians());
+    let y = math::sin(lat.to_radians());
+    let z = r * math::sin(lon.to_radians());
 
     [x, y, z]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fbm, Seedable};
+
+    fn assert_forwards_step(
+        source: &Fbm,
+        map: &NoiseMap,
+        (x, y): (usize, usize),
+        point: [f64; 3],
+        step: f64,
+    ) {
+        let value = map.get_value(x, y);
+
+        assert_eq!(value, source.get_with_footprint(point, step));
+        assert_ne!(value, source.get(point));
+    }
+
+    #[test]
+    fn test_plane_forwards_step() {
+        let source = Fbm::new().set_seed(42);
+        let map = PlaneMapBuilder::new(&source)
+            .set_size(16, 16)
+            .set_x_bounds(0.0, 8.0)
+            .set_y_bounds(0.0, 4.0)
+            .build();
+
+        assert_forwards_step(&source, &map, (3, 5), [1.5, 1.25, 0.0], 0.5);
+    }
+
+    #[test]
+    fn test_cylinder_forwards_step() {
+        let source = Fbm::new().set_seed(42);
+        let map = CylinderMapBuilder::new(&source)
+            .set_size(16, 16)
+            .set_angle_bounds(0.0, 90.0)
+            .set_height_bounds(0.0, 2.0)
+            .build();
+
+        let angle = 22.5_f64.to_radians();
+        let point = [math::cos(angle), 0.375, math::sin(angle)];
+
+        assert_forwards_step(&source, &map, (4, 3), point, 0.125);
+    }
+
+    #[test]
+    fn test_sphere_forwards_step() {
+        let source = Fbm::new().set_seed(42);
+        let map = SphereMapBuilder::new(&source)
+            .set_size(32, 16)
+            .set_latitude_bounds(-90.0, 90.0)
+            .set_longitude_bounds(-180.0, 180.0)
+            .build();
+
+        let point = lat_lon_to_xyz(-56.25, -123.75);
+
+        assert_forwards_step(&source, &map, (5, 3), point, 11.25_f64.to_radians());
+    }
+}
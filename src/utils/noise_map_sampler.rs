@@ -0,0 +1,242 @@
+use crate::{math::interpolate, noise_fns::NoiseFn, utils::noise_map::NoiseMap};
+
+/// How a [`NoiseMapSampler`] blends the values around a sample point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SampleFilter {
+    /// Takes the value nearest to the sample point, giving a blocky result.
+    Nearest,
+
+    /// Blends linearly between the four values around the sample point.
+    Bilinear,
+
+    /// Blends the sixteen values around the sample point with Catmull-Rom
+    /// splines, giving a smooth result that can overshoot the values
+    /// themselves.
+    Bicubic,
+}
+
+// `#[default]` on enum variants requires a newer compiler than we support.
+#[allow(clippy::derivable_impls)]
+impl Default for SampleFilter {
+    fn default() -> Self {
+        SampleFilter::Bilinear
+    }
+}
+
+/// What a [`NoiseMapSampler`] takes for the values past the edges of the map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeMode {
+    /// Repeats the values along the edges.
+    Clamp,
+
+    /// Tiles the map, continuing past each edge from the opposite edge.
+    Wrap,
+
+    /// Uses the border value of the map.
+    Border,
+}
+
+// `#[default]` on enum variants requires a newer compiler than we support.
+#[allow(clippy::derivable_impls)]
+impl Default for EdgeMode {
+    fn default() -> Self {
+        EdgeMode::Clamp
+    }
+}
+
+/// Noise function that outputs the values of a `NoiseMap`, such as a loaded
+/// or post-processed height map, so it can be fed back into a noise function
+/// graph.
+///
+/// The map covers the given bounds with the same layout as a
+/// [`PlaneMapBuilder`](super::PlaneMapBuilder) with those bounds, so sampling
+/// a map at the points it was built from returns its values unchanged. The
+/// _z_ coordinate of 3-dimensional input is ignored.
+pub struct NoiseMapSampler {
+    map: NoiseMap,
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    filter: SampleFilter,
+    edge_mode: EdgeMode,
+}
+
+impl NoiseMapSampler {
+    pub fn new(map: NoiseMap) -> Self {
+        Self {
+            map,
+            x_bounds: (-1.0, 1.0),
+            y_bounds: (-1.0, 1.0),
+            filter: SampleFilter::default(),
+            edge_mode: EdgeMode::default(),
+        }
+    }
+
+    pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
+        Self {
+            x_bounds: (lower_x_bound, upper_x_bound),
+            ..self
+        }
+    }
+
+    pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
+        Self {
+            y_bounds: (lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_bounds
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_bounds
+    }
+
+    pub fn set_filter(self, filter: SampleFilter) -> Self {
+        Self { filter, ..self }
+    }
+
+    pub fn filter(&self) -> SampleFilter {
+        self.filter
+    }
+
+    pub fn set_edge_mode(self, edge_mode: EdgeMode) -> Self {
+        Self { edge_mode, ..self }
+    }
+
+    pub fn edge_mode(&self) -> EdgeMode {
+        self.edge_mode
+    }
+
+    pub fn map(&self) -> &NoiseMap {
+        &self.map
+    }
+
+    /// Returns the map, consuming the sampler.
+    pub fn into_map(self) -> NoiseMap {
+        self.map
+    }
+
+    /// Returns the value at the given position in the map, applying the edge
+    /// mode to positions past its edges.
+    fn value(&self, x: isize, y: isize) -> f64 {
+        let (width, height) = self.map.size();
+        let (width, height) = (width as isize, height as isize);
+
+        match self.edge_mode {
+            EdgeMode::Clamp => self.map.get_value(
+                x.clamp(0, width - 1) as usize,
+                y.clamp(0, height - 1) as usize,
+            ),
+            EdgeMode::Wrap => self
+                .map
+                .get_value(x.rem_euclid(width) as usize, y.rem_euclid(height) as usize),
+            EdgeMode::Border => {
+                if x < 0 || y < 0 {
+                    self.map.border_value()
+                } else {
+                    self.map.get_value(x as usize, y as usize)
+                }
+            }
+        }
+    }
+}
+
+impl NoiseFn<f64, 2> for NoiseMapSampler {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let (width, height) = self.map.size();
+        if width == 0 || height == 0 {
+            return self.map.border_value();
+        }
+
+        // Find the position of the point in the map, in units of its values.
+        let u = (point[0] - self.x_bounds.0) / (self.x_bounds.1 - self.x_bounds.0) * width as f64;
+        let v = (point[1] - self.y_bounds.0) / (self.y_bounds.1 - self.y_bounds.0) * height as f64;
+
+        match self.filter {
+            SampleFilter::Nearest => self.value(u.round() as isize, v.round() as isize),
+            SampleFilter::Bilinear => {
+                let (x, y) = (u.floor(), v.floor());
+                let (x_frac, y_frac) = (u - x, v - y);
+                let (x, y) = (x as isize, y as isize);
+
+                let y0 = interpolate::linear(self.value(x, y), self.value(x + 1, y), x_frac);
+                let y1 =
+                    interpolate::linear(self.value(x, y + 1), self.value(x + 1, y + 1), x_frac);
+
+                interpolate::linear(y0, y1, y_frac)
+            }
+            SampleFilter::Bicubic => {
+                let (x, y) = (u.floor(), v.floor());
+                let (x_frac, y_frac) = (u - x, v - y);
+                let (x, y) = (x as isize, y as isize);
+
+                let row = |y| {
+                    interpolate::catmull_rom(
+                        self.value(x - 1, y),
+                        self.value(x, y),
+                        self.value(x + 1, y),
+                        self.value(x + 2, y),
+                        x_frac,
+                    )
+                };
+
+                interpolate::catmull_rom(row(y - 1), row(y), row(y + 1), row(y + 2), y_frac)
+            }
+        }
+    }
+}
+
+impl NoiseFn<f64, 3> for NoiseMapSampler {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get([point[0], point[1]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp() -> NoiseMap {
+        let mut map = NoiseMap::new(4, 2).set_border_value(-1.0);
+        for y in 0..2 {
+            for x in 0..4 {
+                map.set_value(x, y, x as f64 * 0.25 + y as f64);
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn test_filters() {
+        // The values lie at x = -1.0, -0.5, 0.0 and 0.5, and y = -1.0 and 0.0.
+        let sampler = NoiseMapSampler::new(ramp());
+        assert_eq!(0.25, sampler.get([-0.5, -1.0]));
+        assert_eq!(0.375, sampler.get([-0.25, -1.0]));
+        assert_eq!(0.875, sampler.get([-0.25, -0.5]));
+
+        let nearest = sampler.set_filter(SampleFilter::Nearest);
+        assert_eq!(0.5, nearest.get([-0.2, -0.9]));
+
+        // Catmull-Rom splines reproduce a linear ramp between the values.
+        let bicubic = nearest.set_filter(SampleFilter::Bicubic);
+        assert!((bicubic.get([-0.25, -1.0]) - 0.375).abs() < 1e-12);
+        assert!((bicubic.get([-0.375, -1.0]) - 0.3125).abs() < 1e-12);
+        assert!((bicubic.get([-0.125, -1.0]) - 0.4375).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_edge_modes() {
+        let sampler = NoiseMapSampler::new(ramp()).set_filter(SampleFilter::Nearest);
+        assert_eq!(0.75, sampler.get([3.0, -1.0]));
+
+        let wrapped = sampler.set_edge_mode(EdgeMode::Wrap);
+        assert_eq!(wrapped.get([-0.5, -1.0]), wrapped.get([1.5, -1.0]));
+        assert_eq!(1.25, wrapped.get([-0.5, 2.0]));
+
+        let bordered = wrapped.set_edge_mode(EdgeMode::Border);
+        assert_eq!(-1.0, bordered.get([3.0, -1.0]));
+        assert_eq!(-1.0, bordered.get([-2.0, -1.0]));
+    }
+}
@@ -0,0 +1,441 @@
+use rand::{Rng, SeedableRng};
+use rand_xorshift::XorShiftRng;
+
+use crate::{
+    math,
+    noise_fns::{generators::worley::feature_point, Constant, NoiseFn},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+
+/// Scatters points over a rectangle by Poisson-disk sampling, so that no two
+/// points are closer than a minimum distance, yet no gap is left where
+/// another point would fit.
+///
+/// The points are placed with Bridson's algorithm, which grows the set
+/// outwards from a random first point by trying candidates around the points
+/// placed so far. The result is deterministic for a given seed.
+///
+/// The distance between the points can vary across the rectangle with a
+/// density function. Where it outputs 1.0 the points are the minimum distance
+/// apart, and where it outputs -1.0 they are the maximum distance apart.
+#[derive(Clone, Debug)]
+pub struct PoissonDiskSampler<D = Constant> {
+    seed: u64,
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    min_distance: f64,
+    max_distance: f64,
+    attempts: usize,
+    density: D,
+}
+
+impl PoissonDiskSampler {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_MIN_DISTANCE: f64 = 0.1;
+    pub const DEFAULT_ATTEMPTS: usize = 30;
+
+    pub fn new() -> Self {
+        Self {
+            seed: Self::DEFAULT_SEED,
+            x_bounds: (-1.0, 1.0),
+            y_bounds: (-1.0, 1.0),
+            min_distance: Self::DEFAULT_MIN_DISTANCE,
+            max_distance: Self::DEFAULT_MIN_DISTANCE,
+            attempts: Self::DEFAULT_ATTEMPTS,
+            density: Constant::new(1.0),
+        }
+    }
+}
+
+impl<D> PoissonDiskSampler<D> {
+    pub fn set_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
+        Self {
+            x_bounds: (lower_x_bound, upper_x_bound),
+            ..self
+        }
+    }
+
+    pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
+        Self {
+            y_bounds: (lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_bounds
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_bounds
+    }
+
+    /// Sets the distance that no two points are closer than. Without a
+    /// density function, this is the distance between all the points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_distance` is not positive.
+    pub fn set_min_distance(self, min_distance: f64) -> Self {
+        assert!(min_distance > 0.0, "the minimum distance must be positive");
+
+        Self {
+            min_distance,
+            max_distance: self.max_distance.max(min_distance),
+            ..self
+        }
+    }
+
+    pub fn min_distance(&self) -> f64 {
+        self.min_distance
+    }
+
+    /// Sets the number of candidates tried around each point before no more
+    /// points are placed around it. More attempts pack the points slightly
+    /// more tightly, at the cost of sampling time.
+    pub fn set_attempts(self, attempts: usize) -> Self {
+        Self {
+            attempts: attempts.max(1),
+            ..self
+        }
+    }
+
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Sets the function that varies the distance between the points, from
+    /// the minimum distance where it outputs 1.0 to `max_distance` where it
+    /// outputs -1.0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_distance` is less than the minimum distance.
+    pub fn set_density<E>(self, density: E, max_distance: f64) -> PoissonDiskSampler<E> {
+        assert!(
+            max_distance >= self.min_distance,
+            "the maximum distance must not be less than the minimum distance"
+        );
+
+        PoissonDiskSampler {
+            seed: self.seed,
+            x_bounds: self.x_bounds,
+            y_bounds: self.y_bounds,
+            min_distance: self.min_distance,
+            max_distance,
+            attempts: self.attempts,
+            density,
+        }
+    }
+
+    pub fn density(&self) -> &D {
+        &self.density
+    }
+
+    pub fn max_distance(&self) -> f64 {
+        self.max_distance
+    }
+}
+
+impl<D: NoiseFn<f64, 2>> PoissonDiskSampler<D> {
+    /// Returns the distance that must be kept around a point at the given
+    /// position.
+    fn distance_at(&self, point: [f64; 2]) -> f64 {
+        let density = ((self.density.get(point) + 1.0) * 0.5).clamp(0.0, 1.0);
+        self.max_distance + (self.min_distance - self.max_distance) * density
+    }
+
+    /// Generates the points. The same settings always produce the same
+    /// points, in the same order.
+    pub fn sample(&self) -> Vec<[f64; 2]> {
+        let (x0, x1) = self.x_bounds;
+        let (y0, y1) = self.y_bounds;
+        if !(x1 > x0 && y1 > y0) {
+            return Vec::new();
+        }
+
+        // Each cell of the grid is small enough to hold at most one point,
+        // and a point can only conflict with those within `reach` cells.
+        let cell_size = self.min_distance * std::f64::consts::FRAC_1_SQRT_2;
+        let columns = ((x1 - x0) / cell_size).ceil() as usize;
+        let rows = ((y1 - y0) / cell_size).ceil() as usize;
+        let reach = (self.max_distance / cell_size).ceil() as isize;
+        let mut grid = vec![None; columns * rows];
+
+        let cell_of = |point: [f64; 2]| {
+            let column = (((point[0] - x0) / cell_size) as usize).min(columns - 1);
+            let row = (((point[1] - y0) / cell_size) as usize).min(rows - 1);
+            (column, row)
+        };
+
+        let mut rng = XorShiftRng::seed_from_u64(self.seed);
+        let mut points: Vec<([f64; 2], f64)> = Vec::new();
+        let mut active = Vec::new();
+
+        let first = [
+            x0 + rng.gen::<f64>() * (x1 - x0),
+            y0 + rng.gen::<f64>() * (y1 - y0),
+        ];
+        let (column, row) = cell_of(first);
+        grid[row * columns + column] = Some(0);
+        points.push((first, self.distance_at(first)));
+        active.push(0);
+
+        while !active.is_empty() {
+            let index = rng.gen_range(0, active.len());
+            let (center, distance) = points[active[index]];
+
+            let mut placed = false;
+            for _ in 0..self.attempts {
+                // Try a candidate in the ring between one and two distances
+                // around the point.
+                let angle = rng.gen::<f64>() * std::f64::consts::PI * 2.0;
+                let radius = distance * (1.0 + rng.gen::<f64>());
+                let candidate = [
+                    center[0] + radius * math::cos(angle),
+                    center[1] + radius * math::sin(angle),
+                ];
+
+                if candidate[0] < x0
+                    || candidate[0] >= x1
+                    || candidate[1] < y0
+                    || candidate[1] >= y1
+                {
+                    continue;
+                }
+
+                let candidate_distance = self.distance_at(candidate);
+                let (column, row) = cell_of(candidate);
+
+                let mut fits = true;
+                'search: for r in (row as isize - reach).max(0)..=(row as isize + reach) {
+                    if r >= rows as isize {
+                        break;
+                    }
+                    for c in (column as isize - reach).max(0)..=(column as isize + reach) {
+                        if c >= columns as isize {
+                            break;
+                        }
+                        if let Some(other) = grid[r as usize * columns + c as usize] {
+                            let (point, point_distance) = points[other];
+                            let dx = point[0] - candidate[0];
+                            let dy = point[1] - candidate[1];
+                            let spacing = candidate_distance.max(point_distance);
+                            if dx * dx + dy * dy < spacing * spacing {
+                                fits = false;
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+
+                if fits {
+                    grid[row * columns + column] = Some(points.len());
+                    active.push(points.len());
+                    points.push((candidate, candidate_distance));
+                    placed = true;
+                    break;
+                }
+            }
+
+            // Retire the point once no candidate fits around it.
+            if !placed {
+                active.swap_remove(index);
+            }
+        }
+
+        points.into_iter().map(|(point, _)| point).collect()
+    }
+}
+
+impl Default for PoissonDiskSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Scatters points over a rectangle by jittering a grid, placing one point in
+/// each cell.
+///
+/// The points are the seed points of [`Worley`](crate::Worley) noise with the
+/// same seed and frequency, and each depends only on the seed and its cell.
+/// Sampling adjacent rectangles, such as the chunks of a streamed world,
+/// therefore gives the same points as sampling them together, with no seams
+/// along their shared edges.
+///
+/// The points are not as evenly spaced as those of a [`PoissonDiskSampler`],
+/// but are much faster to generate.
+#[derive(Clone, Debug)]
+pub struct JitteredGridSampler<H = PermutationTable> {
+    seed: u64,
+    frequency: f64,
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    hasher: H,
+}
+
+impl JitteredGridSampler {
+    pub const DEFAULT_SEED: u64 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+
+    pub fn new() -> Self {
+        Self::with_hasher(Self::DEFAULT_SEED)
+    }
+}
+
+impl<H: NoiseHasher> JitteredGridSampler<H> {
+    /// Creates a new sampler with the given seed, using `H` to hash the cells
+    /// of the grid. The points match those of a [`Worley`](crate::Worley)
+    /// noise function using the same hasher.
+    pub fn with_hasher(seed: u64) -> Self {
+        Self {
+            seed,
+            frequency: JitteredGridSampler::DEFAULT_FREQUENCY,
+            x_bounds: (-1.0, 1.0),
+            y_bounds: (-1.0, 1.0),
+            hasher: H::from_seed(seed),
+        }
+    }
+
+    pub fn set_seed(self, seed: u64) -> Self {
+        Self {
+            seed,
+            hasher: H::from_seed(seed),
+            ..self
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Sets the number of grid cells per unit length, and so the average
+    /// number of points per unit area is its square.
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
+        Self {
+            x_bounds: (lower_x_bound, upper_x_bound),
+            ..self
+        }
+    }
+
+    pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
+        Self {
+            y_bounds: (lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_bounds
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_bounds
+    }
+
+    /// Generates the points within the bounds, including those on the lower
+    /// bounds and excluding those on the upper bounds, row by row.
+    pub fn sample(&self) -> Vec<[f64; 2]> {
+        let (x0, x1) = self.x_bounds;
+        let (y0, y1) = self.y_bounds;
+
+        // The seed point of each cell lies within half a cell of its lattice
+        // point.
+        let first = [
+            (x0 * self.frequency - 0.5).floor() as isize,
+            (y0 * self.frequency - 0.5).floor() as isize,
+        ];
+        let last = [
+            (x1 * self.frequency + 0.5).ceil() as isize,
+            (y1 * self.frequency + 0.5).ceil() as isize,
+        ];
+
+        let mut points = Vec::new();
+        for y in first[1]..=last[1] {
+            for x in first[0]..=last[0] {
+                let point = math::mul2(feature_point(&self.hasher, [x, y]).0, 1.0 / self.frequency);
+
+                if point[0] >= x0 && point[0] < x1 && point[1] >= y0 && point[1] < y1 {
+                    points.push(point);
+                }
+            }
+        }
+
+        points
+    }
+}
+
+impl Default for JitteredGridSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{noise_fn, NoiseFn, ReturnType, Worley};
+
+    #[test]
+    fn test_poisson_disk_spacing() {
+        let density = noise_fn(|[x, _]: [f64; 2]| x);
+        let sampler = PoissonDiskSampler::new()
+            .set_seed(3)
+            .set_min_distance(0.05)
+            .set_density(density, 0.2);
+
+        let points = sampler.sample();
+        assert_eq!(points, sampler.sample());
+
+        for (i, a) in points.iter().enumerate() {
+            assert!(a[0] >= -1.0 && a[0] < 1.0 && a[1] >= -1.0 && a[1] < 1.0);
+
+            for b in &points[i + 1..] {
+                let spacing = sampler.distance_at(*a).max(sampler.distance_at(*b));
+                let (dx, dy) = (a[0] - b[0], a[1] - b[1]);
+                assert!((dx * dx + dy * dy).sqrt() >= spacing);
+            }
+        }
+
+        // The points are denser where the density function is higher.
+        let right = points.iter().filter(|p| p[0] > 0.0).count();
+        assert!(right > 2 * (points.len() - right));
+    }
+
+    #[test]
+    fn test_jittered_grid_chunks() {
+        let sampler = JitteredGridSampler::new().set_seed(9).set_frequency(4.0);
+        let whole = sampler.clone().set_x_bounds(-1.0, 1.0).sample();
+        let mut chunks = sampler.clone().set_x_bounds(-1.0, 0.0).sample();
+        chunks.extend(sampler.set_x_bounds(0.0, 1.0).sample());
+
+        assert_eq!(whole.len(), chunks.len());
+        for point in &whole {
+            assert!(chunks.contains(point));
+        }
+
+        // Each point is a seed point of the matching Worley noise.
+        let worley = Worley::new(9)
+            .set_frequency(4.0)
+            .set_return_type(ReturnType::Distance);
+        for &point in &whole {
+            assert!(worley.get(point) < -1.0 + 1e-9);
+        }
+    }
+}
@@ -0,0 +1,494 @@
+use std::f64::consts::PI;
+
+use super::{color_gradient::Color, noise_image::*, noise_map::*};
+use crate::math;
+
+/// Renders a tangent-space normal map from a `NoiseMap` treated as a height
+/// field.
+///
+/// The x, y and z components of each normal are stored in the red, green and
+/// blue channels, remapped from [-1, 1] to [0, 255]. By default the green
+/// channel points up the image, following the OpenGL convention; use
+/// `enable_invert_y` for the DirectX convention.
+#[derive(Clone, Copy, Debug)]
+pub struct NormalMapRenderer {
+    height_scale: f64,
+    invert_y: bool,
+    wrap_enabled: bool,
+}
+
+impl NormalMapRenderer {
+    pub const DEFAULT_HEIGHT_SCALE: f64 = 1.0;
+
+    pub fn new() -> Self {
+        Self {
+            height_scale: Self::DEFAULT_HEIGHT_SCALE,
+            invert_y: false,
+            wrap_enabled: false,
+        }
+    }
+
+    /// Sets the height of a value of 1.0 in the noise map, measured in
+    /// pixels.
+    pub fn set_height_scale(self, height_scale: f64) -> Self {
+        Self {
+            height_scale,
+            ..self
+        }
+    }
+
+    pub fn height_scale(&self) -> f64 {
+        self.height_scale
+    }
+
+    /// Flips the green channel, so that it points down the image.
+    pub fn enable_invert_y(self) -> Self {
+        Self {
+            invert_y: true,
+            ..self
+        }
+    }
+
+    pub fn invert_y(&self) -> bool {
+        self.invert_y
+    }
+
+    pub fn enable_wrap(self) -> Self {
+        Self {
+            wrap_enabled: true,
+            ..self
+        }
+    }
+
+    pub fn wrap_enabled(&self) -> bool {
+        self.wrap_enabled
+    }
+
+    pub fn render(&self, noise_map: &NoiseMap) -> NoiseImage {
+        let heights = HeightField::new(noise_map, self.height_scale, self.wrap_enabled);
+        let (width, height) = noise_map.size();
+
+        let mut destination_image = NoiseImage::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let (dx, dy) = heights.gradient(x as isize, y as isize);
+
+                // The rows of the image run downwards, so the y axis of the
+                // surface is flipped unless the DirectX convention is used.
+                let dy = if self.invert_y { dy } else { -dy };
+
+                let length = (dx * dx + dy * dy + 1.0).sqrt();
+                let normal = [-dx / length, -dy / length, 1.0 / length];
+
+                destination_image.set_value(
+                    x,
+                    y,
+                    [
+                        unit_to_u8(normal[0] * 0.5 + 0.5),
+                        unit_to_u8(normal[1] * 0.5 + 0.5),
+                        unit_to_u8(normal[2] * 0.5 + 0.5),
+                        255,
+                    ],
+                );
+            }
+        }
+
+        destination_image
+    }
+}
+
+impl Default for NormalMapRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renders the slope of a `NoiseMap` treated as a height field, as a greyscale
+/// image.
+///
+/// Flat areas are black, and vertical cliffs are white. The brightness is
+/// proportional to the angle of the slope.
+#[derive(Clone, Copy, Debug)]
+pub struct SlopeRenderer {
+    height_scale: f64,
+    wrap_enabled: bool,
+}
+
+impl SlopeRenderer {
+    pub const DEFAULT_HEIGHT_SCALE: f64 = 1.0;
+
+    pub fn new() -> Self {
+        Self {
+            height_scale: Self::DEFAULT_HEIGHT_SCALE,
+            wrap_enabled: false,
+        }
+    }
+
+    /// Sets the height of a value of 1.0 in the noise map, measured in
+    /// pixels.
+    pub fn set_height_scale(self, height_scale: f64) -> Self {
+        Self {
+            height_scale,
+            ..self
+        }
+    }
+
+    pub fn height_scale(&self) -> f64 {
+        self.height_scale
+    }
+
+    pub fn enable_wrap(self) -> Self {
+        Self {
+            wrap_enabled: true,
+            ..self
+        }
+    }
+
+    pub fn wrap_enabled(&self) -> bool {
+        self.wrap_enabled
+    }
+
+    pub fn render(&self, noise_map: &NoiseMap) -> NoiseImage {
+        let heights = HeightField::new(noise_map, self.height_scale, self.wrap_enabled);
+        let (width, height) = noise_map.size();
+
+        let mut destination_image = NoiseImage::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let (dx, dy) = heights.gradient(x as isize, y as isize);
+                let angle = math::atan((dx * dx + dy * dy).sqrt());
+
+                destination_image.set_value(x, y, grey(angle / (PI / 2.0)));
+            }
+        }
+
+        destination_image
+    }
+}
+
+impl Default for SlopeRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renders the curvature of a `NoiseMap` treated as a height field, as a
+/// greyscale image.
+///
+/// Flat and evenly sloped areas are mid-grey, convex areas such as ridges and
+/// peaks are lighter, and concave areas such as valleys and pits are darker.
+#[derive(Clone, Copy, Debug)]
+pub struct CurvatureRenderer {
+    height_scale: f64,
+    wrap_enabled: bool,
+}
+
+impl CurvatureRenderer {
+    pub const DEFAULT_HEIGHT_SCALE: f64 = 1.0;
+
+    pub fn new() -> Self {
+        Self {
+            height_scale: Self::DEFAULT_HEIGHT_SCALE,
+            wrap_enabled: false,
+        }
+    }
+
+    /// Sets the height of a value of 1.0 in the noise map, measured in
+    /// pixels. Curvatures of magnitude 1.0 or more are rendered as white or
+    /// black.
+    pub fn set_height_scale(self, height_scale: f64) -> Self {
+        Self {
+            height_scale,
+            ..self
+        }
+    }
+
+    pub fn height_scale(&self) -> f64 {
+        self.height_scale
+    }
+
+    pub fn enable_wrap(self) -> Self {
+        Self {
+            wrap_enabled: true,
+            ..self
+        }
+    }
+
+    pub fn wrap_enabled(&self) -> bool {
+        self.wrap_enabled
+    }
+
+    pub fn render(&self, noise_map: &NoiseMap) -> NoiseImage {
+        let heights = HeightField::new(noise_map, self.height_scale, self.wrap_enabled);
+        let (width, height) = noise_map.size();
+
+        let mut destination_image = NoiseImage::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let (x, y) = (x as isize, y as isize);
+
+                // The Laplacian is negative on convex areas.
+                let laplacian = heights.get(x - 1, y)
+                    + heights.get(x + 1, y)
+                    + heights.get(x, y - 1)
+                    + heights.get(x, y + 1)
+                    - 4.0 * heights.get(x, y);
+                let curvature = (-laplacian).clamp(-1.0, 1.0);
+
+                destination_image.set_value(x as usize, y as usize, grey(curvature * 0.5 + 0.5));
+            }
+        }
+
+        destination_image
+    }
+}
+
+impl Default for CurvatureRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renders a baked ambient occlusion map of a `NoiseMap` treated as a height
+/// field, as a greyscale image.
+///
+/// For each pixel, the height field is searched for the highest horizon in a
+/// number of directions around it, and the pixel is darkened by how much of
+/// the sky the horizons block. Unoccluded pixels are white.
+#[derive(Clone, Copy, Debug)]
+pub struct AmbientOcclusionRenderer {
+    height_scale: f64,
+    directions: usize,
+    radius: usize,
+    wrap_enabled: bool,
+}
+
+impl AmbientOcclusionRenderer {
+    pub const DEFAULT_HEIGHT_SCALE: f64 = 1.0;
+    pub const DEFAULT_DIRECTIONS: usize = 8;
+    pub const DEFAULT_RADIUS: usize = 16;
+
+    pub fn new() -> Self {
+        Self {
+            height_scale: Self::DEFAULT_HEIGHT_SCALE,
+            directions: Self::DEFAULT_DIRECTIONS,
+            radius: Self::DEFAULT_RADIUS,
+            wrap_enabled: false,
+        }
+    }
+
+    /// Sets the height of a value of 1.0 in the noise map, measured in
+    /// pixels.
+    pub fn set_height_scale(self, height_scale: f64) -> Self {
+        Self {
+            height_scale,
+            ..self
+        }
+    }
+
+    pub fn height_scale(&self) -> f64 {
+        self.height_scale
+    }
+
+    /// Sets the number of directions searched for the horizon of each pixel.
+    pub fn set_directions(self, directions: usize) -> Self {
+        Self {
+            directions: directions.max(1),
+            ..self
+        }
+    }
+
+    pub fn directions(&self) -> usize {
+        self.directions
+    }
+
+    /// Sets the distance, in pixels, up to which the horizon is searched.
+    pub fn set_radius(self, radius: usize) -> Self {
+        Self { radius, ..self }
+    }
+
+    pub fn radius(&self) -> usize {
+        self.radius
+    }
+
+    pub fn enable_wrap(self) -> Self {
+        Self {
+            wrap_enabled: true,
+            ..self
+        }
+    }
+
+    pub fn wrap_enabled(&self) -> bool {
+        self.wrap_enabled
+    }
+
+    pub fn render(&self, noise_map: &NoiseMap) -> NoiseImage {
+        let heights = HeightField::new(noise_map, self.height_scale, self.wrap_enabled);
+        let (width, height) = noise_map.size();
+
+        let mut destination_image = NoiseImage::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let center = heights.get(x as isize, y as isize);
+                let mut occlusion = 0.0;
+
+                for direction in 0..self.directions {
+                    let angle = 2.0 * PI * direction as f64 / self.directions as f64;
+                    let (step_y, step_x) = (math::sin(angle), math::cos(angle));
+
+                    // Track the tangent of the highest horizon in this direction.
+                    let mut horizon: f64 = 0.0;
+                    for distance in 1..=self.radius {
+                        let sample_x = (x as f64 + step_x * distance as f64).round() as isize;
+                        let sample_y = (y as f64 + step_y * distance as f64).round() as isize;
+
+                        if !self.wrap_enabled && !heights.contains(sample_x, sample_y) {
+                            break;
+                        }
+
+                        let rise = heights.get(sample_x, sample_y) - center;
+                        horizon = horizon.max(rise / distance as f64);
+                    }
+
+                    // The sine of the horizon angle is the fraction of the sky
+                    // blocked in this direction.
+                    occlusion += horizon / (horizon * horizon + 1.0).sqrt();
+                }
+
+                destination_image.set_value(x, y, grey(1.0 - occlusion / self.directions as f64));
+            }
+        }
+
+        destination_image
+    }
+}
+
+impl Default for AmbientOcclusionRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Scaled access to the values of a noise map, handling the edges of the map
+/// by either wrapping around or clamping.
+struct HeightField<'a> {
+    noise_map: &'a NoiseMap,
+    height_scale: f64,
+    wrap_enabled: bool,
+}
+
+impl<'a> HeightField<'a> {
+    fn new(noise_map: &'a NoiseMap, height_scale: f64, wrap_enabled: bool) -> Self {
+        Self {
+            noise_map,
+            height_scale,
+            wrap_enabled,
+        }
+    }
+
+    fn contains(&self, x: isize, y: isize) -> bool {
+        let (width, height) = self.noise_map.size();
+
+        x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height
+    }
+
+    fn get(&self, x: isize, y: isize) -> f64 {
+        let (width, height) = self.noise_map.size();
+
+        let (x, y) = if self.wrap_enabled {
+            (
+                x.rem_euclid(width as isize) as usize,
+                y.rem_euclid(height as isize) as usize,
+            )
+        } else {
+            (
+                x.clamp(0, width as isize - 1) as usize,
+                y.clamp(0, height as isize - 1) as usize,
+            )
+        };
+
+        self.noise_map.get_value(x, y) * self.height_scale
+    }
+
+    /// Returns the gradient of the height field along the columns and rows
+    /// of the map, using central differences.
+    fn gradient(&self, x: isize, y: isize) -> (f64, f64) {
+        (
+            (self.get(x + 1, y) - self.get(x - 1, y)) / 2.0,
+            (self.get(x, y + 1) - self.get(x, y - 1)) / 2.0,
+        )
+    }
+}
+
+fn unit_to_u8(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn grey(value: f64) -> Color {
+    let value = unit_to_u8(value);
+
+    [value, value, value, 255]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(width: usize, height: usize, slope: f64) -> NoiseMap {
+        let mut noise_map = NoiseMap::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                noise_map.set_value(x, y, x as f64 * slope);
+            }
+        }
+        noise_map
+    }
+
+    #[test]
+    fn test_flat_map() {
+        let noise_map = NoiseMap::new(8, 8);
+
+        assert_eq!(
+            NormalMapRenderer::new().render(&noise_map).get_value(3, 3),
+            [128, 128, 255, 255]
+        );
+        assert_eq!(
+            SlopeRenderer::new().render(&noise_map).get_value(3, 3),
+            [0, 0, 0, 255]
+        );
+        assert_eq!(
+            CurvatureRenderer::new().render(&noise_map).get_value(3, 3),
+            [128, 128, 128, 255]
+        );
+        assert_eq!(
+            AmbientOcclusionRenderer::new()
+                .render(&noise_map)
+                .get_value(3, 3),
+            [255, 255, 255, 255]
+        );
+    }
+
+    #[test]
+    fn test_ramp() {
+        let noise_map = ramp(8, 8, 1.0);
+
+        // A 45 degree slope rising to the right.
+        let normal = NormalMapRenderer::new().render(&noise_map).get_value(3, 3);
+        assert_eq!(normal, [37, 128, 218, 255]);
+        assert_eq!(
+            SlopeRenderer::new().render(&noise_map).get_value(3, 3),
+            [128, 128, 128, 255]
+        );
+
+        // Half of the directions face up the slope, so are occluded by the
+        // rising terrain.
+        let occlusion = AmbientOcclusionRenderer::new()
+            .render(&noise_map)
+            .get_value(3, 3);
+        assert!(occlusion[0] < 255);
+    }
+}